@@ -0,0 +1,240 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+//! Node.js bindings for code line count (clc)
+//!
+//! Exposes `countLines` and `countLinesSync` to JavaScript as a native
+//! module. Build it with `napi build --platform --release` from this
+//! directory.
+
+use code_line_count::{CLCError, CategoryRegistry, Counter};
+use napi::{Env, Error, Result, Status, Task, bindgen_prelude::AsyncTask};
+use napi_derive::napi;
+use std::path::PathBuf;
+
+/// Options for a count. Every field is optional.
+#[napi(object)]
+#[derive(Clone, Debug, Default)]
+pub struct CountOptions {
+    /// Paths to search; the current directory if none are given.
+    pub paths: Option<Vec<String>>,
+    /// Extensions to match, with or without the leading dot.
+    pub extensions: Option<Vec<String>>,
+    /// Built-in categories to match, such as `"rust"`.
+    pub categories: Option<Vec<String>>,
+    /// Whether to include hidden files and directories.
+    pub hidden: Option<bool>,
+    /// Whether to respect `.gitignore` files.
+    pub git_ignore: Option<bool>,
+    /// Whether to follow symlinks.
+    pub follow_links: Option<bool>,
+    /// Maximum search depth.
+    pub max_depth: Option<u32>,
+    /// Minimum depth of counted files.
+    pub min_depth: Option<u32>,
+    /// Whether to count files marked as generated, which are left out
+    /// otherwise.
+    pub include_generated: Option<bool>,
+    /// Whether to count minified files, which are left out otherwise.
+    pub include_minified: Option<bool>,
+}
+
+/// Lines counted in a single file.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct FileCount {
+    /// Path to the file.
+    pub path: String,
+    /// Non-empty lines in the file.
+    pub lines: i64,
+    /// Bytes read from the file.
+    pub bytes: i64,
+    /// Whether the file started with a UTF-8 BOM.
+    pub bom: bool,
+    /// Language a plugin reported for the file, if any.
+    pub language: Option<String>,
+    /// Encoding the file was converted from, if it was not UTF-8.
+    pub encoding: Option<String>,
+    /// Whether the file is marked as generated.
+    pub generated: bool,
+    /// Whether the file is minified.
+    pub minified: bool,
+}
+
+/// A matched file that could not be read.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct FileError {
+    /// Path to the file.
+    pub path: String,
+    /// Why the file could not be read.
+    pub message: String,
+}
+
+/// Result of a count.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct Report {
+    /// Total non-empty lines across the counted files.
+    pub total_lines: i64,
+    /// Per-file counts, sorted by path.
+    pub files: Vec<FileCount>,
+    /// Files matched, whether they were counted, vanished, could not be
+    /// read, or were left out.
+    pub files_matched: i64,
+    /// Bytes read from the counted files.
+    pub bytes_read: i64,
+    /// Directories visited.
+    pub dirs_visited: i64,
+    /// Files deleted while the walk was running.
+    pub vanished: i64,
+    /// Matched files that could not be read.
+    pub errors: Vec<FileError>,
+    /// Matched files left out because they are marked as generated,
+    /// sorted by path.
+    pub generated: Vec<String>,
+    /// Matched files left out because they are minified, sorted by path.
+    pub minified: Vec<String>,
+    /// Whether the count was cancelled before it finished.
+    pub cancelled: bool,
+}
+
+/// Converts paths to JavaScript strings.
+fn js_paths(paths: Vec<PathBuf>) -> Vec<String> {
+    paths
+        .into_iter()
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect()
+}
+
+/// Converts a count to a JavaScript number, saturating on overflow.
+fn js_number(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+impl From<code_line_count::Report> for Report {
+    fn from(report: code_line_count::Report) -> Self {
+        Self {
+            total_lines: js_number(report.total_lines()),
+            files_matched: js_number(report.files_matched()),
+            bytes_read: js_number(report.bytes_read()),
+            dirs_visited: js_number(report.dirs_visited),
+            vanished: js_number(report.vanished),
+            cancelled: report.cancelled,
+            files: report
+                .files
+                .into_iter()
+                .map(|file| FileCount {
+                    path: file.path.to_string_lossy().into_owned(),
+                    lines: js_number(file.lines),
+                    bytes: js_number(file.bytes),
+                    bom: file.bom,
+                    language: file.language,
+                    encoding: file.encoding.map(ToString::to_string),
+                    generated: file.generated,
+                    minified: file.minified,
+                })
+                .collect(),
+            errors: report
+                .errors
+                .into_iter()
+                .map(|error| FileError {
+                    path: error.path.to_string_lossy().into_owned(),
+                    message: error.message,
+                })
+                .collect(),
+            generated: js_paths(report.generated),
+            minified: js_paths(report.minified),
+        }
+    }
+}
+
+/// Builds the counter for a count's options.
+///
+/// # Errors
+///
+/// Returns an `InvalidArg` error for an unknown category.
+fn counter_of(options: CountOptions) -> Result<Counter> {
+    let registry = CategoryRegistry::builtin();
+    let mut counter = Counter::new()
+        .paths(options.paths.unwrap_or_default())
+        .extensions(options.extensions.unwrap_or_default())
+        .hidden(options.hidden.unwrap_or(false))
+        .git_ignore(options.git_ignore.unwrap_or(false))
+        .follow_links(options.follow_links.unwrap_or(false))
+        .include_generated(options.include_generated.unwrap_or(false))
+        .include_minified(options.include_minified.unwrap_or(false));
+
+    for name in options.categories.unwrap_or_default() {
+        let cat = registry
+            .lookup(&name)
+            .map_err(|err| Error::new(Status::InvalidArg, err.to_string()))?;
+        counter = counter.category(cat.clone());
+    }
+    if let Some(depth) = options.max_depth {
+        counter = counter.max_depth(depth as usize);
+    }
+    if let Some(depth) = options.min_depth {
+        counter = counter.min_depth(depth as usize);
+    }
+
+    Ok(counter)
+}
+
+/// Runs a counter, turning its error into a JavaScript one.
+fn run(counter: &Counter) -> Result<code_line_count::Report> {
+    counter.run().map_err(|err| {
+        let status = match err {
+            CLCError::InvalidDepth { .. } => Status::InvalidArg,
+            _ => Status::GenericFailure,
+        };
+        Error::new(status, err.to_string())
+    })
+}
+
+/// A count running on the libuv thread pool.
+pub struct CountTask {
+    /// The count to run.
+    counter: Counter,
+}
+
+impl Task for CountTask {
+    type Output = code_line_count::Report;
+    type JsValue = Report;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        run(&self.counter)
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(Report::from(output))
+    }
+}
+
+/// Counts the non-empty lines of the files matching the options, without
+/// blocking the event loop.
+///
+/// # Errors
+///
+/// Throws for an unknown category. The returned promise is rejected if
+/// the count fails.
+#[napi]
+pub fn count_lines(options: Option<CountOptions>) -> Result<AsyncTask<CountTask>> {
+    Ok(AsyncTask::new(CountTask {
+        counter: counter_of(options.unwrap_or_default())?,
+    }))
+}
+
+/// Counts the non-empty lines of the files matching the options, blocking
+/// until the count finishes.
+///
+/// # Errors
+///
+/// Throws for an unknown category or if the count fails.
+#[napi]
+pub fn count_lines_sync(options: Option<CountOptions>) -> Result<Report> {
+    run(&counter_of(options.unwrap_or_default())?).map(Report::from)
+}
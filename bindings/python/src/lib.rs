@@ -0,0 +1,242 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+//! Python bindings for code line count (clc)
+//!
+//! Exposes [`count_lines`] and the report types to Python as the `clc`
+//! module. Build it with `maturin develop` or `maturin build` from this
+//! directory.
+
+use code_line_count::{CLCError, CategoryRegistry, Counter};
+use pyo3::{
+    create_exception,
+    exceptions::{PyException, PyValueError},
+    prelude::*,
+};
+use std::{path::PathBuf, time::SystemTime};
+
+create_exception!(clc, ClcError, PyException, "A count failed.");
+
+/// Lines counted in a single file.
+#[pyclass(frozen, get_all, skip_from_py_object, module = "clc")]
+#[derive(Clone, Debug)]
+pub struct FileCount {
+    /// Path to the file.
+    path: PathBuf,
+    /// Non-empty lines in the file.
+    lines: u64,
+    /// Bytes read from the file.
+    bytes: u64,
+    /// Whether the file started with a UTF-8 BOM.
+    bom: bool,
+    /// Last modification time of the file, if known.
+    modified: Option<SystemTime>,
+    /// Language a plugin reported for the file, if any.
+    language: Option<String>,
+    /// Metrics a plugin reported for the file.
+    metrics: Vec<(String, u64)>,
+    /// Encoding the file was converted from, if it was not UTF-8.
+    encoding: Option<&'static str>,
+    /// Whether the file is marked as generated.
+    generated: bool,
+    /// Whether the file is minified.
+    minified: bool,
+}
+
+#[pymethods]
+impl FileCount {
+    fn __repr__(&self) -> String {
+        format!(
+            "FileCount(path={:?}, lines={})",
+            self.path.display().to_string(),
+            self.lines
+        )
+    }
+}
+
+impl From<code_line_count::FileCount> for FileCount {
+    fn from(file: code_line_count::FileCount) -> Self {
+        Self {
+            path: file.path,
+            lines: file.lines,
+            bytes: file.bytes,
+            bom: file.bom,
+            modified: file.modified,
+            language: file.language,
+            metrics: file.metrics,
+            encoding: file.encoding,
+            generated: file.generated,
+            minified: file.minified,
+        }
+    }
+}
+
+/// A matched file that could not be read.
+#[pyclass(frozen, get_all, skip_from_py_object, module = "clc")]
+#[derive(Clone, Debug)]
+pub struct FileError {
+    /// Path to the file.
+    path: PathBuf,
+    /// Why the file could not be read.
+    message: String,
+}
+
+#[pymethods]
+impl FileError {
+    fn __repr__(&self) -> String {
+        format!(
+            "FileError(path={:?}, message={:?})",
+            self.path.display().to_string(),
+            self.message
+        )
+    }
+}
+
+impl From<code_line_count::FileError> for FileError {
+    fn from(error: code_line_count::FileError) -> Self {
+        Self {
+            path: error.path,
+            message: error.message,
+        }
+    }
+}
+
+/// Result of a count.
+#[pyclass(frozen, get_all, skip_from_py_object, module = "clc")]
+#[derive(Clone, Debug)]
+pub struct Report {
+    /// Total non-empty lines across the counted files.
+    total_lines: u64,
+    /// Per-file counts, sorted by path.
+    files: Vec<FileCount>,
+    /// Files matched, whether they were counted, vanished, could not be
+    /// read, or were left out.
+    files_matched: u64,
+    /// Bytes read from the counted files.
+    bytes_read: u64,
+    /// Directories visited.
+    dirs_visited: u64,
+    /// Files deleted while the walk was running.
+    vanished: u64,
+    /// Matched files that could not be read.
+    errors: Vec<FileError>,
+    /// Matched files left out because they are marked as generated,
+    /// sorted by path.
+    generated: Vec<PathBuf>,
+    /// Matched files left out because they are minified, sorted by path.
+    minified: Vec<PathBuf>,
+    /// Whether the count was cancelled before it finished.
+    cancelled: bool,
+}
+
+#[pymethods]
+impl Report {
+    fn __repr__(&self) -> String {
+        format!(
+            "Report(total_lines={}, files={})",
+            self.total_lines,
+            self.files.len()
+        )
+    }
+}
+
+impl From<code_line_count::Report> for Report {
+    fn from(report: code_line_count::Report) -> Self {
+        Self {
+            total_lines: report.total_lines(),
+            files_matched: report.files_matched(),
+            bytes_read: report.bytes_read(),
+            dirs_visited: report.dirs_visited,
+            vanished: report.vanished,
+            cancelled: report.cancelled,
+            files: report.files.into_iter().map(FileCount::from).collect(),
+            errors: report.errors.into_iter().map(FileError::from).collect(),
+            generated: report.generated,
+            minified: report.minified,
+        }
+    }
+}
+
+/// Counts the non-empty lines of the files under `paths` matching any of
+/// `extensions` or `categories`.
+///
+/// Without any path, the current directory is searched. Categories are
+/// the built-in ones, such as `"rust"`. The GIL is released while
+/// counting.
+///
+/// Raises `ValueError` for an unknown category or a `min_depth` greater
+/// than `max_depth`, and `ClcError` if the count fails.
+#[pyfunction]
+#[pyo3(signature = (
+    paths = vec![],
+    extensions = vec![],
+    categories = vec![],
+    *,
+    hidden = false,
+    git_ignore = false,
+    follow_links = false,
+    max_depth = None,
+    min_depth = None,
+    include_generated = false,
+    include_minified = false,
+))]
+#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
+fn count_lines(
+    py: Python<'_>,
+    paths: Vec<PathBuf>,
+    extensions: Vec<String>,
+    categories: Vec<String>,
+    hidden: bool,
+    git_ignore: bool,
+    follow_links: bool,
+    max_depth: Option<usize>,
+    min_depth: Option<usize>,
+    include_generated: bool,
+    include_minified: bool,
+) -> PyResult<Report> {
+    let registry = CategoryRegistry::builtin();
+    let mut counter = Counter::new()
+        .paths(paths)
+        .extensions(extensions)
+        .hidden(hidden)
+        .git_ignore(git_ignore)
+        .follow_links(follow_links)
+        .include_generated(include_generated)
+        .include_minified(include_minified);
+    for name in categories {
+        let cat = registry
+            .lookup(&name)
+            .map_err(|err| PyValueError::new_err(err.to_string()))?;
+        counter = counter.category(cat.clone());
+    }
+    if let Some(depth) = max_depth {
+        counter = counter.max_depth(depth);
+    }
+    if let Some(depth) = min_depth {
+        counter = counter.min_depth(depth);
+    }
+
+    py.detach(|| counter.run())
+        .map(Report::from)
+        .map_err(|err| match err {
+            CLCError::InvalidDepth { .. } => PyValueError::new_err(err.to_string()),
+            _ => ClcError::new_err(err.to_string()),
+        })
+}
+
+/// Code line count (clc)
+///
+/// Counts the non-empty lines of code in files matching given categories
+/// or file extensions, recursively.
+#[pymodule]
+fn clc(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(count_lines, m)?)?;
+    m.add_class::<Report>()?;
+    m.add_class::<FileCount>()?;
+    m.add_class::<FileError>()?;
+    m.add("ClcError", m.py().get_type::<ClcError>())?;
+    Ok(())
+}
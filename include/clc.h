@@ -0,0 +1,88 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Copyright (c) 2026 c1ph3rC4t */
+
+#ifndef CLC_H
+#define CLC_H
+
+/* Generated by cbindgen from src/ffi.rs; do not edit. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// Outcome of a call.
+typedef enum ClcStatus {
+  // The count finished.
+  CLC_OK = 0,
+  // A required pointer was null, or a path or extension was not valid.
+  CLC_INVALID_ARGUMENT = 1,
+  // The count failed.
+  CLC_COUNT_FAILED = 2,
+  // The count panicked.
+  CLC_PANICKED = 3,
+} ClcStatus;
+
+// Options for a count. A null pointer stands for all fields being false.
+typedef struct ClcOptions {
+  // Whether to include hidden files and directories.
+  bool hidden;
+  // Whether to respect `.gitignore` files.
+  bool git_ignore;
+  // Whether to follow symlinks.
+  bool follow_links;
+  // Whether to count files marked as generated, which are left out
+  // otherwise.
+  bool include_generated;
+  // Whether to count minified files, which are left out otherwise.
+  bool include_minified;
+} ClcOptions;
+
+// Totals of a count.
+typedef struct ClcCount {
+  // Non-empty lines in the counted files.
+  uint64_t lines;
+  // Files counted.
+  uint64_t files;
+  // Bytes read from the counted files.
+  uint64_t bytes;
+  // Directories visited.
+  uint64_t dirs;
+  // Matched files that could not be read.
+  uint64_t errors;
+  // Matched files left out because they are marked as generated.
+  uint64_t generated;
+  // Matched files left out because they are minified.
+  uint64_t minified;
+} ClcCount;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Counts the non-empty lines of the files under `roots` whose extension,
+// given with or without its leading dot, is one of `exts`.
+//
+// Without any root, the current directory is searched. The totals are
+// written to `out` when the count finishes; it is left untouched
+// otherwise.
+//
+// # Safety
+//
+// `roots` and `exts` must each be null with a length of 0, or point to
+// that many NUL-terminated strings. `options` must be null or point to a
+// [`ClcOptions`], and `out` must point to a writable [`ClcCount`].
+enum ClcStatus clc_count_lines(const char *const *roots,
+                               size_t roots_len,
+                               const char *const *exts,
+                               size_t exts_len,
+                               const struct ClcOptions *options,
+                               struct ClcCount *out);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* CLC_H */
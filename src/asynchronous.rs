@@ -0,0 +1,206 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+#[cfg(feature = "plugins")]
+use crate::count_claimed;
+use crate::{
+    CLCError, CountOptions, LineCounter, Outcome, Report, check_depth, classifier_of,
+    collect_outcomes, count_contents, line_counter_of, outcome_of, walk_matches,
+};
+use ignore::WalkState::{Continue, Quit};
+use std::{
+    ffi::{OsStr, OsString},
+    io, panic,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use tokio::{
+    fs::File,
+    io::AsyncReadExt,
+    sync::mpsc,
+    task::{self, JoinError, JoinSet},
+};
+
+/// What the walker found for a single matched file.
+enum Found {
+    /// A file to read and count, possibly through a resolved link.
+    Matched(PathBuf, Option<PathBuf>),
+    /// A file a plugin claimed and the walker already counted, or an
+    /// entry the walker could not walk.
+    Done(Outcome),
+}
+
+/// Counts non-empty lines like [`count_lines`](crate::count_lines), inside
+/// an async runtime.
+///
+/// The directory walk runs on a blocking thread, while matched files are
+/// read with async I/O and counted on blocking threads, at most
+/// `concurrency` of them at once. A
+/// `concurrency` of 0 is taken as 1. [`CountOptions::max_memory`] is not
+/// applied; lower `concurrency` to hold fewer files in memory. Files
+/// claimed by plugins are read and counted on the walker's threads.
+///
+/// Requires a Tokio runtime.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if regex compilation fails, or
+/// [`CLCError::InvalidDepth`] if [`CountOptions::min_depth`] is greater than
+/// [`CountOptions::max_depth`].
+///
+/// # Example
+///
+/// ```
+/// # use code_line_count::{CountOptions, count_lines_async};
+/// # use std::{ffi::OsStr, path::PathBuf};
+/// # tokio::runtime::Builder::new_current_thread()
+/// #     .build()
+/// #     .unwrap()
+/// #     .block_on(async {
+/// let report = count_lines_async(
+///     &[PathBuf::from("src")],
+///     &[OsStr::new("rs")],
+///     &CountOptions::default(),
+///     64,
+/// )
+/// .await?;
+/// println!("{} lines", report.total_lines());
+/// # Ok::<(), code_line_count::CLCError>(())
+/// # })?;
+/// # Ok::<(), code_line_count::CLCError>(())
+/// ```
+pub async fn count_lines_async(
+    roots: &[PathBuf],
+    exts: &[&OsStr],
+    options: &CountOptions,
+    concurrency: usize,
+) -> Result<Report, CLCError> {
+    check_depth(options)?;
+    let counter = line_counter_of(options)?;
+    let concurrency = concurrency.max(1);
+    let (tx, mut rx) = mpsc::channel(concurrency);
+
+    let walker = {
+        let roots = roots.to_vec();
+        let exts: Vec<OsString> = exts.iter().map(|ext| ext.to_os_string()).collect();
+        let options = options.clone();
+        #[cfg(feature = "plugins")]
+        let counter = counter.clone();
+
+        task::spawn_blocking(move || {
+            let exts: Vec<&OsStr> = exts.iter().map(OsString::as_os_str).collect();
+            walk_matches(
+                &roots,
+                &exts,
+                &options,
+                |matched| {
+                    let path = matched.entry.path();
+
+                    #[cfg(feature = "plugins")]
+                    let found = match matched.claim {
+                        Some(claim) => Found::Done(outcome_of(
+                            path,
+                            count_claimed(claim, path, &counter, classifier_of(&options).as_ref()),
+                            matched.target,
+                        )),
+                        None => Found::Matched(path.to_path_buf(), matched.target),
+                    };
+                    #[cfg(not(feature = "plugins"))]
+                    let found = Found::Matched(path.to_path_buf(), matched.target);
+
+                    if tx.blocking_send(found).is_err() {
+                        return Quit;
+                    }
+
+                    Continue
+                },
+                |error| {
+                    let _ = tx.blocking_send(Found::Done(Outcome::Failed(error)));
+                },
+            )
+        })
+    };
+
+    let mut tasks = JoinSet::new();
+    let mut outcomes = vec![];
+
+    while let Some(found) = rx.recv().await {
+        let (path, target) = match found {
+            Found::Matched(path, target) => (path, target),
+            Found::Done(outcome) => {
+                outcomes.push(outcome);
+                continue;
+            }
+        };
+
+        if tasks.len() >= concurrency
+            && let Some(outcome) = tasks.join_next().await
+        {
+            outcomes.extend(joined(outcome));
+        }
+
+        let counter = counter.clone();
+        let classifier = classifier_of(options);
+        tasks.spawn(async move {
+            let count = match read_file(&path).await {
+                Ok((bytes, modified)) => {
+                    let path = path.clone();
+                    joined(
+                        task::spawn_blocking(move || {
+                            count_contents(
+                                &path,
+                                &bytes,
+                                modified,
+                                classifier.as_ref(),
+                                |contents| counter.count_file(&path, contents),
+                            )
+                        })
+                        .await,
+                    )
+                    .ok_or_else(|| io::Error::other("count cancelled"))
+                }
+                Err(err) => Err(err),
+            };
+            outcome_of(&path, count, target)
+        });
+    }
+
+    while let Some(outcome) = tasks.join_next().await {
+        outcomes.extend(joined(outcome));
+    }
+    let dirs_visited = joined(walker.await).unwrap_or(0);
+
+    Ok(Report {
+        dirs_visited,
+        ..collect_outcomes(outcomes, options)
+    })
+}
+
+/// Reads a single file with async I/O, getting its contents and when it
+/// was last modified.
+///
+/// # Errors
+///
+/// Returns the I/O error if the file cannot be read.
+async fn read_file(path: &Path) -> io::Result<(Vec<u8>, Option<SystemTime>)> {
+    let mut file = File::open(path).await?;
+    let meta = file.metadata().await?;
+
+    let mut bytes = Vec::with_capacity(usize::try_from(meta.len()).unwrap_or(0));
+    file.read_to_end(&mut bytes).await?;
+
+    Ok((bytes, meta.modified().ok()))
+}
+
+/// Gets the result of a finished task, resuming its panic if it panicked,
+/// or `None` if it was cancelled, as when the runtime shuts down.
+fn joined<T>(result: Result<T, JoinError>) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(err) if err.is_cancelled() => None,
+        Err(err) => panic::resume_unwind(err.into_panic()),
+    }
+}
@@ -0,0 +1,77 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use std::sync::{Condvar, Mutex, PoisonError};
+
+/// Limits the total number of bytes held in memory by walker threads.
+///
+/// Threads reserve the size of a file before reading it and block while
+/// the budget is exhausted, so concurrency shrinks automatically when
+/// large files are in flight.
+#[derive(Debug)]
+pub struct MemoryBudget {
+    limit: u64,
+    in_use: Mutex<u64>,
+    freed: Condvar,
+}
+
+/// Bytes reserved from a [`MemoryBudget`], released on drop.
+#[derive(Debug)]
+pub struct Reservation<'a> {
+    budget: &'a MemoryBudget,
+    bytes: u64,
+}
+
+impl MemoryBudget {
+    /// Creates a budget allowing at most `limit` bytes in flight.
+    #[must_use]
+    pub const fn new(limit: u64) -> Self {
+        Self {
+            limit,
+            in_use: Mutex::new(0),
+            freed: Condvar::new(),
+        }
+    }
+
+    /// Blocks until `bytes` fit in the budget, then reserves them.
+    ///
+    /// A request larger than the whole budget waits until nothing else is
+    /// in flight and then runs alone, so the limit is only ever exceeded by
+    /// a single oversized file.
+    pub fn reserve(&self, bytes: u64) -> Reservation<'_> {
+        let bytes = bytes.min(self.limit);
+        let mut in_use = self.in_use.lock().unwrap_or_else(PoisonError::into_inner);
+
+        while *in_use > 0 && in_use.saturating_add(bytes) > self.limit {
+            in_use = self
+                .freed
+                .wait(in_use)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+
+        *in_use = in_use.saturating_add(bytes);
+        drop(in_use);
+
+        Reservation {
+            budget: self,
+            bytes,
+        }
+    }
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        {
+            let mut in_use = self
+                .budget
+                .in_use
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            *in_use = in_use.saturating_sub(self.bytes);
+        }
+        self.budget.freed.notify_all();
+    }
+}
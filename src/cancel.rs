@@ -0,0 +1,66 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering::Relaxed},
+};
+
+/// Flag that stops a running count when set.
+///
+/// Clones share the flag, so one clone can be passed to the count through
+/// [`CountOptions::cancel`](crate::CountOptions::cancel) while another
+/// cancels it from elsewhere. Walker threads check the flag before every
+/// entry and stop once it is set, so the count returns a partial
+/// [`Report`](crate::Report) marked as cancelled.
+///
+/// Tokens compare equal if they share a flag.
+///
+/// # Example
+///
+/// ```
+/// use code_line_count::{CancellationToken, CategoryID, Counter};
+///
+/// let token = CancellationToken::new();
+/// let counter = Counter::new()
+///     .path("src")
+///     .category(CategoryID::Rust)
+///     .cancel(token.clone());
+///
+/// token.cancel();
+/// let report = counter.run()?;
+/// assert!(report.cancelled);
+/// # Ok::<(), code_line_count::CLCError>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Creates a token that is not cancelled.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels every count using this token or a clone of it.
+    pub fn cancel(&self) {
+        self.0.store(true, Relaxed);
+    }
+
+    /// Checks whether the token was cancelled.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Relaxed)
+    }
+}
+
+impl PartialEq for CancellationToken {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for CancellationToken {}
@@ -10,13 +10,20 @@
 /// The generated enum derives [`Clone`], [`Copy`], [`Debug`], [`PartialEq`], and [`Eq`].
 /// `from_name` is case-sensitive.
 ///
+/// A category may give the comment syntax of its extensions, as
+/// [`CommentSyntax`](crate::comments::CommentSyntax) constants each
+/// followed by the extensions written with it.
+///
 /// # Example
 ///
 /// ```
+/// use code_line_count::{comments::CommentSyntax, define_categories};
+///
 /// define_categories! {
 ///     Rust => {
 ///         names: ["rust", "rs"],
 ///         extensions: ["rs", "rlib"],
+///         comments: { RUST: ["rs"] },
 ///     },
 ///     Haskell => {
 ///         names: ["haskell", "hs"],
@@ -30,6 +37,10 @@
 ///
 /// // Get extensions for a category
 /// assert_eq!(CategoryID::Haskell.extensions(), &["hs", "lhs"]);
+///
+/// // Get the comment syntax of an extension
+/// assert_eq!(CategoryID::Rust.comment_syntax("rs"), Some(CommentSyntax::RUST));
+/// assert_eq!(CategoryID::Haskell.comment_syntax("hs"), None);
 /// ```
 #[macro_export]
 macro_rules! define_categories {
@@ -38,6 +49,9 @@ macro_rules! define_categories {
             $variant:ident => {
                 names: [$($name:literal),+ $(,)?],
                 extensions: [$($ext:literal),* $(,)?],
+                $(comments: {
+                    $($syntax:ident: [$($syntax_ext:literal),+ $(,)?]),* $(,)?
+                },)?
             }
         ),+ $(,)?
     ) => {
@@ -80,6 +94,23 @@ macro_rules! define_categories {
                     $(Self::$variant => &[$($ext),*]),+
                 }
             }
+
+            /// Gets the comment syntax of the files with extension `ext`
+            /// in the category, if it gives one.
+            #[allow(unreachable_patterns)]
+            pub fn comment_syntax(
+                self,
+                ext: &str,
+            ) -> Option<$crate::comments::CommentSyntax> {
+                match self {
+                    $(Self::$variant => match ext {
+                        $($($($syntax_ext)|+ => {
+                            Some($crate::comments::CommentSyntax::$syntax)
+                        })*)?
+                        _ => None,
+                    }),+
+                }
+            }
         }
     };
 }
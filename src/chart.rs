@@ -0,0 +1,104 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use std::fmt::Write;
+
+/// Block characters for bars, from one eighth to a full cell.
+const BLOCKS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+/// ANSI foreground colors cycled through for successive bars.
+const COLORS: [u8; 6] = [34, 32, 33, 35, 36, 31];
+
+/// Characters bars are drawn with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChartStyle {
+    /// Unicode block characters, with eighth-of-a-cell precision.
+    #[default]
+    Blocks,
+    /// `#` characters, rounded to whole cells, for terminals and fonts
+    /// without block characters.
+    Ascii,
+}
+
+impl ChartStyle {
+    /// Gets a style from its command line name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "blocks" => Some(Self::Blocks),
+            "ascii" => Some(Self::Ascii),
+            _ => None,
+        }
+    }
+}
+
+/// Renders a bar `eighths` eighths of a cell long.
+fn bar(eighths: u64, style: ChartStyle) -> String {
+    let full = usize::try_from(eighths / 8).unwrap_or(0);
+    match style {
+        ChartStyle::Blocks => {
+            let mut bar = BLOCKS[7].to_string().repeat(full);
+            if let Some(rem) = (eighths % 8).checked_sub(1) {
+                bar.push(BLOCKS[usize::try_from(rem).unwrap_or(0)]);
+            }
+            bar
+        }
+        ChartStyle::Ascii => "#".repeat(full + usize::from(eighths % 8 >= 4)),
+    }
+}
+
+/// Renders a horizontal bar chart of each entry's share of the total.
+///
+/// Entries are `(label, value)` pairs and keep their order. The longest
+/// possible bar is `width` cells, drawn in `style`; with `color`, bars
+/// cycle through ANSI colors.
+#[must_use]
+pub fn render_chart(
+    entries: &[(String, u64)],
+    width: usize,
+    style: ChartStyle,
+    color: bool,
+) -> String {
+    let total: u128 = entries.iter().map(|(_, value)| u128::from(*value)).sum();
+    let label_width = entries
+        .iter()
+        .map(|(label, _)| label.len())
+        .max()
+        .unwrap_or(0);
+    let value_width = entries
+        .iter()
+        .map(|(_, value)| value.to_string().len())
+        .max()
+        .unwrap_or(0);
+
+    let mut chart = String::new();
+    for (idx, (label, value)) in entries.iter().enumerate() {
+        let value_wide = u128::from(*value);
+        let eighths = (value_wide * (width as u128) * 8)
+            .checked_div(total)
+            .unwrap_or(0);
+        let tenths_of_percent = (value_wide * 1000 + total / 2)
+            .checked_div(total)
+            .unwrap_or(0);
+
+        let bar = bar(u64::try_from(eighths).unwrap_or(0), style);
+        let padding = " ".repeat(width.saturating_sub(bar.chars().count()));
+        let bar = if color {
+            format!("\x1b[{}m{bar}\x1b[0m", COLORS[idx % COLORS.len()])
+        } else {
+            bar
+        };
+
+        let _ = writeln!(
+            chart,
+            "{label:<label_width$}  {bar}{padding}  {:>3}.{}%  {value:>value_width$}",
+            tenths_of_percent / 10,
+            tenths_of_percent % 10,
+        );
+    }
+
+    chart
+}
@@ -0,0 +1,162 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use crate::csv;
+use code_line_count::{
+    UTF8_BOM,
+    comments::LineClassifier,
+    report::{FileCount, Report},
+};
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    fs,
+    io::{self, Write},
+};
+
+/// Width of cloc's text tables.
+const WIDTH: usize = 79;
+
+/// Counts of one language, split the way cloc reports them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ClocCounts {
+    /// Language name.
+    pub language: String,
+    /// Number of files.
+    pub files: u64,
+    /// Lines with only whitespace.
+    pub blank: u64,
+    /// Lines with only comments.
+    pub comment: u64,
+    /// Remaining lines.
+    pub code: u64,
+}
+
+impl ClocCounts {
+    /// Adds the counts of another group.
+    const fn add(&mut self, other: &Self) {
+        self.files = self.files.saturating_add(other.files);
+        self.blank = self.blank.saturating_add(other.blank);
+        self.comment = self.comment.saturating_add(other.comment);
+        self.code = self.code.saturating_add(other.code);
+    }
+}
+
+/// Splits the counted files' lines into blank, comment, and code lines per
+/// language, sorted by code lines like cloc.
+///
+/// Files the count already split are taken as they are; others are read
+/// again and split by [`LineClassifier`]. Files that vanished since the
+/// count are skipped. `language_of` attributes each file to a language.
+///
+/// # Errors
+///
+/// Returns the I/O error if a file cannot be read.
+pub fn cloc_counts(
+    report: &Report,
+    language_of: impl Fn(&FileCount) -> String,
+) -> io::Result<Vec<ClocCounts>> {
+    let mut languages: BTreeMap<String, ClocCounts> = BTreeMap::new();
+
+    for file in &report.files {
+        let kinds = match file.kinds {
+            Some(kinds) => kinds,
+            None => match fs::read(&file.path) {
+                Ok(bytes) => LineClassifier::default()
+                    .classify_file(&file.path, bytes.strip_prefix(UTF8_BOM).unwrap_or(&bytes)),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            },
+        };
+
+        let language = language_of(file);
+        let counts = languages
+            .entry(language.clone())
+            .or_insert_with(|| ClocCounts {
+                language,
+                ..ClocCounts::default()
+            });
+        counts.files += 1;
+        counts.blank = counts.blank.saturating_add(kinds.blank);
+        counts.comment = counts.comment.saturating_add(kinds.comment);
+        counts.code = counts.code.saturating_add(kinds.code);
+    }
+
+    let mut counts: Vec<ClocCounts> = languages.into_values().collect();
+    counts.sort_by_key(|counts| Reverse(counts.code));
+    Ok(counts)
+}
+
+/// Sums the counts of every language into cloc's `SUM` row.
+fn sum(counts: &[ClocCounts], name: &str) -> ClocCounts {
+    counts.iter().fold(
+        ClocCounts {
+            language: name.to_string(),
+            ..ClocCounts::default()
+        },
+        |mut sum, counts| {
+            sum.add(counts);
+            sum
+        },
+    )
+}
+
+/// Writes per-language counts as cloc's default text table, after a
+/// `banner` line naming the tool.
+///
+/// # Errors
+///
+/// Returns the I/O error if writing fails.
+pub fn write_cloc_text(
+    out: &mut impl Write,
+    counts: &[ClocCounts],
+    banner: &str,
+) -> io::Result<()> {
+    let rule = "-".repeat(WIDTH);
+    let row = |counts: &ClocCounts| {
+        format!(
+            "{:<27} {:>6} {:>14} {:>14} {:>14}",
+            counts.language, counts.files, counts.blank, counts.comment, counts.code
+        )
+    };
+
+    writeln!(out, "{banner}\n{rule}")?;
+    writeln!(
+        out,
+        "{:<27} {:>6} {:>14} {:>14} {:>14}\n{rule}",
+        "Language", "files", "blank", "comment", "code"
+    )?;
+    for counts in counts {
+        writeln!(out, "{}", row(counts))?;
+    }
+    writeln!(out, "{rule}\n{}\n{rule}", row(&sum(counts, "SUM:")))
+}
+
+/// Writes per-language counts as cloc's `--csv` layout, with `banner` as
+/// the last header field and a `SUM` row at the end.
+///
+/// # Errors
+///
+/// Returns the I/O error if writing fails.
+pub fn write_cloc_csv(out: &mut impl Write, counts: &[ClocCounts], banner: &str) -> io::Result<()> {
+    csv::write_row(
+        out,
+        &["files", "language", "blank", "comment", "code", banner],
+    )?;
+    for counts in counts.iter().chain([&sum(counts, "SUM")]) {
+        csv::write_row(
+            out,
+            &[
+                counts.files.to_string(),
+                counts.language.clone(),
+                counts.blank.to_string(),
+                counts.comment.to_string(),
+                counts.code.to_string(),
+            ],
+        )?;
+    }
+    Ok(())
+}
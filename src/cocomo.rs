@@ -0,0 +1,119 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+/// Average yearly wage of a developer, as assumed by scc.
+pub const DEFAULT_AVG_WAGE: f64 = 56_286.0;
+
+/// Multiple of wages a project costs once overhead is included, as assumed
+/// by scc.
+pub const DEFAULT_OVERHEAD: f64 = 2.4;
+
+/// Basic COCOMO project types, which set the model's coefficients.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Model {
+    /// Small teams with good experience and flexible requirements.
+    #[default]
+    Organic,
+    /// Medium teams with mixed experience and requirements.
+    SemiDetached,
+    /// Tight hardware, software, and operational constraints.
+    Embedded,
+}
+
+impl Model {
+    /// Gets a project type from its command line name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "organic" => Some(Self::Organic),
+            "semi-detached" => Some(Self::SemiDetached),
+            "embedded" => Some(Self::Embedded),
+            _ => None,
+        }
+    }
+
+    /// Gets the command line name of the project type.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Organic => "organic",
+            Self::SemiDetached => "semi-detached",
+            Self::Embedded => "embedded",
+        }
+    }
+
+    /// Gets the effort coefficient and exponent, then the schedule
+    /// coefficient and exponent.
+    const fn coefficients(self) -> (f64, f64, f64, f64) {
+        match self {
+            Self::Organic => (2.4, 1.05, 2.5, 0.38),
+            Self::SemiDetached => (3.0, 1.12, 2.5, 0.35),
+            Self::Embedded => (3.6, 1.20, 2.5, 0.32),
+        }
+    }
+}
+
+/// Parameters of an estimate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cocomo {
+    /// Project type.
+    pub model: Model,
+    /// Average yearly wage of a developer.
+    pub avg_wage: f64,
+    /// Multiple of wages the project costs once overhead is included.
+    pub overhead: f64,
+    /// Effort adjustment factor, scaling the effort for cost drivers the
+    /// basic model leaves out.
+    pub eaf: f64,
+}
+
+impl Default for Cocomo {
+    fn default() -> Self {
+        Self {
+            model: Model::default(),
+            avg_wage: DEFAULT_AVG_WAGE,
+            overhead: DEFAULT_OVERHEAD,
+            eaf: 1.0,
+        }
+    }
+}
+
+/// Estimated effort, schedule, and cost of writing some code.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Estimate {
+    /// Effort in person-months.
+    pub effort: f64,
+    /// Development time in months.
+    pub schedule: f64,
+    /// Average number of developers over the schedule.
+    pub people: f64,
+    /// Cost, in the currency of the wage.
+    pub cost: f64,
+}
+
+impl Cocomo {
+    /// Estimates what writing `lines` source lines takes, with the basic
+    /// COCOMO model.
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn estimate(&self, lines: u64) -> Estimate {
+        let (a, b, c, d) = self.model.coefficients();
+        let effort = a * (lines as f64 / 1000.0).powf(b) * self.eaf;
+        let schedule = c * effort.powf(d);
+        let people = if schedule > 0.0 {
+            effort / schedule
+        } else {
+            0.0
+        };
+
+        Estimate {
+            effort,
+            schedule,
+            people,
+            cost: effort * self.avg_wage / 12.0 * self.overhead,
+        }
+    }
+}
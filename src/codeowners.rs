@@ -0,0 +1,96 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Places a CODEOWNERS file is looked for, relative to the repository root,
+/// in the order GitHub checks them.
+const LOCATIONS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// One CODEOWNERS rule: a gitignore-style pattern and its owners.
+#[derive(Clone, Debug)]
+struct Rule {
+    /// Matcher for the rule's pattern alone.
+    pattern: Gitignore,
+    /// Owners of matching files, empty if the rule removes ownership.
+    owners: Vec<String>,
+}
+
+/// Ownership rules of a repository, from its CODEOWNERS file.
+#[derive(Clone, Debug)]
+pub struct CodeOwners {
+    /// The repository root patterns are relative to.
+    root: PathBuf,
+    /// Rules in file order; the last matching rule wins.
+    rules: Vec<Rule>,
+}
+
+impl CodeOwners {
+    /// Reads the CODEOWNERS file of the repository at `root`, if it has one.
+    ///
+    /// Lines whose pattern is invalid are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns the I/O error if the file exists but cannot be read.
+    pub fn find(root: &Path) -> io::Result<Option<Self>> {
+        let Some(path) = LOCATIONS
+            .iter()
+            .map(|location| root.join(location))
+            .find(|path| path.is_file())
+        else {
+            return Ok(None);
+        };
+
+        let rules = fs::read_to_string(path)?
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut words = line.split_whitespace();
+                let mut builder = GitignoreBuilder::new(root);
+                builder.add_line(None, words.next()?).ok()?;
+                Some(Rule {
+                    pattern: builder.build().ok()?,
+                    owners: words
+                        .take_while(|word| !word.starts_with('#'))
+                        .map(ToString::to_string)
+                        .collect(),
+                })
+            })
+            .collect();
+
+        Ok(Some(Self {
+            root: root.to_path_buf(),
+            rules,
+        }))
+    }
+
+    /// Gets the owners of a file, given by its path under the root or
+    /// relative to it. Files no rule assigns have no owners.
+    #[must_use]
+    pub fn owners(&self, path: &Path) -> &[String] {
+        let path = path.strip_prefix(&self.root).unwrap_or(path);
+        let path = path.strip_prefix(".").unwrap_or(path);
+        if path.has_root() {
+            return &[];
+        }
+
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| {
+                rule.pattern
+                    .matched_path_or_any_parents(path, false)
+                    .is_ignore()
+            })
+            .map_or(&[], |rule| &rule.owners)
+    }
+}
@@ -0,0 +1,64 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use std::{env, fmt::Display};
+
+/// Bold text.
+pub const BOLD: &str = "1";
+/// Red text.
+pub const RED: &str = "31";
+/// Yellow text.
+pub const YELLOW: &str = "33";
+/// Cyan text.
+pub const CYAN: &str = "36";
+
+/// When to color output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Color output going to a terminal, unless `NO_COLOR` is set.
+    #[default]
+    Auto,
+    /// Always color output.
+    Always,
+    /// Never color output.
+    Never,
+}
+
+impl ColorChoice {
+    /// Gets a choice from its command line name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "auto" => Some(Self::Auto),
+            "always" => Some(Self::Always),
+            "never" => Some(Self::Never),
+            _ => None,
+        }
+    }
+
+    /// Checks whether to color a stream, given whether it is a terminal.
+    ///
+    /// Following <https://no-color.org>, a non-empty `NO_COLOR` turns
+    /// automatic coloring off.
+    #[must_use]
+    pub fn enabled(self, is_terminal: bool) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => is_terminal && env::var_os("NO_COLOR").is_none_or(|v| v.is_empty()),
+        }
+    }
+}
+
+/// Wraps text in an ANSI SGR sequence, such as [`BOLD`], if `on`.
+#[must_use]
+pub fn paint(text: impl Display, sgr: &str, on: bool) -> String {
+    if on {
+        format!("\x1b[{sgr}m{text}\x1b[0m")
+    } else {
+        text.to_string()
+    }
+}
@@ -0,0 +1,444 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use crate::{
+    BlankChars, Category, CategoryID, license::strip_license_header_in, report::LineKinds,
+    shebang::script_extension,
+};
+use std::{ffi::OsStr, ops::Range, path::Path};
+
+/// How a language writes comments.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommentSyntax {
+    /// Markers starting a comment that runs to the end of the line.
+    pub line: &'static [&'static str],
+    /// Markers opening and closing a block comment.
+    pub block: &'static [(&'static str, &'static str)],
+    /// Whether block comments nest, so each opening marker inside one
+    /// needs its own closing marker.
+    pub nested: bool,
+    /// Quotes opening and closing a docstring: a string written as the
+    /// first statement of a module or a block, documenting it.
+    pub docstrings: &'static [&'static str],
+}
+
+/// A block comment still open at the end of a line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct OpenBlock {
+    /// Index of its markers in [`CommentSyntax::block`].
+    markers: usize,
+    /// Number of closing markers needed to end it.
+    depth: usize,
+}
+
+impl CommentSyntax {
+    /// Scans a line for code outside comments, starting inside `open` if
+    /// a block comment is open, and leaving it set to the block comment
+    /// still open at the end of the line. `on_code` is called with the
+    /// index of each byte of code outside strings.
+    ///
+    /// Comment markers inside double-quoted strings are skipped, but
+    /// other string syntax, such as single quotes or raw strings, is not
+    /// known.
+    fn scan_line(
+        &self,
+        line: &[u8],
+        open: &mut Option<OpenBlock>,
+        mut on_code: impl FnMut(usize),
+    ) -> bool {
+        let mut code = false;
+        let mut in_string = false;
+        let mut idx = 0;
+
+        while idx < line.len() {
+            let rest = &line[idx..];
+            if let Some(block) = open {
+                let (opening, closing) = self.block[block.markers];
+                if rest.starts_with(closing.as_bytes()) {
+                    idx += closing.len();
+                    block.depth -= 1;
+                    if block.depth == 0 {
+                        *open = None;
+                    }
+                } else if self.nested && rest.starts_with(opening.as_bytes()) {
+                    idx += opening.len();
+                    block.depth += 1;
+                } else {
+                    idx += 1;
+                }
+            } else if in_string {
+                match rest[0] {
+                    b'\\' => idx += 2,
+                    b'"' => {
+                        in_string = false;
+                        idx += 1;
+                    }
+                    _ => idx += 1,
+                }
+            } else if rest[0].is_ascii_whitespace() {
+                idx += 1;
+            } else if self
+                .line
+                .iter()
+                .any(|marker| rest.starts_with(marker.as_bytes()))
+            {
+                break;
+            } else if let Some(markers) = self
+                .block
+                .iter()
+                .position(|(opening, _)| rest.starts_with(opening.as_bytes()))
+            {
+                idx += self.block[markers].0.len();
+                *open = Some(OpenBlock { markers, depth: 1 });
+            } else {
+                code = true;
+                in_string = rest[0] == b'"';
+                if !in_string {
+                    on_code(idx);
+                }
+                idx += 1;
+            }
+        }
+        code
+    }
+
+    /// Gets the byte range of the comment block opening `contents`, after
+    /// a `#!` line and blank lines: its lines up to the first line with
+    /// code or blank line outside a block comment. The range is empty if
+    /// the contents open with code.
+    pub(crate) fn leading_comment(&self, contents: &[u8]) -> Range<usize> {
+        let mut open = None;
+        let mut start = None;
+        let mut end = 0;
+        let mut offset = 0;
+
+        for line in contents.split_inclusive(|&b| b == b'\n') {
+            let line_start = offset;
+            offset += line.len();
+            if start.is_none()
+                && (is_blank_line(line) || (line_start == 0 && line.starts_with(b"#!")))
+            {
+                continue;
+            }
+            if (open.is_none() && is_blank_line(line)) || self.scan_line(line, &mut open, |_| {}) {
+                break;
+            }
+            start.get_or_insert(line_start);
+            end = offset;
+        }
+        start.map_or(0..0, |start| start..end)
+    }
+
+    /// Calls `on_code` with each line of `contents` and the index of each
+    /// byte of code in it, outside comments and double-quoted strings.
+    pub(crate) fn scan_code(&self, contents: &[u8], mut on_code: impl FnMut(&[u8], usize)) {
+        let mut open = None;
+        for line in contents.split_inclusive(|&b| b == b'\n') {
+            self.scan_line(line, &mut open, |idx| on_code(line, idx));
+        }
+    }
+}
+
+impl CommentSyntax {
+    /// Comment syntax of Rust, whose block comments nest.
+    pub const RUST: Self = Self {
+        line: &["//"],
+        block: &[("/*", "*/")],
+        nested: true,
+        docstrings: &[],
+    };
+
+    /// Comment syntax of C-like languages.
+    pub const C_LIKE: Self = Self {
+        line: &["//"],
+        block: &[("/*", "*/")],
+        nested: false,
+        docstrings: &[],
+    };
+
+    /// Comment syntax of CSS, which has no line comments.
+    pub const CSS: Self = Self {
+        line: &[],
+        block: &[("/*", "*/")],
+        nested: false,
+        docstrings: &[],
+    };
+
+    /// Comment syntax of shell-like languages.
+    pub const HASH: Self = Self {
+        line: &["#"],
+        block: &[],
+        nested: false,
+        docstrings: &[],
+    };
+
+    /// Comment syntax of Python.
+    pub const PYTHON: Self = Self {
+        line: &["#"],
+        block: &[],
+        nested: false,
+        docstrings: &["\"\"\"", "'''"],
+    };
+
+    /// Comment syntax of Haskell, whose block comments nest.
+    pub const HASKELL: Self = Self {
+        line: &["--"],
+        block: &[("{-", "-}")],
+        nested: true,
+        docstrings: &[],
+    };
+
+    /// Comment syntax of markup.
+    pub const MARKUP: Self = Self {
+        line: &[],
+        block: &[("<!--", "-->")],
+        nested: false,
+        docstrings: &[],
+    };
+
+    /// Comment syntax of PHP.
+    pub const PHP: Self = Self {
+        line: &["//", "#"],
+        block: &[("/*", "*/")],
+        nested: false,
+        docstrings: &[],
+    };
+
+    /// Comment syntax of INI-style configuration files.
+    pub const INI: Self = Self {
+        line: &["#", ";"],
+        block: &[],
+        nested: false,
+        docstrings: &[],
+    };
+
+    /// Gets a syntax by its name in category definitions: `rust`, `c`
+    /// (C-like), `css`, `hash`, `python`, `haskell`, `markup`, `php`, or
+    /// `ini`.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "rust" => Some(Self::RUST),
+            "c" => Some(Self::C_LIKE),
+            "css" => Some(Self::CSS),
+            "hash" => Some(Self::HASH),
+            "python" => Some(Self::PYTHON),
+            "haskell" => Some(Self::HASKELL),
+            "markup" => Some(Self::MARKUP),
+            "php" => Some(Self::PHP),
+            "ini" => Some(Self::INI),
+            _ => None,
+        }
+    }
+}
+
+/// Gets the comment syntax of files with extension `ext`, as given by the
+/// first built-in category listing it with one, or `None` if the extension
+/// is unknown or its files cannot have comments, like JSON.
+///
+/// Extensions shared by several categories, such as `h`, have the same
+/// syntax in all of them, so the syntax follows from the extension alone.
+///
+/// # Example
+///
+/// ```
+/// use code_line_count::comments::{CommentSyntax, comment_syntax};
+///
+/// assert_eq!(comment_syntax("h"), Some(CommentSyntax::C_LIKE));
+/// assert_eq!(comment_syntax("json"), None);
+/// ```
+#[must_use]
+pub fn comment_syntax(ext: &str) -> Option<CommentSyntax> {
+    CategoryID::all_ids()
+        .iter()
+        .find_map(|id| id.comment_syntax(ext))
+}
+
+/// Checks whether a line holds nothing but whitespace, including Unicode
+/// whitespace if the line is UTF-8.
+#[must_use]
+pub fn is_blank_line(line: &[u8]) -> bool {
+    BlankChars::Whitespace.is_blank(line)
+}
+
+/// Gets the comment syntax of a file from its extension, or if it has
+/// none its `#!` line, as with [`script_extension`] and [`comment_syntax`].
+#[must_use]
+pub fn file_syntax(path: &Path, contents: &[u8]) -> Option<CommentSyntax> {
+    script_extension(path, contents)
+        .and_then(OsStr::to_str)
+        .and_then(comment_syntax)
+}
+
+/// Opening quote of a docstring starting `line`, after indentation and an
+/// optional string prefix such as `r`.
+fn docstring_quote(line: &[u8], quotes: &[&'static str]) -> Option<&'static str> {
+    let line = line.trim_ascii_start();
+    let unprefixed = line
+        .strip_prefix(b"r")
+        .or_else(|| line.strip_prefix(b"R"))
+        .or_else(|| line.strip_prefix(b"u"))
+        .or_else(|| line.strip_prefix(b"U"))
+        .unwrap_or(line);
+    quotes.iter().copied().find(|quote| {
+        line.starts_with(quote.as_bytes()) || unprefixed.starts_with(quote.as_bytes())
+    })
+}
+
+/// Checks whether `haystack` contains `needle`.
+fn contains(haystack: &[u8], needle: &str) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle.as_bytes())
+}
+
+/// How lines are split into code, comment, and blank lines, beyond each
+/// language's [`CommentSyntax`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LineClassifier {
+    /// Count docstrings, in languages that have them, as comments rather
+    /// than code.
+    pub docstrings: bool,
+    /// Characters a blank line may hold.
+    pub blank: BlankChars,
+    /// Leave out a license header at the top of files, as found by
+    /// [`license_header`](crate::license::license_header).
+    pub skip_license_header: bool,
+    /// Categories whose comment syntax is used before the built-in one,
+    /// such as those of a [`CategoryRegistry`](crate::CategoryRegistry).
+    pub categories: Vec<Category>,
+}
+
+/// What a line holds, as told by a [`LineClassifier`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineKind {
+    /// Code, possibly followed by a comment.
+    Code,
+    /// Only a comment, or part of a block comment or docstring.
+    Comment,
+    /// Only blank characters.
+    Blank,
+}
+
+impl LineClassifier {
+    /// Splits the lines of a file into code, comment, and blank lines,
+    /// with the comment syntax of its extension or `#!` line.
+    #[must_use]
+    pub fn classify_file(&self, path: &Path, contents: &[u8]) -> LineKinds {
+        let syntax = self.syntax_of(path, contents);
+        if self.skip_license_header
+            && let Some(syntax) = &syntax
+        {
+            self.classify(&strip_license_header_in(syntax, contents), Some(syntax))
+        } else {
+            self.classify(contents, syntax.as_ref())
+        }
+    }
+
+    /// Gets the comment syntax of a file from its extension, or if it has
+    /// none its `#!` line, as given by the first of
+    /// [`LineClassifier::categories`] listing it with one, or else as with
+    /// [`file_syntax`].
+    #[must_use]
+    pub fn syntax_of(&self, path: &Path, contents: &[u8]) -> Option<CommentSyntax> {
+        let ext = script_extension(path, contents)?.to_str()?;
+        self.categories
+            .iter()
+            .find_map(|category| category.comment_syntax(ext))
+            .or_else(|| comment_syntax(ext))
+    }
+
+    /// Splits the lines of `contents` into code, comment, and blank lines.
+    ///
+    /// A line is a comment if all it holds outside comments is whitespace,
+    /// so code followed by a trailing comment is code. Block comments are
+    /// followed across lines, nested in languages where they nest. Without
+    /// a syntax, every line that is not blank is code. With
+    /// [`LineClassifier::docstrings`], a string opening the contents or
+    /// following a line ending with `:`, such as a `def`, is a comment,
+    /// over all its lines. An unterminated final line counts like any
+    /// other.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use code_line_count::comments::{LineClassifier, comment_syntax};
+    ///
+    /// let python = comment_syntax("py");
+    /// let contents = b"def add(a, b):\n    \"\"\"Adds.\n\n    \"\"\"\n    return a + b\n";
+    /// let kinds = LineClassifier::default().classify(contents, python.as_ref());
+    /// assert_eq!((kinds.code, kinds.comment, kinds.blank), (4, 0, 1));
+    ///
+    /// let classifier = LineClassifier {
+    ///     docstrings: true,
+    ///     ..LineClassifier::default()
+    /// };
+    /// let kinds = classifier.classify(contents, python.as_ref());
+    /// assert_eq!((kinds.code, kinds.comment, kinds.blank), (2, 3, 0));
+    /// ```
+    #[must_use]
+    pub fn classify(&self, contents: &[u8], syntax: Option<&CommentSyntax>) -> LineKinds {
+        let mut kinds = LineKinds::default();
+        self.classify_lines(contents, syntax, |_, kind| {
+            let count = match kind {
+                LineKind::Code => &mut kinds.code,
+                LineKind::Comment => &mut kinds.comment,
+                LineKind::Blank => &mut kinds.blank,
+            };
+            *count = count.saturating_add(1);
+        });
+        kinds
+    }
+
+    /// Calls `on_line` with each line of `contents`, its line ending
+    /// included, and what it holds, told apart as by
+    /// [`LineClassifier::classify`].
+    pub fn classify_lines<'a>(
+        &self,
+        contents: &'a [u8],
+        syntax: Option<&CommentSyntax>,
+        mut on_line: impl FnMut(&'a [u8], LineKind),
+    ) {
+        let docstrings = match syntax {
+            Some(syntax) if self.docstrings => syntax.docstrings,
+            _ => &[],
+        };
+        // Closing quote of the docstring being read, if any.
+        let mut docstring: Option<&str> = None;
+        // Whether a docstring may start here: at the top, or after a line
+        // opening a block.
+        let mut expect_docstring = true;
+        let mut open = None;
+
+        for line in contents.split_inclusive(|&b| b == b'\n') {
+            let kind = if let Some(quote) = docstring {
+                if contains(line, quote) {
+                    docstring = None;
+                }
+                LineKind::Comment
+            } else if self.blank.is_blank(line) {
+                LineKind::Blank
+            } else if let Some(quote) =
+                docstring_quote(line, docstrings).filter(|_| expect_docstring && open.is_none())
+            {
+                // The quote follows at most a one-letter prefix.
+                let line = line.trim_ascii_start();
+                let opened = usize::from(!line.starts_with(quote.as_bytes())) + quote.len();
+                if !contains(&line[opened..], quote) {
+                    docstring = Some(quote);
+                }
+                expect_docstring = false;
+                LineKind::Comment
+            } else if syntax.is_some_and(|syntax| !syntax.scan_line(line, &mut open, |_| {})) {
+                LineKind::Comment
+            } else {
+                expect_docstring = line.trim_ascii_end().ends_with(b":");
+                LineKind::Code
+            };
+            on_line(line, kind);
+        }
+    }
+}
@@ -0,0 +1,286 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+#[cfg(feature = "plugins")]
+use crate::plugins::PluginSet;
+use crate::{
+    BlankChars, CLCError, CancellationToken, Category, CountOptions, FileFilter, FinalLine,
+    LineCounter, Progress, Report, SharedLineCounter, count_lines_with_progress,
+};
+#[cfg(feature = "plugins")]
+use std::sync::Arc;
+use std::{
+    ffi::{OsStr, OsString},
+    path::{Path, PathBuf},
+};
+
+/// Builder for a count, collecting the paths, extensions, and options
+/// passed to [`count_lines`](crate::count_lines).
+///
+/// New options are added as methods, so code using the builder keeps
+/// compiling as the options grow. Without any path, the current directory
+/// is searched.
+///
+/// # Example
+///
+/// ```
+/// use code_line_count::{CategoryID, Counter};
+///
+/// let report = Counter::new()
+///     .path("src")
+///     .category(CategoryID::Rust)
+///     .extension("toml")
+///     .git_ignore(true)
+///     .max_depth(4)
+///     .run()?;
+/// println!("{} lines", report.total_lines());
+/// # Ok::<(), code_line_count::CLCError>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[must_use]
+pub struct Counter {
+    /// Paths to search.
+    roots: Vec<PathBuf>,
+    /// Extensions to match, without the leading dot.
+    exts: Vec<OsString>,
+    /// Options for the count.
+    options: CountOptions,
+}
+
+impl Counter {
+    /// Creates a builder for a count with the default options and no paths
+    /// or extensions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a path to search.
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.roots.push(path.into());
+        self
+    }
+
+    /// Adds several paths to search.
+    pub fn paths(mut self, paths: impl IntoIterator<Item = impl Into<PathBuf>>) -> Self {
+        self.roots.extend(paths.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds an extension to match, with or without its leading dot.
+    pub fn extension(mut self, ext: impl AsRef<OsStr>) -> Self {
+        let ext = ext.as_ref();
+        let ext = ext
+            .to_str()
+            .and_then(|ext| ext.strip_prefix('.'))
+            .map_or(ext, OsStr::new);
+        self.exts.push(ext.to_os_string());
+        self
+    }
+
+    /// Adds several extensions to match.
+    pub fn extensions(self, exts: impl IntoIterator<Item = impl AsRef<OsStr>>) -> Self {
+        exts.into_iter().fold(self, Self::extension)
+    }
+
+    /// Adds the extensions of a category, built in or from a
+    /// [`CategoryRegistry`](crate::CategoryRegistry), whose comment syntax
+    /// is used for them.
+    pub fn category(mut self, cat: impl Into<Category>) -> Self {
+        let cat = cat.into();
+        self = self.extensions(cat.extensions());
+        self.options.categories.push(cat);
+        self
+    }
+
+    /// Sets whether to include hidden files and directories.
+    pub const fn hidden(mut self, hidden: bool) -> Self {
+        self.options.hidden = hidden;
+        self
+    }
+
+    /// Sets whether to respect `.gitignore` files.
+    pub const fn git_ignore(mut self, respect: bool) -> Self {
+        self.options.respect_git_ignore = respect;
+        self
+    }
+
+    /// Sets the maximum search depth.
+    pub const fn max_depth(mut self, depth: usize) -> Self {
+        self.options.max_depth = Some(depth);
+        self
+    }
+
+    /// Sets the minimum depth of counted files.
+    pub const fn min_depth(mut self, depth: usize) -> Self {
+        self.options.min_depth = Some(depth);
+        self
+    }
+
+    /// Sets whether to follow symlinks, junctions and reparse points.
+    pub const fn follow_links(mut self, follow: bool) -> Self {
+        self.options.follow_links = follow;
+        self
+    }
+
+    /// Sets whether to categorize file symlinks by their target's name.
+    pub const fn resolve_symlinks(mut self, resolve: bool) -> Self {
+        self.options.resolve_symlinks = resolve;
+        self
+    }
+
+    /// Sets whether to match extensions ASCII case-insensitively.
+    pub const fn ignore_ext_case(mut self, ignore: bool) -> Self {
+        self.options.ignore_ext_case = ignore;
+        self
+    }
+
+    /// Limits the file bytes held in memory at once.
+    pub const fn max_memory(mut self, bytes: u64) -> Self {
+        self.options.max_memory = Some(bytes);
+        self
+    }
+
+    /// Sets the number of threads walking and reading files.
+    pub const fn threads(mut self, threads: usize) -> Self {
+        self.options.threads = Some(threads);
+        self
+    }
+
+    /// Sets whether streaming results are yielded sorted by path, once the
+    /// walk has finished.
+    pub const fn ordered(mut self, ordered: bool) -> Self {
+        self.options.ordered = ordered;
+        self
+    }
+
+    /// Sets how to treat a final line without a trailing newline.
+    pub const fn final_line(mut self, policy: FinalLine) -> Self {
+        self.options.final_line = policy;
+        self
+    }
+
+    /// Sets whether to skip lines holding only comments.
+    pub const fn skip_comments(mut self, skip: bool) -> Self {
+        self.options.skip_comments = skip;
+        self
+    }
+
+    /// Sets whether to split each file's lines into code, comment, and
+    /// blank lines.
+    pub const fn classify(mut self, classify: bool) -> Self {
+        self.options.classify = classify;
+        self
+    }
+
+    /// Sets whether to count blank lines too.
+    pub const fn count_blank(mut self, count: bool) -> Self {
+        self.options.count_blank = count;
+        self
+    }
+
+    /// Sets the characters a line may hold and still be blank.
+    pub fn blank_chars(mut self, chars: BlankChars) -> Self {
+        self.options.blank_chars = chars;
+        self
+    }
+
+    /// Sets whether docstrings count as comments rather than code.
+    pub const fn docstring_comments(mut self, comments: bool) -> Self {
+        self.options.docstring_comments = comments;
+        self
+    }
+
+    /// Sets whether to count statements instead of lines where they can
+    /// be told apart.
+    pub const fn logical(mut self, logical: bool) -> Self {
+        self.options.logical = logical;
+        self
+    }
+
+    /// Sets whether to leave out the license header at the top of each
+    /// file.
+    pub const fn skip_license_header(mut self, skip: bool) -> Self {
+        self.options.skip_license_header = skip;
+        self
+    }
+
+    /// Sets whether to count files marked as generated instead of leaving
+    /// them out.
+    pub const fn include_generated(mut self, include: bool) -> Self {
+        self.options.include_generated = include;
+        self
+    }
+
+    /// Sets whether to count minified files instead of leaving them out.
+    pub const fn include_minified(mut self, include: bool) -> Self {
+        self.options.include_minified = include;
+        self
+    }
+
+    /// Sets the strategy for counting each file's lines, instead of counting
+    /// non-empty lines.
+    pub fn line_counter(mut self, counter: impl LineCounter + 'static) -> Self {
+        self.options.line_counter = Some(SharedLineCounter::new(counter));
+        self
+    }
+
+    /// Sets a predicate every file must pass to be counted, on top of
+    /// extension matching.
+    pub fn filter(mut self, predicate: impl Fn(&Path) -> bool + Send + Sync + 'static) -> Self {
+        self.options.filter = Some(FileFilter::new(predicate));
+        self
+    }
+
+    /// Sets a token that stops the count early when cancelled.
+    pub fn cancel(mut self, token: CancellationToken) -> Self {
+        self.options.cancel = Some(token);
+        self
+    }
+
+    /// Sets the plugins consulted before the built-in extension matching.
+    #[cfg(feature = "plugins")]
+    pub fn plugins(mut self, plugins: Arc<PluginSet>) -> Self {
+        self.options.plugins = Some(plugins);
+        self
+    }
+
+    /// Replaces all options at once.
+    // Dropping the old plugins keeps this from being `const` with the
+    // `plugins` feature.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn options(mut self, options: CountOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Runs the count.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CLCError`] under the same conditions as
+    /// [`count_lines`](crate::count_lines).
+    pub fn run(&self) -> Result<Report, CLCError> {
+        self.run_with_progress(&Progress::new())
+    }
+
+    /// Runs the count, recording every counted file in `progress`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CLCError`] under the same conditions as
+    /// [`count_lines`](crate::count_lines).
+    pub fn run_with_progress(&self, progress: &Progress) -> Result<Report, CLCError> {
+        let default_root = [PathBuf::from("./")];
+        let roots = if self.roots.is_empty() {
+            &default_root[..]
+        } else {
+            &self.roots
+        };
+        let exts: Vec<&OsStr> = self.exts.iter().map(OsString::as_os_str).collect();
+
+        count_lines_with_progress(roots, &exts, &self.options, progress)
+    }
+}
@@ -0,0 +1,30 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use std::{
+    borrow::Cow,
+    io::{self, Write},
+};
+
+/// Quotes a CSV field if it contains a delimiter, quote, or line break.
+#[must_use]
+pub fn field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Writes a CSV row, quoting fields as needed.
+///
+/// # Errors
+///
+/// Returns the I/O error if writing fails.
+pub fn write_row<W: Write, S: AsRef<str>>(out: &mut W, fields: &[S]) -> io::Result<()> {
+    let row: Vec<Cow<'_, str>> = fields.iter().map(|f| field(f.as_ref())).collect();
+    writeln!(out, "{}", row.join(","))
+}
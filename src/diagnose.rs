@@ -0,0 +1,107 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use code_line_count::{CLCError, count_lines, options::CountOptions};
+use std::{cmp::Reverse, ffi::OsStr, path::PathBuf};
+
+/// A filter that kept files out of a count.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hint {
+    /// What the filter excluded.
+    pub filter: &'static str,
+    /// How to lift the filter.
+    pub advice: &'static str,
+    /// Number of extra files matched once the filter is lifted.
+    pub files: usize,
+}
+
+/// Works out which filters kept files out of a count.
+///
+/// Every active filter is lifted on its own and the count is redone, so
+/// this is only meant to run after a count came up empty. Hints are sorted
+/// by how many files each filter eliminated, most first; filters that made
+/// no difference are left out.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if any of the recounts fail.
+pub fn zero_match_hints(
+    roots: &[PathBuf],
+    exts: &[&OsStr],
+    options: &CountOptions,
+) -> Result<Vec<Hint>, CLCError> {
+    let baseline = count_lines(roots, exts, options)?.files.len();
+    let mut relaxed = vec![];
+
+    if options.respect_git_ignore {
+        let lifted = CountOptions {
+            respect_git_ignore: false,
+            ..options.clone()
+        };
+        relaxed.push(("ignored by .gitignore", "drop -g", lifted));
+    }
+    if !options.hidden {
+        let lifted = CountOptions {
+            hidden: true,
+            ..options.clone()
+        };
+        relaxed.push(("hidden", "add -h", lifted));
+    }
+    if options.max_depth.is_some() {
+        let lifted = CountOptions {
+            max_depth: None,
+            ..options.clone()
+        };
+        relaxed.push(("beyond the depth limit", "raise or drop -dN", lifted));
+    }
+    if options.min_depth.is_some() {
+        let lifted = CountOptions {
+            min_depth: None,
+            ..options.clone()
+        };
+        relaxed.push((
+            "above the minimum depth",
+            "lower or drop --min-depth",
+            lifted,
+        ));
+    }
+    if !options.follow_links {
+        let lifted = CountOptions {
+            follow_links: true,
+            ..options.clone()
+        };
+        relaxed.push(("behind links", "add -L", lifted));
+    }
+    if !options.ignore_ext_case {
+        let lifted = CountOptions {
+            ignore_ext_case: true,
+            ..options.clone()
+        };
+        relaxed.push((
+            "with differently cased extensions",
+            "check the extension's case",
+            lifted,
+        ));
+    }
+
+    let mut hints = vec![];
+    for (filter, advice, lifted) in relaxed {
+        let files = count_lines(roots, exts, &lifted)?
+            .files
+            .len()
+            .saturating_sub(baseline);
+        if files > 0 {
+            hints.push(Hint {
+                filter,
+                advice,
+                files,
+            });
+        }
+    }
+
+    hints.sort_by_key(|hint| Reverse(hint.files));
+    Ok(hints)
+}
@@ -0,0 +1,184 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use code_line_count::report::{FileCount, Report};
+use std::{
+    ffi::OsString,
+    process::{Command, Stdio},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+/// A value substituted into a command template.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Field {
+    /// Path of the file.
+    Path,
+    /// Lines counted in the file.
+    Lines,
+    /// Language the file is attributed to.
+    Language,
+}
+
+/// Part of a command word.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Piece {
+    /// Literal text.
+    Text(String),
+    /// A placeholder.
+    Field(Field),
+}
+
+/// A command run for each counted file, such as `wc -c {path}`.
+///
+/// The template is split into words like a shell would, honoring single
+/// quotes, double quotes, and backslashes, but the command is run directly
+/// without a shell. Placeholders are substituted inside words, quoted or
+/// not, so a path containing spaces or quotes is always passed as a single
+/// argument. A literal `{` is written as `\{`, even inside single quotes,
+/// where a backslash otherwise stays as written.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExecTemplate {
+    /// Words of the command, the program first.
+    words: Vec<Vec<Piece>>,
+}
+
+impl ExecTemplate {
+    /// Parses a command template.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem if a quote or placeholder is
+    /// left open, a placeholder is unknown, or the template has no command.
+    pub fn parse(template: &str) -> Result<Self, String> {
+        let mut words = vec![];
+        let mut word: Option<Vec<Piece>> = None;
+        let mut text = String::new();
+        let mut quote = None;
+        let mut chars = template.chars();
+
+        while let Some(c) = chars.next() {
+            match (quote, c) {
+                (None, c) if c.is_whitespace() => {
+                    if let Some(mut pieces) = word.take() {
+                        flush(&mut pieces, &mut text);
+                        words.push(pieces);
+                    }
+                    continue;
+                }
+                (None, '\'' | '"') => quote = Some(c),
+                (Some(open), c) if c == open => quote = None,
+                (None, '\\') => text.push(chars.next().ok_or("trailing backslash")?),
+                (Some('\''), '\\') if chars.as_str().starts_with('{') => {
+                    text.push('{');
+                    chars.next();
+                }
+                (Some('"'), '\\') => match chars.next() {
+                    Some(c @ ('"' | '\\' | '{')) => text.push(c),
+                    Some(c) => {
+                        text.push('\\');
+                        text.push(c);
+                    }
+                    None => return Err("unterminated quote".to_string()),
+                },
+                (_, '{') => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(format!("unterminated placeholder {{{name}")),
+                        }
+                    }
+                    let field = match name.as_str() {
+                        "path" => Field::Path,
+                        "lines" => Field::Lines,
+                        "language" => Field::Language,
+                        _ => return Err(format!("unknown placeholder {{{name}}}")),
+                    };
+                    let pieces = word.get_or_insert_with(Vec::new);
+                    flush(pieces, &mut text);
+                    pieces.push(Piece::Field(field));
+                }
+                (_, c) => text.push(c),
+            }
+            word.get_or_insert_with(Vec::new);
+        }
+
+        if quote.is_some() {
+            return Err("unterminated quote".to_string());
+        }
+
+        if let Some(mut pieces) = word {
+            flush(&mut pieces, &mut text);
+            words.push(pieces);
+        }
+
+        if words.is_empty() {
+            return Err("empty command".to_string());
+        }
+
+        Ok(Self { words })
+    }
+
+    /// Builds the command for one file.
+    fn command(&self, file: &FileCount, language: &str) -> Command {
+        let mut args = self.words.iter().map(|pieces| {
+            let mut arg = OsString::new();
+            for piece in pieces {
+                match piece {
+                    Piece::Text(text) => arg.push(text),
+                    Piece::Field(Field::Path) => arg.push(&file.path),
+                    Piece::Field(Field::Lines) => arg.push(file.lines.to_string()),
+                    Piece::Field(Field::Language) => arg.push(language),
+                }
+            }
+            arg
+        });
+
+        let mut command = Command::new(args.next().unwrap_or_default());
+        command.args(args).stdin(Stdio::null());
+        command
+    }
+}
+
+/// Moves pending literal text into a word.
+fn flush(pieces: &mut Vec<Piece>, text: &mut String) {
+    if !text.is_empty() {
+        pieces.push(Piece::Text(std::mem::take(text)));
+    }
+}
+
+/// Runs `template` once per counted file, at most `jobs` at a time.
+///
+/// Commands inherit stdout and stderr, so their output may interleave.
+/// Commands that cannot be started or exit unsuccessfully are reported to
+/// `on_failure`, with a description of the problem; the rest still run.
+pub fn run_for_each(
+    template: &ExecTemplate,
+    report: &Report,
+    language_of: impl Fn(&FileCount) -> String + Sync,
+    jobs: usize,
+    on_failure: impl Fn(&FileCount, String) + Sync,
+) {
+    let next = AtomicUsize::new(0);
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, report.files.len().max(1)) {
+            scope.spawn(|| {
+                while let Some(file) = report.files.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let status = template.command(file, &language_of(file)).status();
+                    let problem = match status {
+                        Ok(status) if status.success() => continue,
+                        Ok(status) => status.to_string(),
+                        Err(err) => err.to_string(),
+                    };
+                    on_failure(file, problem);
+                }
+            });
+        }
+    });
+}
@@ -0,0 +1,165 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use crate::{Counter, Report};
+use std::{
+    ffi::{CStr, OsStr, c_char},
+    panic::{self, AssertUnwindSafe},
+    slice,
+};
+
+/// Outcome of a call.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClcStatus {
+    /// The count finished.
+    ClcOk = 0,
+    /// A required pointer was null, or a path or extension was not valid.
+    ClcInvalidArgument = 1,
+    /// The count failed.
+    ClcCountFailed = 2,
+    /// The count panicked.
+    ClcPanicked = 3,
+}
+
+/// Options for a count. A null pointer stands for all fields being false.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ClcOptions {
+    /// Whether to include hidden files and directories.
+    pub hidden: bool,
+    /// Whether to respect `.gitignore` files.
+    pub git_ignore: bool,
+    /// Whether to follow symlinks.
+    pub follow_links: bool,
+    /// Whether to count files marked as generated, which are left out
+    /// otherwise.
+    pub include_generated: bool,
+    /// Whether to count minified files, which are left out otherwise.
+    pub include_minified: bool,
+}
+
+/// Totals of a count.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ClcCount {
+    /// Non-empty lines in the counted files.
+    pub lines: u64,
+    /// Files counted.
+    pub files: u64,
+    /// Bytes read from the counted files.
+    pub bytes: u64,
+    /// Directories visited.
+    pub dirs: u64,
+    /// Matched files that could not be read.
+    pub errors: u64,
+    /// Matched files left out because they are marked as generated.
+    pub generated: u64,
+    /// Matched files left out because they are minified.
+    pub minified: u64,
+}
+
+impl From<&Report> for ClcCount {
+    fn from(report: &Report) -> Self {
+        Self {
+            lines: report.total_lines(),
+            files: report.files.len() as u64,
+            bytes: report.bytes_read(),
+            dirs: report.dirs_visited,
+            errors: report.errors.len() as u64,
+            generated: report.generated.len() as u64,
+            minified: report.minified.len() as u64,
+        }
+    }
+}
+
+/// Gets the strings of a C array, or `None` if any is null or not a valid
+/// path.
+///
+/// # Safety
+///
+/// `strings` must be null with `len` 0, or point to `len` pointers that
+/// are each null or a NUL-terminated string.
+unsafe fn os_strs<'a>(strings: *const *const c_char, len: usize) -> Option<Vec<&'a OsStr>> {
+    if len == 0 {
+        return Some(vec![]);
+    }
+    if strings.is_null() {
+        return None;
+    }
+
+    // SAFETY: the caller guarantees `strings` points to `len` pointers.
+    unsafe { slice::from_raw_parts(strings, len) }
+        .iter()
+        .map(|&string| {
+            if string.is_null() {
+                return None;
+            }
+            // SAFETY: the caller guarantees non-null pointers are strings.
+            let string = unsafe { CStr::from_ptr(string) };
+            #[cfg(unix)]
+            return Some(std::os::unix::ffi::OsStrExt::from_bytes(string.to_bytes()));
+            #[cfg(not(unix))]
+            return string.to_str().ok().map(OsStr::new);
+        })
+        .collect()
+}
+
+/// Counts the non-empty lines of the files under `roots` whose extension,
+/// given with or without its leading dot, is one of `exts`.
+///
+/// Without any root, the current directory is searched. The totals are
+/// written to `out` when the count finishes; it is left untouched
+/// otherwise.
+///
+/// # Safety
+///
+/// `roots` and `exts` must each be null with a length of 0, or point to
+/// that many NUL-terminated strings. `options` must be null or point to a
+/// [`ClcOptions`], and `out` must point to a writable [`ClcCount`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn clc_count_lines(
+    roots: *const *const c_char,
+    roots_len: usize,
+    exts: *const *const c_char,
+    exts_len: usize,
+    options: *const ClcOptions,
+    out: *mut ClcCount,
+) -> ClcStatus {
+    // SAFETY: the caller guarantees the arrays hold that many strings.
+    let (Some(roots), Some(exts)) = (unsafe { os_strs(roots, roots_len) }, unsafe {
+        os_strs(exts, exts_len)
+    }) else {
+        return ClcStatus::ClcInvalidArgument;
+    };
+    if out.is_null() {
+        return ClcStatus::ClcInvalidArgument;
+    }
+    // SAFETY: the caller guarantees a non-null `options` is valid.
+    let options = unsafe { options.as_ref() }.copied().unwrap_or_default();
+
+    let counter = Counter::new()
+        .paths(roots)
+        .extensions(exts)
+        .hidden(options.hidden)
+        .git_ignore(options.git_ignore)
+        .follow_links(options.follow_links)
+        .include_generated(options.include_generated)
+        .include_minified(options.include_minified);
+
+    // Nothing outlives the call, so no broken state can be observed after a
+    // panic.
+    match panic::catch_unwind(AssertUnwindSafe(|| counter.run())) {
+        Ok(Ok(report)) => {
+            // SAFETY: `out` is non-null and the caller guarantees it is
+            // writable.
+            unsafe { out.write(ClcCount::from(&report)) };
+            ClcStatus::ClcOk
+        }
+        Ok(Err(_)) => ClcStatus::ClcCountFailed,
+        Err(_) => ClcStatus::ClcPanicked,
+    }
+}
@@ -0,0 +1,115 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use code_line_count::report::Report;
+use std::path::{Component, Path};
+
+/// Patterns recognizing test fixtures and sample data by default.
+pub const DEFAULT_FIXTURE_PATTERNS: &str =
+    "fixtures/,testdata/,test-data/,snapshots/,__snapshots__/,golden/,*.golden,*.golden.*";
+
+/// What to do with files recognized as fixtures.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FixtureMode {
+    /// Count them like any other file.
+    #[default]
+    Include,
+    /// Leave them out.
+    Exclude,
+    /// Leave them out and report them separately.
+    Separate,
+}
+
+impl FixtureMode {
+    /// Gets the mode with the given name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "include" => Some(Self::Include),
+            "exclude" => Some(Self::Exclude),
+            "separate" => Some(Self::Separate),
+            _ => None,
+        }
+    }
+}
+
+/// Patterns recognizing test fixtures and sample data.
+///
+/// A pattern ending in `/` matches a directory of that name anywhere in a
+/// path; any other pattern matches file names, with `*` standing for any
+/// run of characters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FixtureRules {
+    /// Directory names, without the trailing `/`.
+    dirs: Vec<String>,
+    /// File name patterns.
+    files: Vec<String>,
+}
+
+impl Default for FixtureRules {
+    fn default() -> Self {
+        Self::parse(DEFAULT_FIXTURE_PATTERNS)
+    }
+}
+
+impl FixtureRules {
+    /// Parses comma-separated patterns such as `fixtures/,*.golden`.
+    #[must_use]
+    pub fn parse(spec: &str) -> Self {
+        let mut rules = Self {
+            dirs: vec![],
+            files: vec![],
+        };
+
+        for pattern in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            if let Some(dir) = pattern.strip_suffix('/') {
+                rules.dirs.push(dir.to_string());
+            } else {
+                rules.files.push(pattern.to_string());
+            }
+        }
+
+        rules
+    }
+
+    /// Checks whether a file is a fixture, by its directories or name.
+    #[must_use]
+    pub fn is_fixture(&self, path: &Path) -> bool {
+        let in_fixture_dir = path.parent().is_some_and(|dir| {
+            dir.components().any(|c| {
+                matches!(c, Component::Normal(name)
+                    if self.dirs.iter().any(|dir| name == dir.as_str()))
+            })
+        });
+
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        in_fixture_dir
+            || self
+                .files
+                .iter()
+                .any(|pattern| glob_match(pattern.as_bytes(), name.as_bytes()))
+    }
+
+    /// Moves the fixtures out of a report into a report of their own.
+    #[must_use]
+    pub fn split_off(&self, report: &mut Report) -> Report {
+        let (fixtures, rest) = std::mem::take(&mut report.files)
+            .into_iter()
+            .partition(|file| self.is_fixture(&file.path));
+        report.files = rest;
+        Report::from_files(fixtures)
+    }
+}
+
+/// Matches a name against a pattern in which `*` stands for any run of
+/// characters.
+fn glob_match(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|skip| glob_match(rest, &name[skip..])),
+        Some((c, rest)) => name.first() == Some(c) && glob_match(rest, &name[1..]),
+    }
+}
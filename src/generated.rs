@@ -0,0 +1,45 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+/// Number of lines at the top of a file searched for a generated marker.
+pub const MARKER_LINES: usize = 10;
+
+/// Markers code generators write near the top of their output, matched
+/// case-sensitively: `@generated` (prost, Buck, Meta's tools), `DO NOT
+/// EDIT` (protoc, stringer), `Code generated by` (Go's convention), and
+/// `automatically generated by` (bindgen).
+const GENERATED_MARKERS: &[&str] = &[
+    "@generated",
+    "DO NOT EDIT",
+    "Code generated by",
+    "automatically generated by",
+];
+
+/// Checks whether a file's contents are marked as generated, by a marker
+/// such as `// @generated` or `# Code generated by` in their first
+/// [`MARKER_LINES`] lines.
+///
+/// # Example
+///
+/// ```
+/// use code_line_count::generated::is_generated;
+///
+/// assert!(is_generated(b"// Code generated by protoc-gen-go. DO NOT EDIT.\npackage pb\n"));
+/// assert!(is_generated(b"# -*- coding: utf-8 -*-\n# @generated\nx = 1\n"));
+/// assert!(!is_generated(b"fn main() {}\n"));
+/// ```
+#[must_use]
+pub fn is_generated(contents: &[u8]) -> bool {
+    contents
+        .split(|&b| b == b'\n')
+        .take(MARKER_LINES)
+        .any(|line| {
+            GENERATED_MARKERS.iter().any(|marker| {
+                line.windows(marker.len())
+                    .any(|window| window == marker.as_bytes())
+            })
+        })
+}
@@ -0,0 +1,144 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use code_line_count::report::{FileCount, LineKinds, Report, sum_kinds};
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::BuildHasher,
+    io,
+    path::{Component, Path, PathBuf},
+    process::Command,
+};
+
+/// Aggregated counts for one directory.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DirTotals {
+    /// The directory, starting with the root it was found under.
+    pub dir: PathBuf,
+    /// Number of counted files in or below the directory.
+    pub files: usize,
+    /// Total lines in or below the directory, saturating on overflow.
+    pub lines: u64,
+    /// Code, comment, and blank lines in or below the directory, if the
+    /// count classified them.
+    pub kinds: Option<LineKinds>,
+    /// Lines added plus lines deleted over the git history of those files,
+    /// if churn was computed.
+    pub churn: Option<u64>,
+}
+
+/// Finds the directory a file is grouped under.
+///
+/// That is the file's root followed by at most `depth` directories.
+fn group_of(file: &Path, roots: &[PathBuf], depth: usize) -> PathBuf {
+    let root = roots
+        .iter()
+        .find(|root| file.starts_with(root))
+        .map_or_else(PathBuf::new, Clone::clone);
+
+    let relative = file.strip_prefix(&root).unwrap_or(file);
+    let dirs = relative
+        .parent()
+        .into_iter()
+        .flat_map(Path::components)
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .take(depth);
+
+    let mut group = root;
+    group.extend(dirs);
+    group
+}
+
+/// Aggregates a report by directory, sorted by directory.
+///
+/// Each file is attributed to its root followed by at most `depth`
+/// directories, so files nested deeper count towards their ancestor at
+/// that depth. With `churn`, the git churn of the counted files in each
+/// directory is summed as well.
+#[must_use]
+pub fn dir_totals<S: BuildHasher>(
+    report: &Report,
+    roots: &[PathBuf],
+    depth: usize,
+    churn: Option<&HashMap<PathBuf, u64, S>>,
+) -> Vec<DirTotals> {
+    let mut dirs: BTreeMap<PathBuf, DirTotals> = BTreeMap::new();
+
+    for FileCount {
+        path, lines, kinds, ..
+    } in &report.files
+    {
+        let dir = group_of(path, roots, depth);
+        let totals = dirs.entry(dir.clone()).or_insert_with(|| DirTotals {
+            dir,
+            churn: churn.map(|_| 0),
+            ..DirTotals::default()
+        });
+
+        totals.files += 1;
+        totals.lines = totals.lines.saturating_add(*lines);
+        totals.kinds = sum_kinds([totals.kinds, *kinds]);
+        if let (Some(total), Some(churn)) = (totals.churn.as_mut(), churn) {
+            *total = total.saturating_add(churn.get(path).copied().unwrap_or(0));
+        }
+    }
+
+    dirs.into_values().collect()
+}
+
+/// Gets the git churn (lines added plus deleted) of every file ever
+/// changed under the given roots.
+///
+/// Paths are keyed the same way the walker reports them, i.e. as the root
+/// joined with the path below it.
+///
+/// # Errors
+///
+/// Returns an I/O error if `git` cannot be run or a root is not inside a
+/// git work tree.
+pub fn git_churn(roots: &[PathBuf]) -> io::Result<HashMap<PathBuf, u64>> {
+    let mut churn: HashMap<PathBuf, u64> = HashMap::new();
+
+    for root in roots {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args([
+                "log",
+                "--numstat",
+                "--format=",
+                "--no-renames",
+                "--relative",
+            ])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(io::Error::other(format!(
+                "git log failed in \"{}\"",
+                root.display()
+            )));
+        }
+
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            let mut fields = line.splitn(3, '\t');
+            let (Some(added), Some(deleted), Some(path)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+
+            // Binary files report "-" instead of line counts.
+            let (Ok(added), Ok(deleted)) = (added.parse::<u64>(), deleted.parse::<u64>()) else {
+                continue;
+            };
+
+            let total = churn.entry(root.join(path)).or_default();
+            *total = total.saturating_add(added.saturating_add(deleted));
+        }
+    }
+
+    Ok(churn)
+}
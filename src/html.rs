@@ -0,0 +1,225 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use crate::{hotspots::dir_totals, stats::language_stats};
+use code_line_count::report::{FileCount, Report};
+use std::{cmp::Reverse, collections::HashMap, fmt::Write, path::PathBuf};
+
+/// Styles of the report.
+const STYLE: &str = "\
+body{font-family:system-ui,sans-serif;margin:2em auto;max-width:60em;padding:0 1em;color:#222}
+h1{font-size:1.5em}h2{font-size:1.2em;margin-top:2em}
+table{border-collapse:collapse;width:100%}
+th,td{padding:.3em .6em;border-bottom:1px solid #ddd;text-align:left}
+th{cursor:pointer;user-select:none;background:#f4f4f4}
+th:after{content:' \\2195';color:#aaa}
+td.n,th.n{text-align:right;font-variant-numeric:tabular-nums}
+.bar{background:#4a7fd4;height:1em;min-width:1px}
+.chart td{border:none;padding:.15em .6em}
+.chart td:last-child{width:60%}";
+
+/// Sorts a table by the clicked column, toggling the direction on each
+/// click. Numeric columns sort by value.
+const SCRIPT: &str = "\
+document.querySelectorAll('table.sortable th').forEach((th,col)=>{
+th.addEventListener('click',()=>{
+const body=th.closest('table').tBodies[0];
+const asc=th.dataset.asc!=='1';th.dataset.asc=asc?'1':'0';
+const key=row=>{const cell=row.cells[col];
+return th.classList.contains('n')?Number(cell.dataset.v):cell.textContent;};
+[...body.rows].sort((a,b)=>{const x=key(a),y=key(b);
+return (x<y?-1:x>y?1:0)*(asc?1:-1);}).forEach(row=>body.appendChild(row));});});";
+
+/// Escapes text for HTML content and attribute values.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Appends a sortable table. Columns flagged as numeric are right-aligned
+/// and sorted by value.
+fn write_table(out: &mut String, header: &[(&str, bool)], rows: &[Vec<String>]) {
+    out.push_str("<table class=\"sortable\"><thead><tr>");
+    for (name, numeric) in header {
+        let class = if *numeric { " class=\"n\"" } else { "" };
+        let _ = write!(out, "<th{class}>{}</th>", escape(name));
+    }
+    out.push_str("</tr></thead><tbody>\n");
+
+    for row in rows {
+        out.push_str("<tr>");
+        for (cell, (_, numeric)) in row.iter().zip(header) {
+            let cell = escape(cell);
+            let _ = if *numeric {
+                write!(out, "<td class=\"n\" data-v=\"{cell}\">{cell}</td>")
+            } else {
+                write!(out, "<td>{cell}</td>")
+            };
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</tbody></table>\n");
+}
+
+/// Gets `part` as a percentage of `whole`, or 0 if `whole` is 0.
+#[allow(clippy::cast_precision_loss)]
+fn percent(part: u64, whole: u64) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / whole as f64
+    }
+}
+
+/// Appends a bar chart of each entry's lines, relative to the largest.
+fn write_chart(out: &mut String, entries: &[(String, u64)]) {
+    let max = entries.iter().map(|(_, lines)| *lines).max().unwrap_or(0);
+
+    out.push_str("<table class=\"chart\">\n");
+    for (label, lines) in entries {
+        let _ = writeln!(
+            out,
+            "<tr><td>{}</td><td class=\"n\">{lines}</td>\
+             <td><div class=\"bar\" style=\"width:{:.1}%\"></div></td></tr>",
+            escape(label),
+            percent(*lines, max)
+        );
+    }
+    out.push_str("</table>\n");
+}
+
+/// Appends a chart of the 20 directories with the most lines and a table
+/// of every directory, grouped as by [`dir_totals`].
+fn write_dirs(out: &mut String, report: &Report, roots: &[PathBuf], depth: usize) {
+    let mut dirs = dir_totals(report, roots, depth, None::<&HashMap<PathBuf, u64>>);
+    dirs.sort_by_key(|dir| Reverse(dir.lines));
+
+    let chart: Vec<(String, u64)> = dirs
+        .iter()
+        .take(20)
+        .map(|dir| (dir.dir.display().to_string(), dir.lines))
+        .collect();
+    write_chart(out, &chart);
+
+    let total = report.total_lines();
+    let rows: Vec<Vec<String>> = dirs
+        .into_iter()
+        .map(|dir| {
+            vec![
+                dir.dir.display().to_string(),
+                dir.files.to_string(),
+                dir.lines.to_string(),
+                format!("{:.1}", percent(dir.lines, total)),
+            ]
+        })
+        .collect();
+    write_table(
+        out,
+        &[
+            ("directory", false),
+            ("files", true),
+            ("lines", true),
+            ("% of lines", true),
+        ],
+        &rows,
+    );
+}
+
+/// Renders a report as a self-contained HTML page.
+///
+/// The page has sortable tables of the lines per language, per directory,
+/// and per file, and a bar chart of the directories with the most lines.
+/// Files are grouped by directory as by [`dir_totals`], under their root
+/// and at most `depth` directories below it. `language_of` attributes
+/// each file to a language.
+#[must_use]
+pub fn render_html(
+    report: &Report,
+    roots: &[PathBuf],
+    depth: usize,
+    language_of: impl Fn(&FileCount) -> String,
+) -> String {
+    let mut out = String::new();
+    let total = report.total_lines();
+
+    out.push_str(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>clc report</title>\n<style>\n",
+    );
+    out.push_str(STYLE);
+    out.push_str("\n</style>\n</head>\n<body>\n");
+    let _ = writeln!(
+        out,
+        "<h1>clc report</h1>\n<p>{total} lines in {} file(s) under {}.</p>",
+        report.files.len(),
+        escape(
+            &roots
+                .iter()
+                .map(|root| root.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    );
+
+    out.push_str("<h2>Languages</h2>\n");
+    let languages: Vec<Vec<String>> = language_stats(report, &language_of)
+        .into_iter()
+        .map(|stats| {
+            vec![
+                stats.name,
+                stats.files.to_string(),
+                stats.lines.to_string(),
+                format!("{:.1}", percent(stats.lines, total)),
+            ]
+        })
+        .collect();
+    write_table(
+        &mut out,
+        &[
+            ("language", false),
+            ("files", true),
+            ("lines", true),
+            ("% of lines", true),
+        ],
+        &languages,
+    );
+
+    out.push_str("<h2>Directories</h2>\n");
+    write_dirs(&mut out, report, roots, depth);
+
+    out.push_str("<h2>Files</h2>\n");
+    let files: Vec<Vec<String>> = report
+        .files
+        .iter()
+        .map(|file| {
+            vec![
+                file.path.display().to_string(),
+                language_of(file),
+                file.lines.to_string(),
+            ]
+        })
+        .collect();
+    write_table(
+        &mut out,
+        &[("path", false), ("language", false), ("lines", true)],
+        &files,
+    );
+
+    out.push_str("<script>\n");
+    out.push_str(SCRIPT);
+    out.push_str("\n</script>\n</body>\n</html>\n");
+    out
+}
@@ -0,0 +1,190 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use code_line_count::comments::is_blank_line;
+use memchr::memchr_iter;
+use std::fmt;
+
+/// How a file's text is encoded, as far as its bytes tell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    /// Only ASCII bytes.
+    Ascii,
+    /// Valid UTF-8.
+    Utf8,
+    /// Valid UTF-8 after a byte order mark.
+    Utf8Bom,
+    /// UTF-16, little-endian, going by its byte order mark.
+    Utf16Le,
+    /// UTF-16, big-endian, going by its byte order mark.
+    Utf16Be,
+    /// Contains NUL bytes, so probably not text at all.
+    Binary,
+    /// Text in some other encoding, such as Latin-1.
+    Unknown,
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Ascii => "ASCII",
+            Self::Utf8 => "UTF-8",
+            Self::Utf8Bom => "UTF-8 with BOM",
+            Self::Utf16Le => "UTF-16 LE (BOM)",
+            Self::Utf16Be => "UTF-16 BE (BOM)",
+            Self::Binary => "binary (contains NUL bytes)",
+            Self::Unknown => "unknown (not UTF-8)",
+        })
+    }
+}
+
+/// Which line endings a file uses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineEndings {
+    /// No line endings at all.
+    None,
+    /// Only `\n`.
+    Lf,
+    /// Only `\r\n`.
+    CrLf,
+    /// Only a lone `\r`.
+    Cr,
+    /// More than one kind.
+    Mixed,
+}
+
+impl fmt::Display for LineEndings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::None => "none",
+            Self::Lf => "LF",
+            Self::CrLf => "CRLF",
+            Self::Cr => "CR",
+            Self::Mixed => "mixed",
+        })
+    }
+}
+
+/// Properties of a single file's contents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Inspection {
+    /// Detected encoding.
+    pub encoding: Encoding,
+    /// Line endings used.
+    pub line_endings: LineEndings,
+    /// Physical lines, blank ones included.
+    pub physical_lines: usize,
+    /// Lines that are empty or whitespace-only, Unicode whitespace
+    /// included if the line is UTF-8.
+    pub blank_lines: usize,
+    /// Length of the longest line, in characters if the file is UTF-8 and
+    /// in bytes otherwise, without its line ending.
+    pub longest_line: usize,
+    /// 1-based number of the longest line, 0 for an empty file.
+    pub longest_line_number: usize,
+    /// Whether the last line ends with a newline.
+    pub final_newline: bool,
+}
+
+/// Detects the encoding of a file's contents.
+fn detect_encoding(bytes: &[u8]) -> Encoding {
+    if bytes.starts_with(b"\xFF\xFE") {
+        Encoding::Utf16Le
+    } else if bytes.starts_with(b"\xFE\xFF") {
+        Encoding::Utf16Be
+    } else if memchr::memchr(0, bytes).is_some() {
+        Encoding::Binary
+    } else if let Some(rest) = bytes.strip_prefix(b"\xEF\xBB\xBF") {
+        if std::str::from_utf8(rest).is_ok() {
+            Encoding::Utf8Bom
+        } else {
+            Encoding::Unknown
+        }
+    } else if bytes.is_ascii() {
+        Encoding::Ascii
+    } else if std::str::from_utf8(bytes).is_ok() {
+        Encoding::Utf8
+    } else {
+        Encoding::Unknown
+    }
+}
+
+/// Classifies the line endings in a file's contents.
+fn detect_line_endings(bytes: &[u8]) -> LineEndings {
+    let mut lf = false;
+    let mut crlf = false;
+    let mut cr = false;
+
+    for idx in memchr::memchr2_iter(b'\n', b'\r', bytes) {
+        match (bytes[idx], bytes.get(idx + 1)) {
+            (b'\r', Some(b'\n')) => crlf = true,
+            (b'\r', _) => cr = true,
+            _ if idx > 0 && bytes[idx - 1] == b'\r' => {}
+            _ => lf = true,
+        }
+    }
+
+    match (lf, crlf, cr) {
+        (false, false, false) => LineEndings::None,
+        (true, false, false) => LineEndings::Lf,
+        (false, true, false) => LineEndings::CrLf,
+        (false, false, true) => LineEndings::Cr,
+        _ => LineEndings::Mixed,
+    }
+}
+
+/// Inspects a file's raw contents, BOM included.
+#[must_use]
+pub fn inspect(bytes: &[u8]) -> Inspection {
+    let encoding = detect_encoding(bytes);
+    let text = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    let utf8 = matches!(
+        encoding,
+        Encoding::Ascii | Encoding::Utf8 | Encoding::Utf8Bom
+    );
+
+    let mut physical_lines = 0;
+    let mut blank_lines = 0;
+    let mut longest_line = 0;
+    let mut longest_line_number = 0;
+
+    let mut start = 0;
+    let ends = memchr_iter(b'\n', text).chain((!text.ends_with(b"\n")).then_some(text.len()));
+    for end in ends {
+        if end == start && end == text.len() {
+            break;
+        }
+
+        let line = &text[start..end];
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        start = end + 1;
+        physical_lines += 1;
+
+        if is_blank_line(line) {
+            blank_lines += 1;
+        }
+
+        let length = if utf8 {
+            String::from_utf8_lossy(line).chars().count()
+        } else {
+            line.len()
+        };
+        if longest_line_number == 0 || length > longest_line {
+            longest_line = length;
+            longest_line_number = physical_lines;
+        }
+    }
+
+    Inspection {
+        encoding,
+        line_endings: detect_line_endings(text),
+        physical_lines,
+        blank_lines,
+        longest_line,
+        longest_line_number,
+        final_newline: text.ends_with(b"\n"),
+    }
+}
@@ -0,0 +1,233 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use crate::{codeowners::CodeOwners, score::measure, snapshot};
+use code_line_count::{
+    CLCError,
+    comments::LineClassifier,
+    json,
+    report::{FileCount, Report, saturating_sum},
+};
+use std::{collections::BTreeMap, path::Path};
+
+/// Team that files without a CODEOWNERS entry are attributed to.
+pub const UNOWNED: &str = "(unowned)";
+
+/// What a leaderboard ranks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Leaderboard {
+    /// Repositories.
+    Repo,
+    /// Languages, across repositories.
+    Language,
+    /// CODEOWNERS teams, across repositories.
+    Team,
+}
+
+impl Leaderboard {
+    /// Gets the leaderboard with the given name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "repo" => Some(Self::Repo),
+            "language" => Some(Self::Language),
+            "team" => Some(Self::Team),
+            _ => None,
+        }
+    }
+
+    /// Gets the leaderboard's name.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Repo => "repo",
+            Self::Language => "language",
+            Self::Team => "team",
+        }
+    }
+}
+
+/// Standing of one repository, language, or team.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Standing {
+    /// Name of the repository, language, or team.
+    pub name: String,
+    /// Lines counted now.
+    pub lines: u64,
+    /// Change in lines since the latest snapshot, if any repository
+    /// contributing to the standing has one.
+    pub growth: Option<i128>,
+    /// Lines long enough to be checked for duplication.
+    pub checked_lines: u64,
+    /// Checked lines that also appear elsewhere in the same repository.
+    pub duplicated_lines: u64,
+}
+
+impl Standing {
+    /// Gets the share of checked lines that are duplicated.
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn duplication(&self) -> f64 {
+        if self.checked_lines == 0 {
+            0.0
+        } else {
+            self.duplicated_lines as f64 / self.checked_lines as f64
+        }
+    }
+}
+
+/// Gets the names a file counts towards.
+///
+/// A file owned by several teams counts towards each of them.
+fn groups_of(
+    kind: Leaderboard,
+    repo: &str,
+    owners: Option<&CodeOwners>,
+    path: &Path,
+    language: impl FnOnce() -> String,
+) -> Vec<String> {
+    match kind {
+        Leaderboard::Repo => vec![repo.to_string()],
+        Leaderboard::Language => vec![language()],
+        Leaderboard::Team => owners
+            .map(|owners| owners.owners(path))
+            .filter(|owners| !owners.is_empty())
+            .map_or_else(|| vec![UNOWNED.to_string()], <[String]>::to_vec),
+    }
+}
+
+/// Gets the lines per name in a snapshot.
+fn snapshot_lines(
+    kind: Leaderboard,
+    repo: &str,
+    owners: Option<&CodeOwners>,
+    snapshot: &json::Value,
+) -> BTreeMap<String, u64> {
+    let lines_of = |value: &json::Value| {
+        value
+            .get("lines")
+            .and_then(json::Value::as_u64)
+            .unwrap_or(0)
+    };
+    let mut lines: BTreeMap<String, u64> = BTreeMap::new();
+
+    match kind {
+        Leaderboard::Repo => {
+            let total = snapshot.get("total").map_or(0, lines_of);
+            lines.insert(repo.to_string(), total);
+        }
+        Leaderboard::Language => {
+            for (name, counts) in snapshot
+                .get("languages")
+                .and_then(json::Value::as_object)
+                .unwrap_or_default()
+            {
+                lines.insert(name.clone(), lines_of(counts));
+            }
+        }
+        Leaderboard::Team => {
+            let Some(json::Value::Array(files)) = snapshot.get("files") else {
+                return lines;
+            };
+            for file in files {
+                let Some(json::Value::String(path)) = file.get("path") else {
+                    continue;
+                };
+                for team in groups_of(kind, repo, owners, Path::new(path), String::new) {
+                    let total = lines.entry(team).or_default();
+                    *total = total.saturating_add(lines_of(file));
+                }
+            }
+        }
+    }
+
+    lines
+}
+
+/// Computes the standings within one repository, sorted by name.
+///
+/// `report` must have been counted at `root`, whose files are read again
+/// to measure duplication among the code lines `classifier` tells apart.
+/// Growth is measured against the repository's most recently saved
+/// snapshot. Teams are read from the repository's CODEOWNERS file; without
+/// one, every file is [`UNOWNED`].
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if a file, the CODEOWNERS file, or the snapshot
+/// cannot be read.
+pub fn standings(
+    kind: Leaderboard,
+    repo: &str,
+    root: &Path,
+    report: &Report,
+    classifier: &LineClassifier,
+    language_of: impl Fn(&FileCount) -> String,
+) -> Result<Vec<Standing>, CLCError> {
+    let owners = match kind {
+        Leaderboard::Team => CodeOwners::find(root)?,
+        Leaderboard::Repo | Leaderboard::Language => None,
+    };
+
+    let mut groups: BTreeMap<String, Vec<FileCount>> = BTreeMap::new();
+    for file in &report.files {
+        for name in groups_of(kind, repo, owners.as_ref(), &file.path, || {
+            language_of(file)
+        }) {
+            groups.entry(name).or_default().push(file.clone());
+        }
+    }
+
+    let before = snapshot::latest(root)?
+        .map(|snapshot| snapshot_lines(kind, repo, owners.as_ref(), &snapshot));
+
+    groups
+        .into_iter()
+        .map(|(name, files)| {
+            let lines = saturating_sum(files.iter().map(|file| file.lines));
+            let measures = measure(&Report::from_files(files), u64::MAX, classifier)?;
+            let growth = before.as_ref().map(|before| {
+                i128::from(lines) - i128::from(before.get(&name).copied().unwrap_or(0))
+            });
+            Ok(Standing {
+                name,
+                lines,
+                growth,
+                checked_lines: measures.checked_lines,
+                duplicated_lines: measures.duplicated_lines,
+            })
+        })
+        .collect::<Result<_, std::io::Error>>()
+        .map_err(CLCError::from)
+}
+
+/// Combines standings of the same name from several repositories and ranks
+/// them by lines, most first.
+#[must_use]
+pub fn rank(standings: impl IntoIterator<Item = Standing>) -> Vec<Standing> {
+    let mut combined: BTreeMap<String, Standing> = BTreeMap::new();
+
+    for standing in standings {
+        let entry = combined
+            .entry(standing.name.clone())
+            .or_insert_with(|| Standing {
+                name: standing.name.clone(),
+                ..Standing::default()
+            });
+        entry.lines = entry.lines.saturating_add(standing.lines);
+        entry.checked_lines = entry.checked_lines.saturating_add(standing.checked_lines);
+        entry.duplicated_lines = entry
+            .duplicated_lines
+            .saturating_add(standing.duplicated_lines);
+        if let Some(growth) = standing.growth {
+            entry.growth = Some(entry.growth.unwrap_or(0) + growth);
+        }
+    }
+
+    let mut ranked: Vec<Standing> = combined.into_values().collect();
+    ranked.sort_by(|a, b| b.lines.cmp(&a.lines).then_with(|| a.name.cmp(&b.name)));
+    ranked
+}
@@ -0,0 +1,1444 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+//! Code line count (clc)
+//!
+//! Counts the total non-empty lines of code in files matching given categories
+//! or file extensions, recursively. This is the library behind the `clc`
+//! command; run `clc --help` for the command line.
+//!
+//! [`count_lines`] walks one or more roots in parallel and counts every file
+//! whose extension matches, returning a [`Report`] of per-file counts.
+//! [`Counter`] builds the same count step by step, and [`count_lines_iter`]
+//! and [`count_lines_channel`] stream the per-file counts as they come in.
+//! [`CategoryID`] maps language names such as `rust` to their extensions.
+//!
+//! # Example
+//!
+//! ```
+//! use code_line_count::{CategoryID, CountOptions, count_lines};
+//! use std::{ffi::OsStr, path::PathBuf};
+//!
+//! let exts: Vec<&OsStr> = CategoryID::Rust
+//!     .extensions()
+//!     .iter()
+//!     .map(OsStr::new)
+//!     .collect();
+//! let report = count_lines(&[PathBuf::from("src")], &exts, &CountOptions::default())?;
+//! println!("{} lines in {} files", report.total_lines(), report.files.len());
+//! # Ok::<(), code_line_count::CLCError>(())
+//! ```
+
+/// Counting inside an async runtime.
+#[cfg(feature = "tokio")]
+pub mod asynchronous;
+#[doc(hidden)]
+mod budget;
+/// Cancellation of a running count.
+pub mod cancel;
+#[doc(hidden)]
+mod cats;
+/// Telling code, comment, and blank lines apart by each language's
+/// comment syntax.
+pub mod comments;
+/// Builder for a count.
+pub mod counter;
+/// C bindings, for embedding the counter in C and C++ tooling.
+#[cfg(feature = "ffi")]
+pub mod ffi;
+/// Detection of generated files.
+pub mod generated;
+/// JSON values and a parser, for reports read back from disk.
+pub mod json;
+/// Detection of license headers.
+pub mod license;
+/// Strategies for counting the lines of a file.
+pub mod line_counter;
+#[doc(hidden)]
+mod links;
+/// Counting statements instead of physical lines.
+pub mod logical;
+/// Detection of minified files.
+pub mod minified;
+/// Options controlling how files are found and counted.
+pub mod options;
+/// WebAssembly plugins that classify and count files.
+#[cfg(feature = "plugins")]
+pub mod plugins;
+/// Live progress of a running count.
+pub mod progress;
+/// Counting files from sources other than the filesystem.
+pub mod provider;
+/// Categories defined at runtime.
+pub mod registry;
+/// Results of a count.
+pub mod report;
+/// Normalization of the paths to search.
+pub mod roots;
+/// Languages of scripts without an extension, from their `#!` line.
+pub mod shebang;
+#[doc(hidden)]
+#[cfg(feature = "encodings")]
+mod transcode;
+/// Helpers reused by the counter: partitioning iterators and counting the
+/// lines of a buffer.
+pub mod util;
+
+#[cfg(feature = "tokio")]
+pub use asynchronous::count_lines_async;
+pub use cancel::CancellationToken;
+pub use counter::Counter;
+pub use line_counter::{
+    AllLines, CodeLines, LineCounter, LogicalLines, NonEmptyLines, SharedLineCounter,
+    WithoutLicenseHeader,
+};
+pub use options::{BlankChars, CountOptions, FileFilter, FinalLine};
+pub use progress::Progress;
+pub use provider::{FileProvider, MemoryFiles, count_provided};
+pub use registry::{Category, CategoryRegistry};
+pub use report::{CountReport, FileCount, FileError, LineKinds, Report};
+
+use budget::MemoryBudget;
+use comments::LineClassifier;
+use generated::is_generated;
+use ignore::{
+    DirEntry, WalkBuilder, WalkState,
+    WalkState::{Continue, Quit, Skip},
+};
+use links::{dedup_link_targets, is_link, resolve_file_link};
+use minified::is_minified;
+#[cfg(feature = "plugins")]
+use plugins::{Claim, PluginSet, describe_failure};
+use shebang::{read_shebang_extension, script_extensions, shebang_extension};
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    ffi::{OsStr, OsString},
+    fs::{self, File},
+    io::{self, ErrorKind, Read},
+    iter,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering::Relaxed},
+        mpsc,
+    },
+    thread,
+    time::SystemTime,
+};
+use thiserror::Error;
+
+define_categories! {
+    Rust => {
+        names: ["rust", "rs"],
+        extensions: ["rs", "rlib"],
+        comments: { RUST: ["rs"] },
+    },
+    Haskell => {
+        names: ["haskell", "hs"],
+        extensions: ["hs", "lhs"],
+        comments: { HASKELL: ["hs"] },
+    },
+    Kotlin => {
+        names: ["kotlin", "kt"],
+        extensions: ["kt", "kts", "kexe", "klib"],
+        comments: { C_LIKE: ["kt", "kts"] },
+    },
+    CSharp => {
+        names: ["csharp", "c#", "cdim"],
+        extensions: ["cs", "csx"],
+        comments: { C_LIKE: ["cs", "csx"] },
+    },
+    Java => {
+        names: ["java"],
+        extensions: ["java", "class", "jmod", "war"],
+        comments: { C_LIKE: ["java"] },
+    },
+    Zig => {
+        names: ["zig"],
+        extensions: ["zig", "zir", "zigr", "zon"],
+        comments: { C_LIKE: ["zig", "zon"] },
+    },
+    C => {
+        names: ["c"],
+        extensions: ["c", "h"],
+        comments: { C_LIKE: ["c", "h"] },
+    },
+    GoLang => {
+        names: ["golang", "go"],
+        extensions: ["go"],
+        comments: { C_LIKE: ["go"] },
+    },
+    Cpp => {
+        names: ["cplusplus", "c++", "cpp", "hell"],
+        extensions: ["c", "C", "cc", "cpp", "cxx", "c++", "h", "H", "hh", "hpp", "hxx", "h++", "cppm", "ixx"],
+        comments: {
+            C_LIKE: ["c", "C", "cc", "cpp", "cxx", "c++", "h", "H", "hh", "hpp", "hxx", "h++", "cppm", "ixx"],
+        },
+    },
+    Web => {
+        names: ["web", "webdev"],
+        extensions: ["js", "jsx", "ts", "tsx", "mjs", "cjs", "css", "scss", "sass", "less", "styl", "vue", "svelte", "astro"],
+        comments: {
+            C_LIKE: ["js", "jsx", "ts", "tsx", "mjs", "cjs", "scss", "sass", "less", "styl"],
+            CSS: ["css"],
+            MARKUP: ["vue", "svelte", "astro"],
+        },
+    },
+    React => {
+        names: ["react"],
+        extensions: ["tsx", "jsx"],
+        comments: { C_LIKE: ["tsx", "jsx"] },
+    },
+    TypeScript => {
+        names: ["typescript"],
+        extensions: ["tsx", "ts"],
+        comments: { C_LIKE: ["tsx", "ts"] },
+    },
+    JavaScript => {
+        names: ["javascript"],
+        extensions: ["jsx", "js"],
+        comments: { C_LIKE: ["jsx", "js"] },
+    },
+    PHP => {
+        names: ["php"],
+        extensions: ["php", "phar", "phtml", "pht", "phps"],
+        comments: { PHP: ["php", "phtml", "pht", "phps"] },
+    },
+    Ruby => {
+        names: ["ruby"],
+        extensions: ["rb", "ru"],
+        comments: { HASH: ["rb", "ru"] },
+    },
+    Elixir => {
+        names: ["elixir", "ex"],
+        extensions: ["ex", "exs"],
+        comments: { HASH: ["ex", "exs"] },
+    },
+    Python => {
+        names: ["python", "py"],
+        extensions: ["py"],
+        comments: { PYTHON: ["py"] },
+    },
+    Shell => {
+        names: ["shell"],
+        extensions: ["sh", "bash", "zsh", "fish"],
+        comments: { HASH: ["sh", "bash", "zsh", "fish"] },
+    },
+    Styles => {
+        names: ["styles", "css"],
+        extensions: ["css", "scss", "sass", "less"],
+        comments: {
+            CSS: ["css"],
+            C_LIKE: ["scss", "sass", "less"],
+        },
+    },
+    Config => {
+        names: ["config", "cfg"],
+        extensions: ["toml", "yaml", "yml", "json", "cfg"],
+        comments: {
+            HASH: ["toml", "yaml", "yml"],
+            INI: ["cfg"],
+        },
+    },
+    Markup => {
+        names: ["markup"],
+        extensions: ["html", "md"],
+        comments: { MARKUP: ["html", "md"] },
+    },
+}
+
+/// Errors that can occur during CLC operations.
+#[derive(Debug, Error)]
+pub enum CLCError {
+    /// Regex compilation or matching error.
+    ///
+    /// Derived from [`regex::Error`]
+    #[error("Regex error: {0}")]
+    RegexError(#[from] regex::Error),
+
+    /// I/O operation error.
+    ///
+    /// Derived from [`std::io::Error`]
+    #[error("I/O error: {0}")]
+    IOError(#[from] std::io::Error),
+
+    /// JSON parsing error.
+    ///
+    /// Derived from [`json::ParseError`]
+    #[error("JSON error: {0}")]
+    JSONError(#[from] json::ParseError),
+
+    /// A report from another tool could not be understood.
+    #[error("unrecognized report: {0}")]
+    UnrecognizedReport(String),
+
+    /// A query did not apply to the report.
+    #[error("query error: {0}")]
+    QueryError(String),
+
+    /// A category definition was invalid.
+    #[error("category error: {0}")]
+    CategoryError(String),
+
+    /// A file could not be read.
+    #[error("could not read {}: {source}", .path.display())]
+    ReadFile {
+        /// Path to the file.
+        path: PathBuf,
+        /// Why the file could not be read.
+        source: std::io::Error,
+    },
+
+    /// The minimum depth was greater than the maximum depth, so no file
+    /// could ever match.
+    #[error("minimum depth {min} is greater than maximum depth {max}")]
+    InvalidDepth {
+        /// The minimum depth of counted files.
+        min: usize,
+        /// The maximum search depth.
+        max: usize,
+    },
+
+    /// No category has the given name.
+    #[error(
+        "category {name} not found{}",
+        .suggestion.as_ref().map(|s| format!("; did you mean {s}?")).unwrap_or_default()
+    )]
+    UnknownCategory {
+        /// The name that was looked up.
+        name: String,
+        /// The closest name of a known category, if any is close enough.
+        suggestion: Option<String>,
+    },
+
+    /// Plugin loading error.
+    ///
+    /// Derived from [`wasmtime::Error`]
+    #[cfg(feature = "plugins")]
+    #[error("plugin error: {0}")]
+    PluginError(#[from] wasmtime::Error),
+
+    /// Error reading or writing a database of exported runs.
+    ///
+    /// Derived from [`rusqlite::Error`]
+    #[cfg(feature = "sqlite")]
+    #[error("SQLite error: {0}")]
+    SqliteError(#[from] rusqlite::Error),
+}
+
+/// What a walker thread found for a single matched file.
+enum Outcome {
+    /// The file was read and counted, possibly through a resolved link.
+    Counted(FileCount, Option<PathBuf>),
+    /// The file was deleted between being listed and being read.
+    Vanished,
+    /// The file could not be read for another reason.
+    Failed(FileError),
+}
+
+/// The UTF-8 byte order mark.
+pub const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Reads and counts a single file, classifying its lines with
+/// `classifier`, if any.
+///
+/// # Errors
+///
+/// Returns the I/O error if the file cannot be read.
+fn count_file(
+    path: &Path,
+    strategy: &dyn LineCounter,
+    classifier: Option<&LineClassifier>,
+) -> io::Result<FileCount> {
+    count_file_with(path, classifier, |contents| {
+        strategy.count_file(path, contents)
+    })
+}
+
+/// Reads a single file and counts its contents, without a leading BOM,
+/// with `count`, classifying its lines with `classifier`, if any.
+///
+/// # Errors
+///
+/// Returns the I/O error if the file cannot be read.
+fn count_file_with(
+    path: &Path,
+    classifier: Option<&LineClassifier>,
+    count: impl FnOnce(&[u8]) -> u64,
+) -> io::Result<FileCount> {
+    let mut file = File::open(path)?;
+    let meta = file.metadata()?;
+
+    let mut bytes = Vec::with_capacity(usize::try_from(meta.len()).unwrap_or(0));
+    file.read_to_end(&mut bytes)?;
+
+    Ok(count_contents(
+        path,
+        &bytes,
+        meta.modified().ok(),
+        classifier,
+        count,
+    ))
+}
+
+/// Counts a file's contents, without a leading BOM, with `count`.
+///
+/// With the `encodings` feature, contents in another encoding than UTF-8
+/// are converted to UTF-8 first. With a `classifier`, the lines are also
+/// split into code, comment, and blank lines.
+fn count_contents(
+    path: &Path,
+    bytes: &[u8],
+    modified: Option<SystemTime>,
+    classifier: Option<&LineClassifier>,
+    count: impl FnOnce(&[u8]) -> u64,
+) -> FileCount {
+    let (bom, encoding, contents) = decode_contents(bytes);
+
+    FileCount {
+        path: path.to_path_buf(),
+        lines: count(&contents),
+        bytes: bytes.len() as u64,
+        bom,
+        modified,
+        language: None,
+        metrics: vec![],
+        encoding,
+        kinds: classifier.map(|classifier| classifier.classify_file(path, &contents)),
+        generated: is_generated(&contents),
+        minified: is_minified(&contents),
+        shebang: path
+            .extension()
+            .is_none()
+            .then(|| shebang_extension(&contents))
+            .flatten(),
+    }
+}
+
+/// Strips a leading BOM from a file's contents, noting whether there was
+/// one.
+///
+/// With the `encodings` feature, contents in another encoding than UTF-8
+/// are converted to UTF-8, and the encoding they were in is returned.
+fn decode_contents(bytes: &[u8]) -> (bool, Option<&'static str>, Cow<'_, [u8]>) {
+    let (bom, contents) = bytes
+        .strip_prefix(UTF8_BOM)
+        .map_or((false, bytes), |contents| (true, contents));
+
+    #[cfg(feature = "encodings")]
+    let (encoding, contents) = transcode::to_utf8(contents);
+    #[cfg(not(feature = "encodings"))]
+    let (encoding, contents) = (None, Cow::Borrowed(contents));
+
+    (bom, encoding, contents)
+}
+
+/// Asks the plugins whether one of them claims a file.
+///
+/// A failing plugin is reported to [`PluginSet::on_failure`] and treated as
+/// not claiming it.
+#[cfg(feature = "plugins")]
+fn claim_file<'a>(plugins: &'a PluginSet, path: &Path) -> Option<Claim<'a>> {
+    let name = path.to_string_lossy();
+    plugins.classify(&name).unwrap_or_else(|err| {
+        plugins.report_failure(&format!(
+            "plugins failed to classify \"{name}\": {}",
+            describe_failure(&err)
+        ));
+        None
+    })
+}
+
+/// Reads and counts a file with the plugin that claimed it.
+///
+/// If the plugin traps, that is reported to [`PluginSet::on_failure`] and
+/// the file is counted by `strategy` instead.
+///
+/// # Errors
+///
+/// Returns the I/O error if the file cannot be read.
+#[cfg(feature = "plugins")]
+fn count_claimed(
+    claim: Claim<'_>,
+    path: &Path,
+    strategy: &dyn LineCounter,
+    classifier: Option<&LineClassifier>,
+) -> io::Result<FileCount> {
+    let name = path.to_string_lossy();
+    let (plugin, set) = (claim.plugin, claim.set);
+    let mut counted = None;
+
+    let file = count_file_with(path, classifier, |contents| {
+        match claim.count(&name, contents) {
+            Ok(count) => {
+                let lines = count.lines;
+                counted = Some(count);
+                lines
+            }
+            Err(err) => {
+                set.report_failure(&format!(
+                    "plugin {plugin} failed on \"{name}\": {}",
+                    describe_failure(&err)
+                ));
+                strategy.count_file(path, contents)
+            }
+        }
+    })?;
+
+    Ok(match counted {
+        Some(count) => FileCount {
+            language: Some(count.language),
+            metrics: count.metrics,
+            ..file
+        },
+        None => file,
+    })
+}
+
+/// Gets the strategy a count uses: [`CountOptions::line_counter`], or else
+/// [`LogicalLines`] with [`CountOptions::logical`], falling back on the
+/// strategy counting physical lines, and [`WithoutLicenseHeader`] around
+/// either with [`CountOptions::skip_license_header`].
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if regex compilation fails.
+fn line_counter_of(options: &CountOptions) -> Result<SharedLineCounter, CLCError> {
+    if let Some(counter) = &options.line_counter {
+        return Ok(counter.clone());
+    }
+    let mut counter = physical_counter_of(options)?;
+    if options.logical {
+        counter = SharedLineCounter::new(LogicalLines::new(counter));
+    }
+    if options.skip_license_header {
+        counter = SharedLineCounter::new(WithoutLicenseHeader::new(counter));
+    }
+    Ok(counter)
+}
+
+/// Gets the built-in strategy counting physical lines: [`CodeLines`] with
+/// [`CountOptions::skip_comments`], [`AllLines`] with
+/// [`CountOptions::count_blank`], and [`NonEmptyLines`] otherwise, each with
+/// [`CountOptions::final_line`].
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if regex compilation fails.
+fn physical_counter_of(options: &CountOptions) -> Result<SharedLineCounter, CLCError> {
+    if options.skip_comments {
+        Ok(SharedLineCounter::new(
+            CodeLines::new(options.final_line, line_classifier(options))
+                .count_blank(options.count_blank),
+        ))
+    } else if options.count_blank {
+        Ok(SharedLineCounter::new(AllLines::new(options.final_line)))
+    } else {
+        Ok(SharedLineCounter::new(
+            NonEmptyLines::new(options.final_line)?.blank_chars(options.blank_chars.clone()),
+        ))
+    }
+}
+
+/// Gets how lines are classified under the options.
+fn line_classifier(options: &CountOptions) -> LineClassifier {
+    LineClassifier {
+        docstrings: options.docstring_comments,
+        blank: options.blank_chars.clone(),
+        skip_license_header: options.skip_license_header,
+        categories: options.categories.clone(),
+    }
+}
+
+/// Gets the classifier splitting each file's lines with
+/// [`CountOptions::classify`], if set.
+fn classifier_of(options: &CountOptions) -> Option<LineClassifier> {
+    if options.classify {
+        Some(line_classifier(options))
+    } else {
+        None
+    }
+}
+
+/// Checks that the depth limits of the options can match any file.
+///
+/// # Errors
+///
+/// Returns [`CLCError::InvalidDepth`] if the minimum depth is greater than
+/// the maximum depth.
+const fn check_depth(options: &CountOptions) -> Result<(), CLCError> {
+    match (options.min_depth, options.max_depth) {
+        (Some(min), Some(max)) if min > max => Err(CLCError::InvalidDepth { min, max }),
+        _ => Ok(()),
+    }
+}
+
+/// Counts the non-empty lines of contents that are not in a file, such as
+/// a network buffer, an archive entry, or an editor buffer.
+///
+/// The contents are counted like a file's: a leading BOM is stripped, with
+/// the `encodings` feature other encodings are converted to UTF-8, and
+/// [`CountOptions::line_counter`] and [`CountOptions::final_line`] apply.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if regex compilation fails.
+///
+/// # Example
+///
+/// ```
+/// # use code_line_count::{CountOptions, count_bytes};
+/// let lines = count_bytes(b"fn main() {\n\n    run();\n}", &CountOptions::default())?;
+/// assert_eq!(lines, 3);
+/// # Ok::<(), code_line_count::CLCError>(())
+/// ```
+pub fn count_bytes(bytes: &[u8], options: &CountOptions) -> Result<u64, CLCError> {
+    let strategy = line_counter_of(options)?;
+    Ok(strategy.count(&decode_contents(bytes).2))
+}
+
+/// Counts the non-empty lines read from `reader` until its end, like
+/// [`count_bytes`].
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if reading fails or regex compilation fails.
+///
+/// # Example
+///
+/// ```
+/// # use code_line_count::{CountOptions, count_reader};
+/// # use std::fs::File;
+/// let lines = count_reader(File::open("src/lib.rs")?, &CountOptions::default())?;
+/// # Ok::<(), code_line_count::CLCError>(())
+/// ```
+pub fn count_reader(mut reader: impl Read, options: &CountOptions) -> Result<u64, CLCError> {
+    let mut bytes = vec![];
+    reader.read_to_end(&mut bytes)?;
+    count_bytes(&bytes, options)
+}
+
+/// Counts non-empty lines like [`count_lines`], but of the given files
+/// instead of those found by walking directories, for tools that already
+/// know which files to measure.
+///
+/// Every file is counted whatever its extension, as often as it is given.
+/// Files are read in parallel and the report is sorted by path.
+/// [`CountOptions::filter`], [`CountOptions::max_memory`],
+/// [`CountOptions::threads`],
+/// [`CountOptions::cancel`], the plugins, and the counting options apply;
+/// the options about walking do not. Files that do not exist are tallied
+/// in [`Report::vanished`], and other files that cannot be read, such as
+/// directories, are listed in [`Report::errors`].
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if regex compilation fails.
+///
+/// # Example
+///
+/// ```
+/// # use code_line_count::{CountOptions, count_files};
+/// # use std::path::PathBuf;
+/// let report = count_files(
+///     [PathBuf::from("src/lib.rs"), PathBuf::from("src/main.rs")],
+///     &CountOptions::default(),
+/// )?;
+/// assert_eq!(report.files.len(), 2);
+/// # Ok::<(), code_line_count::CLCError>(())
+/// ```
+pub fn count_files(
+    paths: impl IntoIterator<Item = PathBuf>,
+    options: &CountOptions,
+) -> Result<Report, CLCError> {
+    let strategy = line_counter_of(options)?;
+    let strategy = &strategy;
+    let classifier = &classifier_of(options);
+    let budget = &options.max_memory.map(MemoryBudget::new);
+    let paths = &paths.into_iter().collect::<Vec<PathBuf>>();
+    let next = &AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    let jobs = options
+        .threads
+        .filter(|&threads| threads > 0)
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, NonZeroUsize::get));
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, paths.len().max(1)) {
+            let tx = tx.clone();
+            scope.spawn(move || {
+                while let Some(path) = paths.get(next.fetch_add(1, Relaxed)) {
+                    if options
+                        .cancel
+                        .as_ref()
+                        .is_some_and(CancellationToken::is_cancelled)
+                    {
+                        break;
+                    }
+
+                    if options
+                        .filter
+                        .as_ref()
+                        .is_some_and(|filter| !filter.allows(path))
+                    {
+                        continue;
+                    }
+
+                    let _reservation = budget.as_ref().map(|budget| {
+                        budget.reserve(fs::metadata(path).map_or(0, |meta| meta.len()))
+                    });
+
+                    #[cfg(feature = "plugins")]
+                    let counted = options
+                        .plugins
+                        .as_deref()
+                        .and_then(|plugins| claim_file(plugins, path))
+                        .map_or_else(
+                            || count_file(path, strategy, classifier.as_ref()),
+                            |claim| count_claimed(claim, path, strategy, classifier.as_ref()),
+                        );
+                    #[cfg(not(feature = "plugins"))]
+                    let counted = count_file(path, strategy, classifier.as_ref());
+
+                    let _ = tx.send(outcome_of(path, counted, None));
+                }
+            });
+        }
+    });
+    drop(tx);
+
+    Ok(collect_outcomes(rx, options))
+}
+
+/// Counts non-empty lines of code under one or more roots.
+///
+/// Uses [`WalkBuilder`] from the [`ignore`] crate to create
+/// a multi-threaded code line counter.
+///
+/// Links (symlinks, junctions, reparse points) are skipped unless
+/// [`CountOptions::follow_links`] is set. When following, the walker
+/// detects cycles and never descends into a directory that is already
+/// being traversed.
+///
+/// With [`CountOptions::resolve_symlinks`], symlinks to files are matched
+/// by their target's name instead of their own, so `build-script -> gen.py`
+/// counts as `py`. Such files are counted once no matter how many links
+/// reach them.
+///
+/// With [`CountOptions::max_memory`], threads wait for each other rather
+/// than hold more file bytes at once than allowed; a single file larger
+/// than the limit is read alone.
+///
+/// Files deleted while the walk is running are tallied in
+/// [`Report::vanished`] instead of being counted. Files that cannot be
+/// read for another reason, such as missing permissions, are listed in
+/// [`Report::errors`].
+///
+/// Roots should not overlap (see [`roots::dedup_roots`]), otherwise files
+/// under the shared part are counted once per root.
+///
+/// Per-file results are gathered into a [`Report`] sorted by path, so the
+/// output does not depend on thread scheduling.
+///
+/// Extensions are compared as [`OsStr`]s, so files whose names are not
+/// valid UTF-8 are still matched and counted.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if regex compilation fails, or
+/// [`CLCError::InvalidDepth`] if [`CountOptions::min_depth`] is greater than
+/// [`CountOptions::max_depth`].
+///
+/// # Example
+///
+/// ```
+/// # use code_line_count::{CountOptions, count_lines};
+/// # use std::{ffi::OsStr, path::PathBuf};
+/// let report = count_lines(
+///     &[PathBuf::from("./")],
+///     &[OsStr::new("rs"), OsStr::new("hs")],
+///     &CountOptions {
+///         hidden: true,
+///         respect_git_ignore: true,
+///         ..CountOptions::default()
+///     },
+/// )?;
+/// # Ok::<(), code_line_count::CLCError>(())
+/// ```
+pub fn count_lines(
+    roots: &[PathBuf],
+    exts: &[&OsStr],
+    options: &CountOptions,
+) -> Result<Report, CLCError> {
+    count_lines_with_progress(roots, exts, options, &Progress::new())
+}
+
+/// Counts non-empty lines like [`count_lines`], recording every counted
+/// file in `progress` as soon as it is counted.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] under the same conditions as [`count_lines`].
+pub fn count_lines_with_progress(
+    roots: &[PathBuf],
+    exts: &[&OsStr],
+    options: &CountOptions,
+    progress: &Progress,
+) -> Result<Report, CLCError> {
+    check_depth(options)?;
+    let counter = line_counter_of(options)?;
+    let (tx, rx) = mpsc::channel();
+    let dirs_visited = walk(roots, exts, options, &counter, progress, &tx);
+    drop(tx);
+    Ok(Report {
+        dirs_visited,
+        ..collect_outcomes(rx, options)
+    })
+}
+
+/// Counts non-empty lines like [`count_lines`], but yields each file's path
+/// and lines as soon as it is counted instead of gathering a [`Report`].
+///
+/// The walk runs on a background thread and files arrive in no particular
+/// order, unless [`CountOptions::ordered`] is set, in which case they
+/// arrive sorted by path once the walk has finished. Dropping the iterator
+/// stops the walk early. Files deleted while the walk is running, or that
+/// cannot be read, are skipped; [`FileCounts::skipped`] tells which. With
+/// [`CountOptions::resolve_symlinks`], each link target is yielded once,
+/// but unless the files are ordered, a target also reached directly may be
+/// yielded a second time.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if regex compilation fails, or
+/// [`CLCError::InvalidDepth`] if [`CountOptions::min_depth`] is greater than
+/// [`CountOptions::max_depth`].
+///
+/// # Example
+///
+/// ```
+/// # use code_line_count::{CountOptions, count_lines_iter};
+/// # use std::{ffi::OsStr, path::PathBuf};
+/// for (path, lines) in count_lines_iter(
+///     &[PathBuf::from("src")],
+///     &[OsStr::new("rs")],
+///     &CountOptions::default(),
+/// )? {
+///     println!("{lines:>6} {}", path.display());
+/// }
+/// # Ok::<(), code_line_count::CLCError>(())
+/// ```
+///
+/// With [`CountOptions::ordered`], files come sorted by path:
+///
+/// ```
+/// # use code_line_count::{CountOptions, count_lines_iter};
+/// # use std::{ffi::OsStr, path::PathBuf};
+/// let options = CountOptions {
+///     ordered: true,
+///     ..CountOptions::default()
+/// };
+/// let paths: Vec<PathBuf> =
+///     count_lines_iter(&[PathBuf::from("src")], &[OsStr::new("rs")], &options)?
+///         .map(|(path, _)| path)
+///         .collect();
+/// assert!(paths.is_sorted());
+/// # Ok::<(), code_line_count::CLCError>(())
+/// ```
+pub fn count_lines_iter(
+    roots: &[PathBuf],
+    exts: &[&OsStr],
+    options: &CountOptions,
+) -> Result<FileCounts, CLCError> {
+    check_depth(options)?;
+    let counter = line_counter_of(options)?;
+    let (tx, rx) = mpsc::channel();
+    let left_out = options.clone();
+
+    let roots = roots.to_vec();
+    let exts: Vec<OsString> = exts.iter().map(|ext| ext.to_os_string()).collect();
+    let options = options.clone();
+    thread::spawn(move || {
+        let exts: Vec<&OsStr> = exts.iter().map(OsString::as_os_str).collect();
+        if !options.ordered {
+            walk(&roots, &exts, &options, &counter, &Progress::new(), &tx);
+            return;
+        }
+
+        let (walk_tx, walk_rx) = mpsc::channel();
+        walk(
+            &roots,
+            &exts,
+            &options,
+            &counter,
+            &Progress::new(),
+            &walk_tx,
+        );
+        drop(walk_tx);
+        // Left-out files are kept for the iterator to record as skipped.
+        let report = collect_outcomes(
+            walk_rx,
+            &CountOptions {
+                include_generated: true,
+                include_minified: true,
+                ..options
+            },
+        );
+        let outcomes = report
+            .files
+            .into_iter()
+            .map(|count| Outcome::Counted(count, None))
+            .chain(report.errors.into_iter().map(Outcome::Failed))
+            .chain((0..report.vanished).map(|_| Outcome::Vanished));
+        for outcome in outcomes {
+            if tx.send(outcome).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(FileCounts {
+        rx,
+        seen_targets: HashSet::new(),
+        left_out,
+        skipped: Report::default(),
+    })
+}
+
+/// Counts non-empty lines like [`count_lines_iter`], but sends each file's
+/// count down a channel as soon as it is counted.
+///
+/// Unlike the iterator, the receiver can be handed to another thread, or
+/// polled with [`try_recv`](mpsc::Receiver::try_recv) while doing other
+/// work, and each [`FileCount`] keeps everything known about the file.
+/// Dropping the receiver stops the walk early.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if regex compilation fails, or
+/// [`CLCError::InvalidDepth`] if [`CountOptions::min_depth`] is greater than
+/// [`CountOptions::max_depth`].
+///
+/// # Example
+///
+/// ```
+/// # use code_line_count::{CountOptions, count_lines_channel};
+/// # use std::{ffi::OsStr, path::PathBuf, thread};
+/// let rx = count_lines_channel(
+///     &[PathBuf::from("src")],
+///     &[OsStr::new("rs")],
+///     &CountOptions::default(),
+/// )?;
+/// let largest = thread::spawn(move || rx.iter().map(|file| file.lines).max());
+/// println!("largest file: {:?} lines", largest.join().unwrap());
+/// # Ok::<(), code_line_count::CLCError>(())
+/// ```
+pub fn count_lines_channel(
+    roots: &[PathBuf],
+    exts: &[&OsStr],
+    options: &CountOptions,
+) -> Result<mpsc::Receiver<FileCount>, CLCError> {
+    let mut counts = count_lines_iter(roots, exts, options)?;
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        while let Some(count) = counts.next_count() {
+            if tx.send(count).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(rx)
+}
+
+/// Iterator over per-file counts, returned by [`count_lines_iter`].
+#[derive(Debug)]
+pub struct FileCounts {
+    /// Outcomes sent by the walker threads.
+    rx: mpsc::Receiver<Outcome>,
+    /// Link targets already yielded.
+    seen_targets: HashSet<PathBuf>,
+    /// Options deciding which files are left out.
+    left_out: CountOptions,
+    /// Files skipped so far.
+    skipped: Report,
+}
+
+impl FileCounts {
+    /// Waits for the next counted file, skipping link targets already
+    /// yielded and recording the files that vanished, could not be read,
+    /// or are left out by the options.
+    fn next_count(&mut self) -> Option<FileCount> {
+        loop {
+            match self.rx.recv().ok()? {
+                Outcome::Counted(count, _)
+                    if count.generated && !self.left_out.include_generated =>
+                {
+                    self.skipped.generated.push(count.path);
+                }
+                Outcome::Counted(count, _) if count.minified && !self.left_out.include_minified => {
+                    self.skipped.minified.push(count.path);
+                }
+                Outcome::Counted(count, target) => {
+                    if target.is_none_or(|target| self.seen_targets.insert(target)) {
+                        return Some(count);
+                    }
+                }
+                Outcome::Vanished => {
+                    self.skipped.vanished = self.skipped.vanished.saturating_add(1);
+                }
+                Outcome::Failed(error) => self.skipped.errors.push(error),
+            }
+        }
+    }
+
+    /// Turns the iterator into one yielding each file's full [`FileCount`],
+    /// with the language a plugin assigned it and the encoding it was
+    /// converted from, instead of only its path and lines.
+    ///
+    /// It borrows the iterator, so [`skipped`](Self::skipped) can still be
+    /// read once it is done.
+    pub fn detailed(&mut self) -> impl Iterator<Item = FileCount> {
+        iter::from_fn(move || self.next_count())
+    }
+
+    /// Gets a report of the files skipped so far, with no counted files:
+    /// those that vanished or could not be read, and the generated and
+    /// minified files the options leave out, in the order they were met.
+    #[must_use]
+    pub const fn skipped(&self) -> &Report {
+        &self.skipped
+    }
+}
+
+impl Iterator for FileCounts {
+    type Item = (PathBuf, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_count().map(|count| (count.path, count.lines))
+    }
+}
+
+/// Walks the roots like [`count_lines`], handing every matched file's
+/// contents to `visit` instead of counting its lines.
+///
+/// This lets callers compute a metric of their own with the same
+/// traversal, filtering, and parallelism.
+///
+/// `visit` runs on the walker threads, concurrently and in no particular
+/// order. It gets the contents without a leading BOM and, with the
+/// `encodings` feature, converted to UTF-8. Plugins only decide which files
+/// match; they do not see the contents. [`CountOptions::max_memory`],
+/// [`CountOptions::cancel`], and the other options apply as they do to a
+/// count.
+///
+/// Returns each file's path with what `visit` returned for it, sorted by
+/// path. Files deleted while the walk is running are skipped.
+///
+/// # Errors
+///
+/// Returns [`CLCError::ReadFile`] if a matched file cannot be read for
+/// another reason than having been deleted, or a directory cannot be
+/// walked. The walk stops at the first such file or directory. Returns
+/// [`CLCError::InvalidDepth`] if [`CountOptions::min_depth`] is greater
+/// than [`CountOptions::max_depth`].
+///
+/// # Example
+///
+/// ```
+/// # use code_line_count::{CountOptions, walk_matching_files};
+/// # use std::{ffi::OsStr, path::PathBuf};
+/// let todos = walk_matching_files(
+///     &[PathBuf::from("src")],
+///     &[OsStr::new("rs")],
+///     &CountOptions::default(),
+///     |_path, contents| contents.windows(4).filter(|w| w == b"TODO").count(),
+/// )?;
+/// for (path, count) in todos {
+///     println!("{count:>4} {}", path.display());
+/// }
+/// # Ok::<(), code_line_count::CLCError>(())
+/// ```
+pub fn walk_matching_files<T: Send>(
+    roots: &[PathBuf],
+    exts: &[&OsStr],
+    options: &CountOptions,
+    visit: impl Fn(&Path, &[u8]) -> T + Sync,
+) -> Result<Vec<(PathBuf, T)>, CLCError> {
+    check_depth(options)?;
+    let budget = &options.max_memory.map(MemoryBudget::new);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            walk_matches(
+                roots,
+                exts,
+                options,
+                |matched| {
+                    let path = matched.entry.path();
+
+                    let _reservation = budget.as_ref().map(|budget| {
+                        budget.reserve(matched.entry.metadata().map_or(0, |meta| meta.len()))
+                    });
+
+                    let visited = match fs::read(path) {
+                        Ok(bytes) => {
+                            Ok((path.to_path_buf(), visit(path, &decode_contents(&bytes).2)))
+                        }
+                        Err(err) if err.kind() == ErrorKind::NotFound => return Continue,
+                        Err(source) => Err(CLCError::ReadFile {
+                            path: path.to_path_buf(),
+                            source,
+                        }),
+                    };
+                    let failed = visited.is_err();
+
+                    if tx.send(visited).is_err() || failed {
+                        return Quit;
+                    }
+
+                    Continue
+                },
+                |error| {
+                    let _ = tx.send(Err(CLCError::ReadFile {
+                        path: error.path,
+                        source: io::Error::other(error.message),
+                    }));
+                },
+            );
+            drop(tx);
+        });
+
+        let mut visited = rx.into_iter().collect::<Result<Vec<_>, _>>()?;
+        visited.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        Ok(visited)
+    })
+}
+
+/// A file the walker matched, by its extension or a plugin's claim.
+struct Matched<'a> {
+    /// The walked entry.
+    entry: &'a DirEntry,
+    /// Target of the file, if it is a file symlink that was resolved.
+    target: Option<PathBuf>,
+    /// Plugin that claimed the file, if any.
+    #[cfg(feature = "plugins")]
+    claim: Option<Claim<'a>>,
+}
+
+/// Walks the roots, calling `visit` for every matched file and `on_error`
+/// for every entry that could not be walked, such as an unreadable
+/// directory, and returns the number of directories visited.
+///
+/// Stops early once `visit` returns [`Quit`] or the count is cancelled.
+fn walk_matches(
+    roots: &[PathBuf],
+    exts: &[&OsStr],
+    options: &CountOptions,
+    visit: impl Fn(Matched<'_>) -> WalkState + Sync,
+    on_error: impl Fn(FileError) + Sync,
+) -> u64 {
+    let CountOptions {
+        hidden,
+        respect_git_ignore,
+        max_depth,
+        min_depth,
+        follow_links,
+        resolve_symlinks,
+        ignore_ext_case,
+        threads,
+        ref filter,
+        ref cancel,
+        #[cfg(feature = "plugins")]
+        ref plugins,
+        ..
+    } = *options;
+
+    let Some((first, rest)) = roots.split_first() else {
+        return 0;
+    };
+
+    let mut builder = WalkBuilder::new(first);
+    for root in rest {
+        builder.add(root);
+    }
+
+    // Extensionless files are only read for a `#!` line if it could give
+    // them one of the extensions.
+    let probe_shebangs =
+        script_extensions().any(|ext| is_one_of(OsStr::new(ext), exts, ignore_ext_case));
+
+    let visit = &visit;
+    let on_error = &on_error;
+    let dirs = &AtomicU64::new(0);
+    builder
+        .hidden(!hidden)
+        .ignore(false)
+        .git_ignore(respect_git_ignore)
+        .max_depth(max_depth)
+        .follow_links(follow_links)
+        .threads(threads.unwrap_or(0))
+        .build_parallel()
+        .run(|| {
+            Box::new(move |entry| {
+                if cancel.as_ref().is_some_and(CancellationToken::is_cancelled) {
+                    return Quit;
+                }
+
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(err) => {
+                        on_error(walk_error(err));
+                        return Continue;
+                    }
+                };
+
+                if entry.file_type().is_some_and(|ft| ft.is_dir()) {
+                    dirs.fetch_add(1, Relaxed);
+                }
+
+                let path = entry.path();
+
+                // Roots are always stat'ed through links, so check them directly.
+                let is_symlink =
+                    entry.path_is_symlink() || (entry.depth() == 0 && path.is_symlink());
+
+                let target = if resolve_symlinks && is_symlink {
+                    resolve_file_link(path)
+                } else {
+                    None
+                };
+
+                if target.is_none() {
+                    if !follow_links && entry.depth() > 0 && is_link(&entry) {
+                        return Skip;
+                    }
+
+                    if entry.file_type().is_none_or(|ft| !ft.is_file()) {
+                        return Continue;
+                    }
+                }
+
+                if min_depth.is_some_and(|min| entry.depth() < min) {
+                    return Continue;
+                }
+
+                if filter.as_ref().is_some_and(|filter| !filter.allows(path)) {
+                    return Continue;
+                }
+
+                let reached = target.as_deref().unwrap_or(path);
+                let matched = has_extension(reached, exts, ignore_ext_case)
+                    || (probe_shebangs
+                        && reached.extension().is_none()
+                        && read_shebang_extension(reached)
+                            .is_some_and(|ext| is_one_of(OsStr::new(ext), exts, ignore_ext_case)));
+
+                #[cfg(feature = "plugins")]
+                let claim = plugins
+                    .as_deref()
+                    .and_then(|plugins| claim_file(plugins, path));
+                #[cfg(not(feature = "plugins"))]
+                let claim: Option<std::convert::Infallible> = None;
+
+                if !matched && claim.is_none() {
+                    return Continue;
+                }
+
+                visit(Matched {
+                    entry: &entry,
+                    target,
+                    #[cfg(feature = "plugins")]
+                    claim,
+                })
+            })
+        });
+
+    dirs.load(Relaxed)
+}
+
+/// Turns an error met while walking into the error of the path it is
+/// about.
+fn walk_error(err: ignore::Error) -> FileError {
+    match err {
+        ignore::Error::WithPath { path, err } => FileError {
+            path,
+            message: err.to_string(),
+        },
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => {
+            walk_error(*err)
+        }
+        ignore::Error::Loop { ref child, .. } => FileError {
+            path: child.clone(),
+            message: err.to_string(),
+        },
+        err => FileError {
+            path: PathBuf::new(),
+            message: err.to_string(),
+        },
+    }
+}
+
+/// Checks whether a file's extension is one of `exts`, optionally ignoring
+/// ASCII case.
+fn has_extension(path: &Path, exts: &[&OsStr], ignore_case: bool) -> bool {
+    is_one_of(path.extension().unwrap_or_default(), exts, ignore_case)
+}
+
+/// Checks whether an extension is one of `exts`, optionally ignoring ASCII
+/// case.
+fn is_one_of(ext: &OsStr, exts: &[&OsStr], ignore_case: bool) -> bool {
+    if ignore_case {
+        exts.iter().any(|e| e.eq_ignore_ascii_case(ext))
+    } else {
+        exts.contains(&ext)
+    }
+}
+
+/// Walks the roots, sending the outcome for every matched file to `tx`, and
+/// returns the number of directories visited.
+///
+/// Stops early once the receiving end is dropped.
+fn walk(
+    roots: &[PathBuf],
+    exts: &[&OsStr],
+    options: &CountOptions,
+    strategy: &dyn LineCounter,
+    progress: &Progress,
+    tx: &mpsc::Sender<Outcome>,
+) -> u64 {
+    let budget = &options.max_memory.map(MemoryBudget::new);
+    let classifier = classifier_of(options);
+
+    walk_matches(
+        roots,
+        exts,
+        options,
+        |matched| {
+            let path = matched.entry.path();
+
+            let _reservation = budget.as_ref().map(|budget| {
+                budget.reserve(matched.entry.metadata().map_or(0, |meta| meta.len()))
+            });
+
+            #[cfg(feature = "plugins")]
+            let counted = matched.claim.map_or_else(
+                || count_file(path, strategy, classifier.as_ref()),
+                |claim| count_claimed(claim, path, strategy, classifier.as_ref()),
+            );
+            #[cfg(not(feature = "plugins"))]
+            let counted = count_file(path, strategy, classifier.as_ref());
+
+            let outcome = outcome_of(path, counted, matched.target);
+            if let Outcome::Counted(count, _) = &outcome {
+                progress.record(&count.path, count.lines);
+            }
+
+            if tx.send(outcome).is_err() {
+                return Quit;
+            }
+
+            Continue
+        },
+        |error| {
+            let _ = tx.send(Outcome::Failed(error));
+        },
+    )
+}
+
+/// Turns the result of counting a file into its outcome, treating a file
+/// that was deleted in the meantime as vanished.
+fn outcome_of(path: &Path, counted: io::Result<FileCount>, target: Option<PathBuf>) -> Outcome {
+    match counted {
+        Ok(count) => Outcome::Counted(count, target),
+        Err(err) if err.kind() == ErrorKind::NotFound => Outcome::Vanished,
+        Err(err) => Outcome::Failed(FileError {
+            path: path.to_path_buf(),
+            message: err.to_string(),
+        }),
+    }
+}
+
+/// Checks whether a counted file is left out of the report under the
+/// options, being generated or minified.
+const fn is_left_out(count: &FileCount, options: &CountOptions) -> bool {
+    (count.generated && !options.include_generated) || (count.minified && !options.include_minified)
+}
+
+/// Paths of counted files left out of a report, by reason.
+#[derive(Debug, Default)]
+struct LeftOut {
+    /// Files marked as generated.
+    generated: Vec<PathBuf>,
+    /// Minified files.
+    minified: Vec<PathBuf>,
+}
+
+impl LeftOut {
+    /// Keeps the path of a counted file if the options leave it out, and
+    /// gives the count back otherwise.
+    fn sift(&mut self, count: FileCount, options: &CountOptions) -> Option<FileCount> {
+        if !is_left_out(&count, options) {
+            Some(count)
+        } else if count.generated && !options.include_generated {
+            self.generated.push(count.path);
+            None
+        } else {
+            self.minified.push(count.path);
+            None
+        }
+    }
+
+    /// Adds the paths, sorted, to a report.
+    fn into_report(mut self, report: Report) -> Report {
+        self.generated.sort_unstable();
+        self.minified.sort_unstable();
+        Report {
+            generated: self.generated,
+            minified: self.minified,
+            ..report
+        }
+    }
+}
+
+/// Gathers the outcomes sent by walker threads into a report.
+fn collect_outcomes(outcomes: impl IntoIterator<Item = Outcome>, options: &CountOptions) -> Report {
+    let mut files = vec![];
+    let mut vanished: u64 = 0;
+    let mut errors = vec![];
+    let mut left_out = LeftOut::default();
+    for outcome in outcomes {
+        match outcome {
+            Outcome::Counted(count, target) => {
+                if let Some(count) = left_out.sift(count, options) {
+                    files.push((count, target));
+                }
+            }
+            Outcome::Vanished => vanished = vanished.saturating_add(1),
+            Outcome::Failed(error) => errors.push(error),
+        }
+    }
+    errors.sort_unstable_by(|a: &FileError, b| a.path.cmp(&b.path));
+
+    let files = if options.resolve_symlinks {
+        dedup_link_targets(files)
+    } else {
+        files.into_iter().map(|(count, _)| count).collect()
+    };
+
+    left_out.into_report(Report {
+        vanished,
+        errors,
+        cancelled: options
+            .cancel
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled),
+        ..Report::from_files(files)
+    })
+}
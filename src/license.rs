@@ -0,0 +1,93 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use crate::comments::{CommentSyntax, file_syntax};
+use std::{borrow::Cow, ops::Range, path::Path};
+
+/// Phrases of common license headers, matched by their words, ASCII
+/// case-insensitively, so they may wrap across comment lines: the MPL, the
+/// Apache License, the MIT License, and SPDX identifiers.
+const LICENSE_MARKERS: &[&str] = &[
+    "Mozilla Public License",
+    "Apache License",
+    "Licensed under the Apache",
+    "MIT License",
+    "Permission is hereby granted",
+    "SPDX-License-Identifier",
+];
+
+/// Gets the byte range of the license header at the top of a file, or
+/// `None` if it has none.
+///
+/// The header is the comment block opening the file, after a `#!` line
+/// and blank lines, written in the comment syntax of the file's extension
+/// or `#!` line.
+/// It ends at the first blank line outside a block comment, so documentation
+/// following it after a blank line is kept. It is a license header if it
+/// names a license, such as "Mozilla Public License", even wrapped across
+/// lines, or "SPDX-License-Identifier".
+///
+/// # Example
+///
+/// ```
+/// use code_line_count::license::license_header;
+/// use std::path::Path;
+///
+/// let contents = b"// SPDX-License-Identifier: MIT\n// Copyright\n\n//! Docs.\nfn main() {}\n";
+/// assert_eq!(license_header(Path::new("main.rs"), contents), Some(0..45));
+/// assert_eq!(license_header(Path::new("main.rs"), b"// Docs.\nfn main() {}\n"), None);
+/// ```
+#[must_use]
+pub fn license_header(path: &Path, contents: &[u8]) -> Option<Range<usize>> {
+    license_header_in(&file_syntax(path, contents)?, contents)
+}
+
+/// Gets the byte range of the license header at the top of contents
+/// written with `syntax`, as with [`license_header`].
+pub(crate) fn license_header_in(syntax: &CommentSyntax, contents: &[u8]) -> Option<Range<usize>> {
+    let header = syntax.leading_comment(contents);
+    let text = words(&contents[header.clone()]);
+    LICENSE_MARKERS
+        .iter()
+        .any(|marker| text.contains(&words(marker.as_bytes())))
+        .then_some(header)
+}
+
+/// Gets the ASCII words of text, lowercased and separated by single
+/// spaces, with a space on either side, so comment markers, punctuation,
+/// and line breaks between them are ignored.
+fn words(text: &[u8]) -> String {
+    let mut words = String::from(" ");
+    for word in text
+        .split(|b| !b.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        words.extend(word.iter().map(|b| char::from(b.to_ascii_lowercase())));
+        words.push(' ');
+    }
+    words
+}
+
+/// Leaves the license header of a file, if any, out of its contents.
+#[must_use]
+pub fn strip_license_header<'a>(path: &Path, contents: &'a [u8]) -> Cow<'a, [u8]> {
+    file_syntax(path, contents).map_or(Cow::Borrowed(contents), |syntax| {
+        strip_license_header_in(&syntax, contents)
+    })
+}
+
+/// Leaves the license header of contents written with `syntax`, if any, out
+/// of them.
+pub(crate) fn strip_license_header_in<'a>(
+    syntax: &CommentSyntax,
+    contents: &'a [u8],
+) -> Cow<'a, [u8]> {
+    match license_header_in(syntax, contents) {
+        Some(header) if header.start == 0 => Cow::Borrowed(&contents[header.end..]),
+        Some(header) => Cow::Owned([&contents[..header.start], &contents[header.end..]].concat()),
+        None => Cow::Borrowed(contents),
+    }
+}
@@ -0,0 +1,322 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use crate::{
+    BlankChars, CLCError, FinalLine,
+    comments::{CommentSyntax, LineClassifier},
+    license::strip_license_header,
+    logical::count_statements,
+    util::{BLANK_LINES_PATTERN, count_buffer},
+};
+use memchr::memchr_iter;
+use regex::bytes::Regex;
+use std::{fmt::Debug, path::Path, sync::Arc};
+
+/// Strategy for counting the lines of a file's contents.
+///
+/// [`NonEmptyLines`] is the built-in strategy. Others, such as counting
+/// only lines that are not comments, can be set in
+/// [`CountOptions::line_counter`](crate::CountOptions::line_counter).
+///
+/// # Example
+///
+/// ```
+/// use code_line_count::{CategoryID, Counter, LineCounter};
+///
+/// /// Counts every newline, blank lines included.
+/// #[derive(Debug)]
+/// struct Newlines;
+///
+/// impl LineCounter for Newlines {
+///     fn count(&self, contents: &[u8]) -> u64 {
+///         contents.iter().filter(|&&b| b == b'\n').count() as u64
+///     }
+/// }
+///
+/// let report = Counter::new()
+///     .path("src")
+///     .category(CategoryID::Rust)
+///     .line_counter(Newlines)
+///     .run()?;
+/// # Ok::<(), code_line_count::CLCError>(())
+/// ```
+pub trait LineCounter: Debug + Send + Sync {
+    /// Counts the lines of a file's contents, given without a leading BOM.
+    ///
+    /// Called on the walker threads, concurrently.
+    fn count(&self, contents: &[u8]) -> u64;
+
+    /// Counts the lines of a file's contents knowing its path, such as to
+    /// tell its language. By default, the path is ignored.
+    ///
+    /// Called instead of [`LineCounter::count`] for every file, while
+    /// contents that are not in a file are counted without a path.
+    fn count_file(&self, path: &Path, contents: &[u8]) -> u64 {
+        let _ = path;
+        self.count(contents)
+    }
+}
+
+/// The built-in strategy, counting lines that are not blank.
+///
+/// Blank lines are whitespace-only by default; see [`BlankChars`]. See
+/// [`FinalLine`] for how a final line without a trailing newline is
+/// treated.
+#[derive(Clone, Debug)]
+pub struct NonEmptyLines {
+    /// Pattern collapsing runs of blank lines.
+    re: Regex,
+    /// How to treat a final line without a trailing newline.
+    final_line: FinalLine,
+    /// Characters a blank line may hold.
+    blank: BlankChars,
+}
+
+impl NonEmptyLines {
+    /// Creates the strategy with the given final line policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CLCError`] if regex compilation fails.
+    pub fn new(final_line: FinalLine) -> Result<Self, CLCError> {
+        Ok(Self {
+            re: Regex::new(BLANK_LINES_PATTERN)?,
+            final_line,
+            blank: BlankChars::default(),
+        })
+    }
+
+    /// Sets the characters a blank line may hold.
+    #[must_use]
+    pub fn blank_chars(mut self, blank: BlankChars) -> Self {
+        self.blank = blank;
+        self
+    }
+}
+
+impl LineCounter for NonEmptyLines {
+    fn count(&self, contents: &[u8]) -> u64 {
+        let lines = match self.blank {
+            BlankChars::Whitespace => count_buffer(contents, &self.re, self.final_line),
+            BlankChars::Only(_) if self.final_line == FinalLine::WcCompat => {
+                memchr_iter(b'\n', contents).count()
+            }
+            BlankChars::Only(_) => lines_of(contents, self.final_line)
+                .filter(|line| !self.blank.is_blank(line))
+                .count(),
+        };
+        u64::try_from(lines).unwrap_or(u64::MAX)
+    }
+}
+
+/// Strategy counting every line, blank lines included.
+///
+/// An unterminated final line is counted with [`FinalLine::Count`], and
+/// not otherwise, so the count is that of `wc -l` unless the final line
+/// is counted.
+#[derive(Clone, Copy, Debug)]
+pub struct AllLines {
+    /// How to treat a final line without a trailing newline.
+    final_line: FinalLine,
+}
+
+impl AllLines {
+    /// Creates the strategy with the given final line policy.
+    #[must_use]
+    pub const fn new(final_line: FinalLine) -> Self {
+        Self { final_line }
+    }
+}
+
+impl LineCounter for AllLines {
+    fn count(&self, contents: &[u8]) -> u64 {
+        let newlines = memchr_iter(b'\n', contents).count();
+        let unterminated =
+            self.final_line == FinalLine::Count && contents.last().is_some_and(|&b| b != b'\n');
+        u64::try_from(newlines + usize::from(unterminated)).unwrap_or(u64::MAX)
+    }
+}
+
+/// Strategy counting lines of code, skipping blank lines and lines that
+/// hold only comments, as written in the file's language and told apart
+/// by a [`LineClassifier`].
+///
+/// The language's syntax is found from the file's extension, or its `#!`
+/// line, with [`LineClassifier::syntax_of`]. Files of other languages, and contents counted
+/// without a path, have no comments, so only blank lines are skipped.
+/// Blank lines can be counted as well, skipping only comments. An
+/// unterminated final line is not counted with [`FinalLine::Ignore`], and
+/// counted otherwise.
+#[derive(Clone, Debug)]
+pub struct CodeLines {
+    /// How to treat a final line without a trailing newline.
+    final_line: FinalLine,
+    /// How lines are told apart.
+    classifier: LineClassifier,
+    /// Whether blank lines are counted.
+    count_blank: bool,
+}
+
+impl CodeLines {
+    /// Creates the strategy with the given final line policy and
+    /// classifier.
+    #[must_use]
+    pub const fn new(final_line: FinalLine, classifier: LineClassifier) -> Self {
+        Self {
+            final_line,
+            classifier,
+            count_blank: false,
+        }
+    }
+
+    /// Sets whether blank lines are counted, skipping only comments.
+    #[must_use]
+    pub const fn count_blank(mut self, count: bool) -> Self {
+        self.count_blank = count;
+        self
+    }
+
+    /// Counts the lines of code in `contents`, written with `syntax`.
+    fn count_with(&self, contents: &[u8], syntax: Option<&CommentSyntax>) -> u64 {
+        let contents = if self.final_line == FinalLine::Ignore {
+            terminated(contents)
+        } else {
+            contents
+        };
+        let kinds = self.classifier.classify(contents, syntax);
+        if self.count_blank {
+            kinds.code.saturating_add(kinds.blank)
+        } else {
+            kinds.code
+        }
+    }
+}
+
+impl LineCounter for CodeLines {
+    fn count(&self, contents: &[u8]) -> u64 {
+        self.count_with(contents, None)
+    }
+
+    fn count_file(&self, path: &Path, contents: &[u8]) -> u64 {
+        self.count_with(contents, self.classifier.syntax_of(path, contents).as_ref())
+    }
+}
+
+/// Strategy counting logical lines: statements rather than physical lines,
+/// so the count depends less on formatting style.
+///
+/// Statements are counted with
+/// [`count_statements`](crate::logical::count_statements) in languages
+/// where they can be told apart, such as semicolon-terminated languages
+/// and Python. Files of other languages, and contents counted without a
+/// path, are counted with a fallback strategy instead.
+#[derive(Clone, Debug)]
+pub struct LogicalLines {
+    /// Strategy for contents without a known statement syntax.
+    fallback: SharedLineCounter,
+}
+
+impl LogicalLines {
+    /// Creates the strategy, counting files whose statements cannot be
+    /// told apart with `fallback`.
+    #[must_use]
+    pub const fn new(fallback: SharedLineCounter) -> Self {
+        Self { fallback }
+    }
+}
+
+impl LineCounter for LogicalLines {
+    fn count(&self, contents: &[u8]) -> u64 {
+        self.fallback.count(contents)
+    }
+
+    fn count_file(&self, path: &Path, contents: &[u8]) -> u64 {
+        count_statements(path, contents).unwrap_or_else(|| self.fallback.count_file(path, contents))
+    }
+}
+
+/// Strategy leaving out the license header at the top of each file, as
+/// found by [`license_header`](crate::license::license_header), and
+/// counting the rest with another strategy.
+///
+/// Contents counted without a path have no known comment syntax, so they
+/// are counted whole.
+#[derive(Clone, Debug)]
+pub struct WithoutLicenseHeader {
+    /// Strategy counting the rest of each file.
+    inner: SharedLineCounter,
+}
+
+impl WithoutLicenseHeader {
+    /// Creates the strategy, counting what follows the license header
+    /// with `inner`.
+    #[must_use]
+    pub const fn new(inner: SharedLineCounter) -> Self {
+        Self { inner }
+    }
+}
+
+impl LineCounter for WithoutLicenseHeader {
+    fn count(&self, contents: &[u8]) -> u64 {
+        self.inner.count(contents)
+    }
+
+    fn count_file(&self, path: &Path, contents: &[u8]) -> u64 {
+        self.inner
+            .count_file(path, &strip_license_header(path, contents))
+    }
+}
+
+/// Strips an unterminated final line from contents: everything after the
+/// last newline.
+fn terminated(contents: &[u8]) -> &[u8] {
+    contents
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(&[][..], |end| &contents[..=end])
+}
+
+/// Iterates over the lines of contents with their endings, leaving out an
+/// unterminated final line with [`FinalLine::Ignore`].
+fn lines_of(contents: &[u8], final_line: FinalLine) -> impl Iterator<Item = &[u8]> {
+    let contents = if final_line == FinalLine::Ignore {
+        terminated(contents)
+    } else {
+        contents
+    };
+    contents.split_inclusive(|&b| b == b'\n')
+}
+
+/// A [`LineCounter`] shared between counts and threads.
+///
+/// Clones share the strategy, and compare equal if they share it.
+#[derive(Clone, Debug)]
+pub struct SharedLineCounter(Arc<dyn LineCounter>);
+
+impl SharedLineCounter {
+    /// Wraps a strategy for sharing.
+    pub fn new(counter: impl LineCounter + 'static) -> Self {
+        Self(Arc::new(counter))
+    }
+}
+
+impl LineCounter for SharedLineCounter {
+    fn count(&self, contents: &[u8]) -> u64 {
+        self.0.count(contents)
+    }
+
+    fn count_file(&self, path: &Path, contents: &[u8]) -> u64 {
+        self.0.count_file(path, contents)
+    }
+}
+
+impl PartialEq for SharedLineCounter {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SharedLineCounter {}
@@ -0,0 +1,89 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use crate::report::FileCount;
+use ignore::DirEntry;
+use std::{
+    collections::HashSet,
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Checks whether an entry is a link-like filesystem object.
+///
+/// Symlinks always qualify. On Windows, directory junctions and any other
+/// reparse point are treated the same way, so they are skipped unless links
+/// are followed.
+pub fn is_link(entry: &DirEntry) -> bool {
+    entry.path_is_symlink() || is_reparse_point(entry)
+}
+
+/// Checks whether an entry carries `FILE_ATTRIBUTE_REPARSE_POINT`.
+#[cfg(windows)]
+fn is_reparse_point(entry: &DirEntry) -> bool {
+    use std::os::windows::fs::MetadataExt;
+
+    const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x400;
+
+    entry
+        .metadata()
+        .is_ok_and(|meta| meta.file_attributes() & FILE_ATTRIBUTE_REPARSE_POINT != 0)
+}
+
+/// Checks whether an entry carries `FILE_ATTRIBUTE_REPARSE_POINT`.
+///
+/// Reparse points only exist on Windows.
+#[cfg(not(windows))]
+const fn is_reparse_point(_entry: &DirEntry) -> bool {
+    false
+}
+
+/// Resolves a link to its final target if that target is a regular file.
+///
+/// The returned path is canonical, so two links to the same file resolve
+/// to the same target.
+pub fn resolve_file_link(path: &Path) -> Option<PathBuf> {
+    let target = fs::canonicalize(path).ok()?;
+    target.is_file().then_some(target)
+}
+
+/// Drops counts of resolved links whose target is already counted.
+///
+/// Each entry is a file count paired with the canonical target it was
+/// resolved through, if any. A target reached both directly and through
+/// links, or through several links, keeps only one count: the direct one
+/// if present, otherwise the link with the smallest path.
+pub fn dedup_link_targets(mut files: Vec<(FileCount, Option<PathBuf>)>) -> Vec<FileCount> {
+    files.sort_unstable_by(|a, b| a.0.path.cmp(&b.0.path));
+
+    let target_names: HashSet<&OsStr> = files
+        .iter()
+        .filter_map(|(_, target)| target.as_deref()?.file_name())
+        .collect();
+
+    // Only files sharing a name with some target can be one, so the rest
+    // never needs canonicalizing.
+    let mut seen: HashSet<PathBuf> = files
+        .iter()
+        .filter(|(file, target)| {
+            target.is_none()
+                && file
+                    .path
+                    .file_name()
+                    .is_some_and(|name| target_names.contains(name))
+        })
+        .filter_map(|(file, _)| fs::canonicalize(&file.path).ok())
+        .collect();
+
+    files
+        .into_iter()
+        .filter_map(|(file, target)| match target {
+            Some(target) => seen.insert(target).then_some(file),
+            None => Some(file),
+        })
+        .collect()
+}
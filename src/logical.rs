@@ -0,0 +1,159 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use crate::{
+    comments::{CommentSyntax, comment_syntax},
+    shebang::script_extension,
+};
+use std::{ffi::OsStr, path::Path};
+
+/// How a language ends its statements, for counting logical lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatementSyntax {
+    /// Statements end with `;`, as in C, Rust, Java, and JavaScript,
+    /// written with the given comment syntax.
+    Semicolons(CommentSyntax),
+    /// Python's statements, ending at the end of a line outside brackets
+    /// and strings, or at `;`.
+    Python,
+}
+
+/// Gets how files with extension `ext` end their statements, or `None` if
+/// the language's statements cannot be told apart from its lines.
+///
+/// Languages where semicolons are optional and rarely written, such as Go
+/// and Kotlin, are left out.
+#[must_use]
+pub fn statement_syntax(ext: &str) -> Option<StatementSyntax> {
+    match ext {
+        "rs" | "cs" | "csx" | "java" | "zig" | "c" | "h" | "C" | "cc" | "cpp" | "cxx" | "c++"
+        | "H" | "hh" | "hpp" | "hxx" | "h++" | "cppm" | "ixx" | "js" | "jsx" | "ts" | "tsx"
+        | "mjs" | "cjs" | "php" | "phtml" | "pht" | "phps" => {
+            comment_syntax(ext).map(StatementSyntax::Semicolons)
+        }
+        "py" => Some(StatementSyntax::Python),
+        _ => None,
+    }
+}
+
+/// Counts the statements of a file, or `None` if its extension, or the one
+/// its `#!` line gives it, has no known [`StatementSyntax`].
+///
+/// In semicolon languages, each `;` outside comments, double-quoted
+/// strings, and parentheses ends a statement, so a `for (;;)` header is
+/// not one, and neither are items and expressions without a `;`, such as
+/// a Rust function's final expression. In Python, each line is a
+/// statement, except within brackets, triple-quoted strings, and after a
+/// backslash continuation, and `;` separates statements on a line.
+/// Comments are never statements.
+///
+/// # Example
+///
+/// ```
+/// use code_line_count::logical::count_statements;
+/// use std::path::Path;
+///
+/// let c = b"for (i = 0; i < n; i++) {\n    a();\n    b(\";\"); // c();\n}\n";
+/// assert_eq!(count_statements(Path::new("a.c"), c), Some(2));
+///
+/// let python = b"x = [\n    1,\n    2,\n]\ny = 1; z = 2  # w = 3\n";
+/// assert_eq!(count_statements(Path::new("a.py"), python), Some(3));
+///
+/// assert_eq!(count_statements(Path::new("a.toml"), b"a = 1\n"), None);
+/// ```
+#[must_use]
+pub fn count_statements(path: &Path, contents: &[u8]) -> Option<u64> {
+    let ext = script_extension(path, contents).and_then(OsStr::to_str)?;
+    Some(match statement_syntax(ext)? {
+        StatementSyntax::Semicolons(syntax) => count_semicolons(contents, &syntax),
+        StatementSyntax::Python => count_python(contents),
+    })
+}
+
+/// Counts the semicolons ending statements in code with `syntax`.
+fn count_semicolons(contents: &[u8], syntax: &CommentSyntax) -> u64 {
+    let mut statements = 0u64;
+    let mut parens = 0usize;
+    syntax.scan_code(contents, |line, idx| match line[idx] {
+        b'(' => parens += 1,
+        b')' => parens = parens.saturating_sub(1),
+        // A `';'` character literal ends nothing.
+        b';' if parens == 0
+            && !(idx > 0 && line[idx - 1] == b'\'' && line.get(idx + 1) == Some(&b'\'')) =>
+        {
+            statements = statements.saturating_add(1);
+        }
+        _ => {}
+    });
+    statements
+}
+
+/// Counts the statements of Python code.
+fn count_python(contents: &[u8]) -> u64 {
+    let mut statements = 0u64;
+    let mut brackets = 0usize;
+    // Closing quote of the string being read, if any.
+    let mut quote: Option<&[u8]> = None;
+    // Whether the statement being read has any code yet.
+    let mut pending = false;
+    let mut idx = 0;
+
+    while idx < contents.len() {
+        let rest = &contents[idx..];
+        if let Some(closing) = quote {
+            if rest[0] == b'\\' {
+                idx += 2;
+                continue;
+            }
+            if rest.starts_with(closing) {
+                idx += closing.len();
+                quote = None;
+            } else if rest[0] == b'\n' && closing.len() == 1 {
+                // An unterminated string ends with its line.
+                quote = None;
+            } else {
+                idx += 1;
+            }
+            continue;
+        }
+
+        match rest[0] {
+            b'#' => {
+                idx += rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len());
+                continue;
+            }
+            b'"' | b'\'' => {
+                let len = if rest.starts_with(&[rest[0]; 3]) {
+                    3
+                } else {
+                    1
+                };
+                quote = Some(&rest[..len]);
+                pending = true;
+                idx += len;
+                continue;
+            }
+            b'\\' if rest[1..].starts_with(b"\n") => idx += 1,
+            b'\\' if rest[1..].starts_with(b"\r\n") => idx += 2,
+            b'(' | b'[' | b'{' => {
+                brackets += 1;
+                pending = true;
+            }
+            b')' | b']' | b'}' => {
+                brackets = brackets.saturating_sub(1);
+                pending = true;
+            }
+            b';' | b'\n' if brackets == 0 => {
+                statements = statements.saturating_add(u64::from(pending));
+                pending = false;
+            }
+            b if b.is_ascii_whitespace() => {}
+            _ => pending = true,
+        }
+        idx += 1;
+    }
+    statements.saturating_add(u64::from(pending))
+}
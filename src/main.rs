@@ -9,108 +9,114 @@
 //! Counts the total non-empty lines of code in files matching given categories
 //! or file extensions, recursively.
 //!
-//! Run `clc --help` for usage and options.
+//! Run `clc --help` for usage and options. The counting itself lives in the
+//! `code_line_count` library.
 
-#[doc(hidden)]
-mod cats;
-#[doc(hidden)]
-mod partition_n;
+mod chart;
+mod cloc;
+mod cocomo;
+mod codeowners;
+mod color;
+mod csv;
+mod diagnose;
+mod exec;
+mod fixtures;
+mod hotspots;
+mod html;
+mod inspect;
+mod leaderboard;
+mod multi;
+mod prom;
+mod query;
+mod review;
+mod scatter;
+mod schema;
+mod score;
+mod snapshot;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod stats;
+mod structured;
+mod table;
+mod template;
+mod terminal;
+mod timestamp;
+mod tree;
+mod validate;
+mod xml;
+mod yaml;
 
+use crate::{
+    chart::{ChartStyle, render_chart},
+    cloc::{cloc_counts, write_cloc_csv, write_cloc_text},
+    cocomo::Cocomo,
+    color::{BOLD, ColorChoice, RED, YELLOW, paint},
+    diagnose::zero_match_hints,
+    exec::{ExecTemplate, run_for_each},
+    fixtures::{FixtureMode, FixtureRules},
+    hotspots::{dir_totals, git_churn},
+    html::render_html,
+    inspect::{Encoding, Inspection, inspect},
+    leaderboard::{Leaderboard, rank, standings},
+    multi::{count_repos, read_repos_file},
+    prom::write_prometheus,
+    query::Query,
+    review::{REVIEW_LINES_PER_HOUR, git_changes, review_weight},
+    scatter::write_scatter,
+    score::{Weights, measure, score_metrics, total_score},
+    snapshot::SnapshotAction,
+    stats::{HISTOGRAM_BOUNDS, histogram, language_stats, outliers},
+    structured::{file_value, report_value},
+    table::{Table, elide},
+    template::{ROW_FIELDS, SUMMARY_FIELDS, Template},
+    terminal::terminal_width,
+    tree::render_tree,
+    validate::{Tool, lines_with_exts, parse_external},
+    xml::{REPORT_MAPS, to_xml},
+    yaml::to_yaml,
+};
 use clap::Parser;
-use ignore::{WalkBuilder, WalkState::Continue};
+#[cfg(feature = "plugins")]
+use code_line_count::plugins::PluginSet;
+use code_line_count::{
+    AllLines, BlankChars, CLCError, Category, CategoryID, CategoryRegistry, CodeLines,
+    CountOptions, FileCount, FinalLine, LineCounter, LineKinds, NonEmptyLines, Progress, Report,
+    UTF8_BOM,
+    comments::LineClassifier,
+    count_lines, count_lines_iter, count_lines_with_progress,
+    generated::is_generated,
+    json,
+    license::{license_header, strip_license_header},
+    logical::{count_statements, statement_syntax},
+    minified::{MINIFIED_LINE_LENGTH, average_line_length},
+    progress::{self, ProgressLine},
+    registry::CATEGORIES_FILE,
+    report::is_saturated,
+    roots::dedup_roots,
+    shebang::script_extension,
+    util::PartitionN,
+};
 use memchr::memchr_iter;
-use partition_n::PartitionN;
 use regex::bytes::Regex;
-use std::fmt::Write;
-use std::{fs, path::PathBuf, process::exit, sync::mpsc};
-use thiserror::Error;
-
-define_categories! {
-    Rust => {
-        names: ["rust", "rs"],
-        extensions: ["rs", "rlib"],
-    },
-    Haskell => {
-        names: ["haskell", "hs"],
-        extensions: ["hs", "lhs"],
-    },
-    Kotlin => {
-        names: ["kotlin", "kt"],
-        extensions: ["kt", "kts", "kexe", "klib"],
-    },
-    CSharp => {
-        names: ["csharp", "c#", "cdim"],
-        extensions: ["cs", "csx"],
-    },
-    Java => {
-        names: ["java"],
-        extensions: ["java", "class", "jmod", "war"],
-    },
-    Zig => {
-        names: ["zig"],
-        extensions: ["zig", "zir", "zigr", "zon"],
-    },
-    C => {
-        names: ["c"],
-        extensions: ["c", "h"],
-    },
-    GoLang => {
-        names: ["golang", "go"],
-        extensions: ["go"],
-    },
-    Cpp => {
-        names: ["cplusplus", "c++", "cpp", "hell"],
-        extensions: ["c", "C", "cc", "cpp", "cxx", "c++", "h", "H", "hh", "hpp", "hxx", "h++", "cppm", "ixx"],
-    },
-    Web => {
-        names: ["web", "webdev"],
-        extensions: ["js", "jsx", "ts", "tsx", "mjs", "cjs", "css", "scss", "sass", "less", "styl", "vue", "svelte", "astro"],
-    },
-    React => {
-        names: ["react"],
-        extensions: ["tsx", "jsx"],
-    },
-    TypeScript => {
-        names: ["typescript"],
-        extensions: ["tsx", "ts"],
-    },
-    JavaScript => {
-        names: ["javascript"],
-        extensions: ["jsx", "js"],
-    },
-    PHP => {
-        names: ["php"],
-        extensions: ["php", "phar", "phtml", "pht", "phps"],
-    },
-    Ruby => {
-        names: ["ruby"],
-        extensions: ["rb", "ru"],
-    },
-    Elixir => {
-        names: ["elixir", "ex"],
-        extensions: ["ex", "exs"],
-    },
-    Python => {
-        names: ["python", "py"],
-        extensions: ["py"],
-    },
-    Shell => {
-        names: ["shell"],
-        extensions: ["sh", "bash", "zsh", "fish"],
-    },
-    Styles => {
-        names: ["styles", "css"],
-        extensions: ["css", "scss", "sass", "less"],
-    },
-    Config => {
-        names: ["config", "cfg"],
-        extensions: ["toml", "yaml", "yml", "json", "cfg"],
-    },
-    Markup => {
-        names: ["markup"],
-        extensions: ["html", "md"],
-    },
-}
+use std::{
+    borrow::Cow,
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+};
+use std::{
+    env,
+    ffi::{OsStr, OsString},
+    fs::{self, File},
+    io::{self, BufWriter, IsTerminal, Write as _},
+    num::NonZeroUsize,
+    path::{Component, Path, PathBuf, is_separator},
+    process::exit,
+    str::FromStr,
+    sync::{Arc, Mutex, OnceLock},
+    thread,
+    time::{Duration, Instant},
+};
 
 #[doc(hidden)]
 #[derive(Parser)]
@@ -121,38 +127,21 @@ define_categories! {
 )]
 struct Args {
     #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
-    args: Vec<String>,
-}
-
-/// Errors that can occur during CLC operations.
-#[derive(Debug, Error)]
-pub enum CLCError {
-    /// Regex compilation or matching error.
-    ///
-    /// Derived from [`regex::Error`]
-    #[error("Regex error: {0}")]
-    RegexError(#[from] regex::Error),
-
-    /// I/O operation error.
-    ///
-    /// Derived from [`std::io::Error`]
-    #[error("I/O error: {0}")]
-    IOError(#[from] std::io::Error),
+    args: Vec<OsString>,
 }
 
-/// Generates a help string for clc
-#[must_use]
-pub fn gen_help() -> String {
+/// Lists every category with its extensions, for the help text.
+fn category_list(registry: &CategoryRegistry) -> String {
     let mut cat_strings = vec![];
     let mut ext_strings = vec![];
     let mut cat_list = "Categories:".to_string();
     let mut longest_cat_string = 0;
 
-    for cat in CategoryID::all_ids() {
+    for cat in registry {
         let mut cat_string = String::new();
         let mut ext_string = String::new();
 
-        for (idx, name) in CategoryID::names(*cat).iter().enumerate() {
+        for (idx, name) in cat.names().iter().enumerate() {
             match idx {
                 0 => cat_string += name,
                 _ => {
@@ -161,7 +150,7 @@ pub fn gen_help() -> String {
             }
         }
 
-        for (idx, ext) in CategoryID::extensions(*cat).iter().enumerate() {
+        for (idx, ext) in cat.extensions().iter().enumerate() {
             match idx {
                 0 => ext_string += ext,
                 _ => {
@@ -187,174 +176,3055 @@ pub fn gen_help() -> String {
         );
     }
 
+    cat_list
+}
+
+/// Generates a help string for clc
+#[must_use]
+pub fn gen_help(registry: &CategoryRegistry) -> String {
+    let cat_list = category_list(registry);
+
     format!(
-        "Usage: clc [OPTION | CATEGORY | .EXT]...
+        "Usage: clc [COMMAND] [OPTION | CATEGORY | .EXT | PATH]...
 Count non-empty lines of code in files matching CATEGORY or .EXT, recursively.
-Example: clc -g .py web -d2 .rs
+Searches PATH, or the current directory if no PATH is given.
+Example: clc -g .py web -d2 .rs src/ tests/
 
 Arguments may be given in any order:
   starting with '-'         option
+  containing '/', or . ..   path
   starting with '.'         file extension
-  otherwise                 category
+  otherwise                 category, or else a path if it exists
+
+Commands:
+  (none)                    print the total number of lines, per category if
+                            several are given
+  hotspots                  rank directories by lines, files, and churn
+  validate --against FILE   compare against a tokei or cloc JSON report
+  schema                    print the JSON Schema of structured output
+  score                     rate size health: comments, tests, size, duplication
+  diff [REV..REV]           lines changed per language in a git diff
+  multi --repos-file FILE   count each repository (path or URL) listed in FILE
+  file PATH                 explain how a single file is categorized and counted
+  snapshot save NAME        count and save the report under .clc/snapshots/
+  snapshot diff OLD NEW     compare the lines per language of two snapshots
+  export --sqlite FILE      count and append the run to an SQLite database
+
+Overlapping paths (e.g. '.' and './src') are searched once.
+On Unix, sending SIGUSR1 prints a progress snapshot to stderr. Counts taking
+over 200ms show live progress on stderr when both stdout and stderr are
+terminals.
+
+{NOTES_HELP}
 
 Options:
       --help                display this help text and exit
   -v, --version             display version and exit
   -dN                       set maximum search depth to N
+      --min-depth N         only count files at depth N or deeper
   -g, --git                 respect .gitignore files
   -h, --hidden              include hidden files and directories
+  -L, --follow              follow symlinks, junctions and reparse points
+      --resolve-symlinks    categorize file symlinks by their target's name
+      --max-memory SIZE     limit file bytes held in memory (e.g. 512M, 1G)
+      --threads N           use N threads (default: one per CPU)
+      --strict              exit with status 1 if any matched file cannot be
+                            read
+  -q, --quiet               print only the total, without warnings or notes
+  -V, --verbose             print counting statistics, skipped files, and the
+                            time taken to stderr
+      --list-empty          list files without any code to stderr
+      --summary             print how many files and directories were counted
+      --stats               print per-language lines-per-file statistics
+      --by-root             print the files and lines under each PATH
+      --by-file             print each file's lines, then the total, like wc -l
+      --by-ext              print the files and lines per extension
+      --by-dir[=N]          print the files and lines per directory, grouped up
+                            to N levels below each PATH (default 1)
+      --tree[=N]            draw the directories and files under each PATH as
+                            a tree with their lines, N levels deep (default all)
+      --top N               list the N files with the most lines
+      --color WHEN          color output: auto (default), always, or never
+      --no-truncate         print long paths in full instead of eliding them to
+                            fit the terminal's width
+      --chart[=STYLE]       draw a bar chart of each language's share of lines,
+                            in 'blocks' (default) or plain 'ascii' characters
+      --histogram[=B,...]   draw how many files have up to 50, 200, 500 (or B,
+                            ...) lines, and more
+      --outliers            list files over 10 times their language's median
+      --outlier-factor N    list files over N times the median instead of 10
+      --cocomo              estimate the effort, schedule, and cost of writing
+                            the counted lines with basic COCOMO, like scc
+      --fixtures MODE       include (default), exclude, or separate test
+                            fixtures
+      --fixture-patterns P  recognize fixtures by P instead of the default
+                            'fixtures/,testdata/,test-data/,snapshots/,
+                            __snapshots__/,golden/,*.golden,*.golden.*'
+      --export-scatter FILE write path, lines, last-modified, language as CSV
+      --exec CMD            run CMD for each counted file; {{path}}, {{lines}},
+                            and {{language}} are substituted
+      --ordered             run --exec commands one at a time, and print
+                            --format jsonl files once counted, in path order
+      --format FORMAT       print the report as json, jsonl (one line per file,
+                            as counted), yaml, xml, csv, an md table, an html
+                            page, a cloc table, or prom (Prometheus) metrics
+                            instead of the total
+      --csv                 with --format cloc, use cloc's --csv layout
+  -o, --output FILE         write the report to FILE, in the --format or the
+                            one of FILE's extension, and print the usual output
+      --format-template T   print T per language instead of the total; see above
+      --format-str T        same as --format-template
+      --summary-template T  print T after the rows instead of the total
+      --query EXPR          print part of the structured report instead of the
+                            total, e.g. 'languages.rust.lines'
 
+{COUNTING_OPTIONS_HELP}
+{MORE_OPTIONS_HELP}
 {cat_list}"
     )
 }
 
-/// Counts non-empty lines of code.
+/// Notes on which lines are counted and on templates, which [`gen_help`]
+/// gives before the options.
+const NOTES_HELP: &str = "\
+A last line without a trailing newline is counted by default. With
+'--final-line ignore' it is not; '--final-line wc-compat' counts newline
+characters exactly like 'wc -l', blank lines included.
+
+Blank lines hold only whitespace, Unicode whitespace such as no-break spaces
+included, and are skipped unless '--count-blank' is given. '--blank-chars'
+narrows them to lines of the given characters, where \\t, \\r, \\f, \\v, \\\\, and
+\\u{XXXX} are unescaped: with --blank-chars=' \\t', a line holding a form
+feed is code.
+
+Files without an extension are matched by their #! line: a script starting with
+'#!/usr/bin/env python3' counts as a .py file, and one starting with '#!/bin/sh'
+as a .sh file.
+
+Row templates may use {lang} (or {category}), {files}, {lines}, {median},
+{p90}, and {p99}; summary templates {languages}, {files}, and {lines}. Write
+{{ and }} for literal braces; \\t, \\n, and \\\\ are unescaped.";
+
+/// Help on the options deciding which lines are counted, which
+/// [`gen_help`] lists after the general options.
+const COUNTING_OPTIONS_HELP: &str = "\
+Counting options:
+      --final-line POLICY   count (default), ignore, or wc-compat; see above
+      --no-comments         skip lines holding only comments in the file's
+                            language, block comments followed across lines,
+                            counting only code
+      --breakdown           split lines into code, comment, and blank lines per
+                            file and category
+      --docstring-comments  count docstrings, as in Python, as comments
+      --count-blank         count blank lines too
+      --blank-chars CHARS   treat lines of only CHARS as blank, instead of any
+                            whitespace; see above
+      --logical             count statements instead of lines in languages
+                            ending them with ';', and in Python
+      --skip-license-header leave out license headers (MPL, Apache, MIT, SPDX)
+                            at the top of files
+      --include-generated   count files marked as generated (e.g. '@generated',
+                            'DO NOT EDIT') near the top, which are skipped
+      --include-minified    count minified files, with lines over 255 bytes long
+                            on average, which are skipped
+";
+
+/// Help on the options of `--cocomo` and of commands, which [`gen_help`]
+/// lists after the general options.
+const MORE_OPTIONS_HELP: &str = "\
+COCOMO options:
+      --cocomo-model TYPE   organic (default), semi-detached, or embedded
+      --avg-wage N          yearly wage of a developer (default 56286)
+      --overhead N          multiple of wages the project costs (default 2.4)
+      --eaf N               effort adjustment factor (default 1)
+
+Hotspot options:
+      --group-depth N       group by directories up to N levels deep (default 1)
+      --sort KEY            rank by lines (default), files, or churn
+      --churn               add git churn (lines added and deleted)
+
+Multi options:
+      --repos-file FILE     list of repositories, one path or URL per line
+      --leaderboard KIND    rank repo, language, or team (CODEOWNERS) by lines,
+                            growth since the latest snapshot, and duplication
+
+Diff options:
+      --staged-diff         diff staged changes (also works without 'diff')
+      --estimate-review     estimate the review time of the changes
+
+Score options:
+      --weights SPEC        weigh metrics, e.g. comments=2,duplication=0
+      --max-file-lines N    count files over N lines as large (default 500)
+";
+
+/// Finds extensions shared by more than one of the given categories.
+///
+/// Categories linked by shared extensions are grouped together. Each group
+/// lists its categories in the order they were given, along with every
+/// extension shared within it, ordered by first appearance.
+fn category_overlaps(cats: &[Category]) -> Vec<(Vec<&Category>, Vec<&str>)> {
+    let mut owners: Vec<(&str, Vec<&Category>)> = vec![];
+
+    for cat in cats {
+        for ext in cat.extensions() {
+            match owners.iter_mut().find(|(e, _)| e == ext) {
+                Some((_, ids)) if !ids.contains(&cat) => ids.push(cat),
+                Some(_) => {}
+                None => owners.push((ext, vec![cat])),
+            }
+        }
+    }
+
+    let mut groups: Vec<(Vec<&Category>, Vec<&str>)> = vec![];
+    for (ext, ids) in owners.into_iter().filter(|(_, ids)| ids.len() > 1) {
+        let (linked, mut rest): (Vec<_>, Vec<_>) = groups
+            .into_iter()
+            .partition(|(group, _)| group.iter().any(|id| ids.contains(id)));
+
+        let mut merged: (Vec<&Category>, Vec<&str>) = (ids, vec![]);
+        for (group, group_exts) in linked {
+            merged.0.extend(group);
+            merged.1.extend(group_exts);
+        }
+        merged.1.push(ext);
+
+        rest.push(merged);
+        groups = rest;
+    }
+
+    for (group, _) in &mut groups {
+        let mut ordered: Vec<&Category> = vec![];
+        for cat in cats {
+            if group.contains(&cat) && !ordered.contains(&cat) {
+                ordered.push(cat);
+            }
+        }
+        *group = ordered;
+    }
+
+    groups
+}
+
+/// Options that take a value, as either `--opt VALUE` or `--opt=VALUE`.
+const VALUE_FLAGS: &[&str] = &[
+    "--max-memory",
+    "--threads",
+    "--final-line",
+    "--blank-chars",
+    "--min-depth",
+    "--group-depth",
+    "--sort",
+    "--export-scatter",
+    "--against",
+    "--repos-file",
+    "--sqlite",
+    "--leaderboard",
+    "--fixtures",
+    "--fixture-patterns",
+    "--format",
+    "--exec",
+    "--format-template",
+    "--format-str",
+    "--summary-template",
+    "--query",
+    "--weights",
+    "--max-file-lines",
+    "--outlier-factor",
+    "--cocomo-model",
+    "--avg-wage",
+    "--overhead",
+    "--eaf",
+    "--top",
+    "--color",
+    "-o",
+    "--output",
+];
+
+/// Joins value options with a separately given value.
+///
+/// Turns `--max-memory 1G` into `--max-memory=1G`, so the value is not
+/// mistaken for a category once arguments are partitioned.
+fn join_flag_values(args: Vec<OsString>) -> Vec<OsString> {
+    let mut joined = Vec::with_capacity(args.len());
+    let mut args = args.into_iter();
+
+    while let Some(mut arg) = args.next() {
+        if VALUE_FLAGS.iter().any(|flag| arg == *flag)
+            && let Some(value) = args.next()
+        {
+            arg.push("=");
+            arg.push(value);
+        }
+        joined.push(arg);
+    }
+
+    joined
+}
+
+/// Parses a byte size such as `4096`, `512K`, `512M`, `1G`, or `1GiB`.
+///
+/// Suffixes are binary (powers of 1024) and case-insensitive.
+fn parse_size(size: &str) -> Option<u64> {
+    let size = size.to_ascii_uppercase();
+    let size = size
+        .strip_suffix("IB")
+        .or_else(|| size.strip_suffix('B'))
+        .unwrap_or(&size);
+
+    let (digits, shift) = match size.as_bytes().last()? {
+        b'K' => (&size[..size.len() - 1], 10),
+        b'M' => (&size[..size.len() - 1], 20),
+        b'G' => (&size[..size.len() - 1], 30),
+        b'T' => (&size[..size.len() - 1], 40),
+        _ => (size, 0),
+    };
+
+    digits.parse::<u64>().ok()?.checked_mul(1 << shift)
+}
+
+/// Loads the plugins from the plugins directory.
 ///
-/// Uses [`WalkBuilder`] from the [`ignore`] crate to create
-/// a multi-threaded code line counter.
+/// Plugins only claim files they classify as one of the requested
+/// categories or `languages`. Exits, suggesting the closest category of
+/// `registry`, if `languages` is not empty but there are no plugins that
+/// could provide them.
 ///
 /// # Errors
 ///
-/// Returns [`CLCError`] if regex compilation fails or if file I/O
-/// operations fail (e.g., permission denied, unable to read file contents).
-///
-/// # Example
-///
-/// ```
-/// let lines = count_lines(
-///     PathBuf::from("./"),
-///     &["rs", "hs"],
-///     true,
-///     true,
-///     None,
-/// )?;
-/// ```
-pub fn count_lines(
-    path: PathBuf,
-    exts: &[&str],
-    hidden: bool,
-    respect_git_ignore: bool,
-    maxdepth: Option<usize>,
-) -> Result<u128, CLCError> {
-    let re = &Regex::new(r"\n\s+")?;
-    let (tx, rx) = mpsc::channel();
-
-    WalkBuilder::new(path)
-        .hidden(!hidden)
-        .ignore(false)
-        .git_ignore(respect_git_ignore)
-        .max_depth(maxdepth)
-        .build_parallel()
-        .run(|| {
-            let tx = tx.clone();
-            Box::new(move |entry| {
-                let Ok(entry) = entry else { return Continue };
-
-                let path = entry.path();
-
-                if entry.file_type().is_none_or(|ft| !ft.is_file()) {
-                    return Continue;
-                }
+/// Returns [`CLCError`] if a plugin fails to load.
+#[cfg(feature = "plugins")]
+fn load_plugins(
+    cats: &[Category],
+    languages: &[String],
+    registry: &CategoryRegistry,
+) -> Result<Option<Arc<PluginSet>>, CLCError> {
+    let plugins = match PluginSet::dir() {
+        Some(dir) => {
+            let mut requested: Vec<String> = cats
+                .iter()
+                .flat_map(|cat| cat.names().iter().map(ToString::to_string))
+                .collect();
+            requested.extend_from_slice(languages);
+            PluginSet::load(&dir, requested)?.on_failure(print_warning)
+        }
+        None => return Ok(None),
+    };
+
+    if plugins.is_empty() {
+        if let Some(Err(err)) = languages.first().map(|name| registry.lookup(name)) {
+            usage_error(&err.to_string());
+        }
+        return Ok(None);
+    }
 
-                let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("");
+    Ok(Some(Arc::new(plugins)))
+}
 
-                if !exts.contains(&ext) {
-                    return Continue;
-                }
+/// Checks whether a command line argument names a path.
+fn is_path_arg(arg: &[u8]) -> bool {
+    arg == b"." || arg == b".." || arg.iter().any(|&b| is_separator(char::from(b)))
+}
 
-                let result = fs::read(path).map_or_else(
-                    |_| unreachable!(),
-                    |bytes| {
-                        memchr_iter(b'\n', &re.replace_all(&bytes, b"\n")).count()
-                            + usize::from(!bytes.ends_with(b"\n"))
-                    },
-                );
+/// Strips the leading `.` of an extension argument.
+///
+/// Works on the raw bytes, so extensions that are not valid UTF-8 survive.
+#[cfg(unix)]
+fn strip_ext_dot(arg: &OsStr) -> &OsStr {
+    use std::os::unix::ffi::OsStrExt;
 
-                tx.send(result).ok();
+    OsStr::from_bytes(arg.as_bytes().get(1..).unwrap_or_default())
+}
 
-                Continue
-            })
-        });
+/// Strips the leading `.` of an extension argument.
+///
+/// Extensions that are not valid UTF-8 cannot be sliced safely outside of
+/// Unix and become empty.
+#[cfg(not(unix))]
+fn strip_ext_dot(arg: &OsStr) -> &OsStr {
+    OsStr::new(arg.to_str().and_then(|s| s.get(1..)).unwrap_or(""))
+}
+
+/// Explains on stderr why a count came up empty.
+///
+/// If files matched, tells why none of them had lines to count; otherwise
+/// works out which filters kept files out.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if the diagnostic recounts fail.
+fn print_zero_match_hints(
+    roots: &[PathBuf],
+    exts: &[&OsStr],
+    options: &CountOptions,
+    report: &Report,
+) -> Result<(), CLCError> {
+    if report.files_matched() > 0 {
+        let reasons: Vec<String> = [
+            (report.empty_files().count(), "empty"),
+            (report.errors.len(), "unreadable"),
+            (
+                usize::try_from(report.vanished).unwrap_or(usize::MAX),
+                "vanished",
+            ),
+            (report.generated.len(), "generated"),
+            (report.minified.len(), "minified"),
+        ]
+        .into_iter()
+        .filter(|&(files, _)| files > 0)
+        .map(|(files, reason)| format!("{files} {reason}"))
+        .collect();
+        print_note(&format!(
+            "{} file(s) matched, but none had lines to count: {}",
+            report.files_matched(),
+            reasons.join(", ")
+        ));
+        return Ok(());
+    }
+
+    let hints = zero_match_hints(roots, exts, options)?;
+
+    if hints.is_empty() {
+        print_note(
+            "no files with the requested extensions were found; \
+             check the paths and extensions given",
+        );
+        return Ok(());
+    }
 
-    drop(tx);
-    Ok(rx.iter().map(|n| n as u128).sum())
+    print_note("nothing was counted; files were left out by these filters:");
+    let width = hints[0].files.to_string().len();
+    for hint in hints {
+        eprintln!("  {:>width$} {} ({})", hint.files, hint.filter, hint.advice);
+    }
+
+    Ok(())
 }
 
-#[doc(hidden)]
-fn main() -> Result<(), CLCError> {
-    let mut exts: Vec<&str> = vec![];
-    let mut hidden = false;
-    let mut respect_git_ignore = false;
-    let mut maxdepth = None;
-    let depth_re = Regex::new(r"^\-d[1-9][0-9]+$")?;
-    let args = Args::parse().args;
-
-    let [flags, extargs, cats]: [Vec<&str>; 3] =
-        args.iter().map(String::as_str).partition_n(|arg| {
-            if arg.starts_with('-') {
-                0
-            } else if arg.starts_with('.') {
-                1
-            } else {
-                2
-            }
-        });
+/// What clc was asked to do.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+enum Command {
+    /// Print the total number of lines.
+    #[default]
+    Count,
+    /// Rank directories by lines, files, and churn.
+    Hotspots,
+    /// Compare counts against a tokei or cloc report.
+    Validate,
+    /// Print the schema of structured output.
+    Schema,
+    /// Save or compare report snapshots.
+    Snapshot(SnapshotAction),
+    /// Explain how a single file is counted.
+    File(PathBuf),
+    /// Rate the code base's size health.
+    Score,
+    /// Summarize the lines changed in a git diff, optionally in a range.
+    Diff(Option<String>),
+    /// Count several repositories with the same settings.
+    Multi,
+    /// Append the report to a database.
+    Export,
+}
 
-    let flags = flags.clone();
+/// Key used to rank hotspots.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum SortKey {
+    /// Total lines.
+    #[default]
+    Lines,
+    /// Number of files.
+    Files,
+    /// Git churn.
+    Churn,
+}
 
-    for flag in flags {
-        match flag.as_bytes() {
-            b"--help" => {
-                println!("{}", gen_help());
-                exit(0)
+/// Splits off a leading command name, if there is one.
+///
+/// Exits if a command's own arguments are missing or invalid.
+fn split_command(mut args: Vec<OsString>) -> (Command, Vec<OsString>) {
+    let command = match args.first().and_then(|arg| arg.to_str()) {
+        Some("hotspots") => Command::Hotspots,
+        Some("validate") => Command::Validate,
+        Some("schema") => Command::Schema,
+        Some("score") => Command::Score,
+        Some("multi") => Command::Multi,
+        Some("export") => Command::Export,
+        Some("diff") => {
+            args.remove(0);
+            let range = args
+                .first()
+                .and_then(|arg| arg.to_str())
+                .filter(|arg| arg.contains("..") && !arg.starts_with('-') && *arg != "..")
+                .map(ToString::to_string);
+            if range.is_some() {
+                args.remove(0);
             }
-            b"-v" | b"--version" => {
-                println!("clc {}", env!("CARGO_PKG_VERSION"));
-                exit(0)
+            return (Command::Diff(range), args);
+        }
+        Some("file") => {
+            if args.len() < 2 {
+                usage_error("usage: clc file PATH");
             }
-            b"-h" | b"--hidden" => hidden = true,
-            b"-g" | b"--git" => respect_git_ignore = true,
-            flag_bytes if depth_re.is_match(flag_bytes) => {
-                maxdepth = flag[2..].parse().ok();
+            let path = PathBuf::from(args.remove(1));
+            args.remove(0);
+            return (Command::File(path), args);
+        }
+        Some("snapshot") => {
+            args.remove(0);
+            return (Command::Snapshot(split_snapshot_action(&mut args)), args);
+        }
+        _ => return (Command::Count, args),
+    };
+
+    args.remove(0);
+    (command, args)
+}
+
+/// Splits off the action and snapshot names following `snapshot`.
+fn split_snapshot_action(args: &mut Vec<OsString>) -> SnapshotAction {
+    let (arity, usage) = match args.first().and_then(|arg| arg.to_str()) {
+        Some("save") => (1, "snapshot save NAME"),
+        Some("diff") => (2, "snapshot diff OLD NEW"),
+        _ => usage_error("snapshot needs 'save NAME' or 'diff OLD NEW'"),
+    };
+
+    if args.len() <= arity {
+        usage_error(&format!("usage: clc {usage}"));
+    }
+
+    let mut names = args.drain(..=arity).skip(1).map(|name| {
+        let name = name.to_string_lossy().into_owned();
+        snapshot::check_name(&name).unwrap_or_else(|err| usage_error(&err));
+        name
+    });
+
+    match (names.next(), names.next()) {
+        (Some(old), Some(new)) => SnapshotAction::Diff(old, new),
+        (Some(name), None) => SnapshotAction::Save(name),
+        _ => unreachable!("the arity was checked"),
+    }
+}
+
+/// Lists the rules that decide whether and how a file is counted.
+fn file_rules(
+    path: &Path,
+    cats: &[&str],
+    info: &Inspection,
+    contents: &[u8],
+    blank: u64,
+    options: &CountOptions,
+) -> Vec<String> {
+    let ext = script_extension(path, contents).unwrap_or_default();
+    let mut rules = vec![];
+    if path.extension().is_none() && !ext.is_empty() {
+        rules.push(format!(
+            "has no extension, but its #! line names a .{} interpreter",
+            ext.to_string_lossy()
+        ));
+    }
+    if cats.is_empty() {
+        rules.push(format!(
+            "no category lists extension \"{}\"; request it as .{0}",
+            ext.to_string_lossy()
+        ));
+    } else {
+        rules.push(format!(
+            "extension \"{}\" is listed by {}; the first one requested gets the file",
+            ext.to_string_lossy(),
+            cats.join(", ")
+        ));
+    }
+    if path.is_symlink() {
+        rules.push("is a symlink, skipped unless -L or --resolve-symlinks is given".to_string());
+    }
+    if path
+        .components()
+        .any(|c| matches!(c, Component::Normal(name) if name.as_encoded_bytes().starts_with(b".")))
+    {
+        rules.push("is hidden, skipped unless -h is given".to_string());
+    }
+    if is_generated(contents) {
+        rules.push(if options.include_generated {
+            "is marked as generated, counted because of --include-generated".to_string()
+        } else {
+            "is marked as generated, skipped unless --include-generated is given".to_string()
+        });
+    }
+    let line_length = average_line_length(contents);
+    if line_length > MINIFIED_LINE_LENGTH {
+        rules.push(format!(
+            "lines are {line_length} bytes long on average, over {MINIFIED_LINE_LENGTH}, so it is \
+             minified and {}",
+            if options.include_minified {
+                "counted because of --include-minified"
+            } else {
+                "skipped unless --include-minified is given"
             }
-            _ => {
-                println!(
-                    "clc: flag \"{flag}\" not found\nTry 'clc --help' for more information on how to use clc."
-                );
-                exit(0)
+        ));
+    }
+    if info.encoding == Encoding::Utf8Bom {
+        rules.push("the UTF-8 byte order mark is ignored".to_string());
+    }
+    if blank > 0 && options.final_line != FinalLine::WcCompat {
+        rules.push(if options.count_blank {
+            format!("{blank} blank line(s) are counted (--count-blank)")
+        } else if options.blank_chars == BlankChars::Whitespace {
+            format!("{blank} blank or whitespace-only line(s) are skipped")
+        } else {
+            format!("{blank} line(s) of only --blank-chars characters are skipped")
+        });
+    }
+    if !info.final_newline && info.physical_lines > 0 {
+        rules.push(
+            match options.final_line {
+                FinalLine::Count => "the unterminated last line is counted (--final-line count)",
+                FinalLine::Ignore => {
+                    "the unterminated last line is not counted (--final-line ignore)"
+                }
+                FinalLine::WcCompat => {
+                    "the unterminated last line is not counted (--final-line wc-compat)"
+                }
             }
+            .to_string(),
+        );
+    }
+    if options.skip_license_header {
+        rules.push(license_header(path, contents).map_or_else(
+            || "no license header was found at the top".to_string(),
+            |header| {
+                let lines = memchr_iter(b'\n', &contents[header]).count();
+                format!("the {lines}-line license header is left out (--skip-license-header)")
+            },
+        ));
+    }
+    if options.logical {
+        rules.push(if statement_syntax(&ext.to_string_lossy()).is_some() {
+            "statements are counted instead of lines (--logical)".to_string()
+        } else {
+            format!(
+                "statements of .{} files cannot be told apart, so lines are counted",
+                ext.to_string_lossy()
+            )
+        });
+    }
+    if matches!(
+        info.encoding,
+        Encoding::Utf16Le | Encoding::Utf16Be | Encoding::Binary
+    ) {
+        rules.push("the file is not ASCII-compatible, so its count is unreliable".to_string());
+    }
+
+    rules
+}
+
+/// Prints how a single file is categorized and counted.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if the file cannot be read.
+fn print_file_inspection(
+    path: &Path,
+    registry: &CategoryRegistry,
+    options: &CountOptions,
+) -> Result<(), CLCError> {
+    let bytes = fs::read(path).map_err(|source| CLCError::ReadFile {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let info = inspect(&bytes);
+    let unstripped = bytes.strip_prefix(UTF8_BOM).unwrap_or(&bytes);
+    let stripped = if options.skip_license_header {
+        strip_license_header(path, unstripped)
+    } else {
+        Cow::Borrowed(unstripped)
+    };
+    let contents = &*stripped;
+    let classifier = line_classifier(options);
+    let kinds = classifier.classify_file(path, contents);
+    let physical = if options.skip_comments {
+        CodeLines::new(options.final_line, classifier.clone())
+            .count_blank(options.count_blank)
+            .count_file(path, contents)
+    } else if options.count_blank {
+        AllLines::new(options.final_line).count(contents)
+    } else {
+        NonEmptyLines::new(options.final_line)?
+            .blank_chars(options.blank_chars.clone())
+            .count(contents)
+    };
+    let lines = options
+        .logical
+        .then(|| count_statements(path, contents))
+        .flatten()
+        .unwrap_or(physical);
+
+    let ext = script_extension(path, contents).unwrap_or_default();
+    let comments = if !options.skip_comments {
+        "not distinguished, counted as lines".to_string()
+    } else if classifier.syntax_of(path, contents).is_some() {
+        format!("{} line(s), skipped", kinds.comment)
+    } else {
+        "syntax unknown, counted as lines".to_string()
+    };
+    let cats: Vec<&str> = registry
+        .iter()
+        .filter(|cat| {
+            cat.extensions().iter().any(|e| {
+                OsStr::new(e) == ext
+                    || (options.ignore_ext_case && OsStr::new(e).eq_ignore_ascii_case(ext))
+            })
+        })
+        .map(Category::name)
+        .collect();
+
+    let longest = if info.longest_line_number == 0 {
+        "-".to_string()
+    } else {
+        format!(
+            "{} {} (line {})",
+            info.longest_line,
+            if matches!(info.encoding, Encoding::Unknown | Encoding::Binary) {
+                "bytes"
+            } else {
+                "characters"
+            },
+            info.longest_line_number
+        )
+    };
+
+    let fields = [
+        ("path", path.display().to_string()),
+        (
+            "category",
+            cats.first().map_or_else(
+                || format!(".{}", ext.to_string_lossy()),
+                ToString::to_string,
+            ),
+        ),
+        ("lines", lines.to_string()),
+        ("blank", kinds.blank.to_string()),
+        ("physical", info.physical_lines.to_string()),
+        ("comments", comments),
+        ("longest line", longest),
+        ("encoding", info.encoding.to_string()),
+        ("line endings", info.line_endings.to_string()),
+        (
+            "final newline",
+            if info.final_newline { "yes" } else { "no" }.to_string(),
+        ),
+    ];
+
+    for (name, value) in fields {
+        println!("{:<14} {value}", format!("{name}:"));
+    }
+    println!("rules:");
+    for rule in file_rules(path, &cats, &info, unstripped, kinds.blank, options) {
+        println!("  - {rule}");
+    }
+
+    Ok(())
+}
+
+/// Prints the lines changed per language in a git diff, and with
+/// `--estimate-review`, how long the diff takes to review.
+///
+/// Only files with one of `exts` are included, or all files if none are
+/// given.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if git fails.
+fn print_diff(
+    range: Option<&str>,
+    root: &Path,
+    cats: &[Category],
+    exts: &[OsString],
+    output: &Output,
+) -> Result<(), CLCError> {
+    let changes = git_changes(root, range, output.staged_diff)?;
+
+    let mut by_language: BTreeMap<String, (u64, u64, u64, f64)> = BTreeMap::new();
+    for change in &changes {
+        let ext = change.path.extension().unwrap_or_default();
+        if !exts.is_empty() && !exts.iter().any(|e| e == ext) {
+            continue;
         }
+        let entry = by_language
+            .entry(language_of_path(&change.path, cats))
+            .or_default();
+        entry.0 += 1;
+        entry.1 = entry.1.saturating_add(change.added);
+        entry.2 = entry.2.saturating_add(change.deleted);
+        entry.3 += change.review_lines(review_weight(&ext.to_string_lossy()));
     }
 
-    for cat_name in cats {
-        if let Some(cat_id) = CategoryID::from_name(cat_name) {
-            exts.extend(cat_id.extensions());
-        } else {
-            println!(
-                "clc: category {cat_name} not found\nTry 'clc --help' for more information on how to use clc."
-            );
-            exit(0)
+    let minutes = |lines: f64| lines / REVIEW_LINES_PER_HOUR * 60.0;
+    let mut total: (u64, u64, u64, f64) = (0, 0, 0, 0.0);
+    let mut rows: Vec<Vec<String>> = vec![];
+    for (language, (files, added, deleted, review)) in by_language {
+        total.0 += files;
+        total.1 = total.1.saturating_add(added);
+        total.2 = total.2.saturating_add(deleted);
+        total.3 += review;
+        let mut row = vec![
+            language,
+            files.to_string(),
+            added.to_string(),
+            deleted.to_string(),
+        ];
+        if output.estimate_review {
+            row.push(format!("{:.0}", minutes(review)));
         }
+        rows.push(row);
+    }
+    let mut row = vec![
+        "total".to_string(),
+        total.0.to_string(),
+        total.1.to_string(),
+        total.2.to_string(),
+    ];
+    if output.estimate_review {
+        row.push(format!("{:.0}", minutes(total.3)));
     }
 
-    for ext in extargs {
-        exts.push(ext.get(1..).unwrap_or(""));
+    let mut header = vec!["language", "files", "added", "deleted"];
+    if output.estimate_review {
+        header.push("review min");
+    }
+    print_table_with_total(&header, &rows, row);
+
+    if output.estimate_review {
+        let minutes = minutes(total.3);
+        println!(
+            "\nestimated review time: {minutes:.0} min ({:.1} h)",
+            minutes / 60.0
+        );
+    }
+
+    Ok(())
+}
+
+/// Gets the classifier telling code, comment, and blank lines apart as the
+/// options do, leaving license headers in.
+fn line_classifier(options: &CountOptions) -> LineClassifier {
+    LineClassifier {
+        docstrings: options.docstring_comments,
+        blank: options.blank_chars.clone(),
+        skip_license_header: false,
+        categories: options.categories.clone(),
+    }
+}
+
+/// Default line count above which a file counts as large when scoring.
+const DEFAULT_MAX_FILE_LINES: u64 = 500;
+
+/// Prints the size health score with a breakdown by metric.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if a counted file cannot be read again.
+fn print_score(report: &Report, options: &CountOptions, output: &Output) -> Result<(), CLCError> {
+    let max_file_lines = output.max_file_lines.unwrap_or(DEFAULT_MAX_FILE_LINES);
+    let measures = measure(report, max_file_lines, &line_classifier(options))?;
+    let metrics = score_metrics(&measures, &output.weights);
+
+    let rows: Vec<Vec<String>> = metrics
+        .iter()
+        .map(|c| {
+            vec![
+                c.metric.name().to_string(),
+                format!("{:.1}", c.value * 100.0),
+                format!("{:.1}", c.score),
+                c.weight.to_string(),
+            ]
+        })
+        .collect();
+
+    print_table(&["metric", "value %", "score", "weight"], &rows);
+    println!("\nscore: {:.1}/100", total_score(&metrics));
+    println!(
+        "\ncomments: share of comment lines, as --no-comments finds them\n\
+         tests: lines in test files per line of other code\n\
+         size: share of files over {max_file_lines} lines\n\
+         duplication: share of lines of {} or more characters repeated elsewhere",
+        score::MIN_DUPLICATE_LEN
+    );
+
+    Ok(())
+}
+
+/// Prints the per-language change in lines between two snapshots.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if either snapshot cannot be loaded.
+fn print_snapshot_diff(old: &str, new: &str) -> Result<(), CLCError> {
+    for name in [old, new] {
+        if !snapshot::exists(name) {
+            usage_error(&format!("no snapshot named \"{name}\""));
+        }
+    }
+
+    let rows: Vec<Vec<String>> = snapshot::diff(&snapshot::load(old)?, &snapshot::load(new)?)
+        .into_iter()
+        .map(|diff| {
+            vec![
+                diff.name.clone(),
+                diff.before.to_string(),
+                diff.after.to_string(),
+                format!("{:+}", diff.change()),
+            ]
+        })
+        .collect();
+
+    print_table(&["language", old, new, "change"], &rows);
+    Ok(())
+}
+
+/// Command line settings that only affect what is printed.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Debug, Default)]
+struct Output {
+    /// Directory depth hotspots are grouped by.
+    group_depth: Option<usize>,
+    /// Key hotspots are ranked by.
+    sort: SortKey,
+    /// Compute git churn for hotspots.
+    churn: bool,
+    /// Print only the total, without warnings or notes.
+    quiet: bool,
+    /// Print long paths in full, however wide the terminal.
+    no_truncate: bool,
+    /// Print counting statistics to stderr.
+    verbose: bool,
+    /// List files without any code to stderr.
+    list_empty: bool,
+    /// Fail if any matched file could not be read.
+    strict: bool,
+    /// Print per-language statistics after the total.
+    stats: bool,
+    /// Print the files and directories counted after the total.
+    summary: bool,
+    /// Print each root's files and lines after the total.
+    by_root: bool,
+    /// Print each file's lines, then the total, instead of only the total.
+    by_file: bool,
+    /// When to color output.
+    color: ColorChoice,
+    /// Number of files with the most lines to list after the total.
+    top: Option<usize>,
+    /// Print each extension's files and lines after the total.
+    by_ext: bool,
+    /// Directory depth to print each directory's files and lines by, after
+    /// the total.
+    by_dir: Option<usize>,
+    /// Levels below each path to draw as a tree of lines, if any.
+    tree: Option<usize>,
+    /// Draw a bar chart of language shares after the total, in this style.
+    chart: Option<ChartStyle>,
+    /// Upper bounds of the buckets to draw a histogram of files by lines
+    /// with, if any.
+    histogram: Option<Vec<u64>>,
+    /// Write a size-versus-age CSV dataset to this file.
+    export_scatter: Option<PathBuf>,
+    /// Print cloc's `--csv` layout with `--format cloc`.
+    cloc_csv: bool,
+    /// File to write the formatted report to, leaving stdout to the usual
+    /// output.
+    report_file: Option<PathBuf>,
+    /// Database `export` appends the report to.
+    sqlite: Option<PathBuf>,
+    /// Report to validate against.
+    against: Option<PathBuf>,
+    /// Name given to `--format`, which the schema command reads as a
+    /// schema format.
+    format_name: Option<String>,
+    /// Format the report is printed in, or written to the `-o` file in.
+    format: Option<OutputFormat>,
+    /// Command to run for each counted file.
+    exec: Option<ExecTemplate>,
+    /// Run `--exec` commands one at a time, in path order.
+    ordered: bool,
+    /// Template printed once per language instead of the total.
+    row_template: Option<Template>,
+    /// Template printed once after the rows instead of the total.
+    summary_template: Option<Template>,
+    /// Query whose result is printed instead of the total.
+    query: Option<Query>,
+    /// Weights of the score metrics.
+    weights: Weights,
+    /// Lines above which a file counts as large when scoring.
+    max_file_lines: Option<u64>,
+    /// What to do with test fixtures and sample data, if set.
+    fixtures: Option<FixtureMode>,
+    /// Patterns recognizing fixtures, if not the default ones.
+    fixture_rules: Option<FixtureRules>,
+    /// Whether to list files far longer than their language's median.
+    outliers: bool,
+    /// Multiple of the median above which a file is an outlier.
+    outlier_factor: Option<f64>,
+    /// Parameters to estimate the cost of the counted code with, if any.
+    cocomo: Option<Cocomo>,
+    /// File listing the repositories to count with `multi`.
+    repos_file: Option<PathBuf>,
+    /// What `multi` ranks, if it prints a leaderboard.
+    leaderboard: Option<Leaderboard>,
+    /// Diff staged changes instead of unstaged ones.
+    staged_diff: bool,
+    /// Estimate how long a diff takes to review.
+    estimate_review: bool,
+}
+
+/// Gets the language a file is attributed to.
+///
+/// That is the language assigned by the plugin that counted it, or else
+/// the one given by [`language_of_ext`] for its extension, or the one its
+/// `#!` line gives it.
+fn language_of(file: &FileCount, cats: &[Category]) -> String {
+    file.language
+        .clone()
+        .unwrap_or_else(|| language_of_ext(file.extension(), cats))
+}
+
+/// Gets the language a path is attributed to by its extension.
+fn language_of_path(path: &Path, cats: &[Category]) -> String {
+    language_of_ext(path.extension().unwrap_or_default(), cats)
+}
+
+/// Gets the language an extension is attributed to.
+///
+/// That is the first of the requested categories listing the extension,
+/// or the extension itself (e.g. `.txt`) if none does.
+fn language_of_ext(ext: &OsStr, cats: &[Category]) -> String {
+    cats.iter()
+        .find(|cat| cat.extensions().iter().any(|e| OsStr::new(e) == ext))
+        .map_or_else(
+            || format!(".{}", ext.to_string_lossy()),
+            |cat| cat.name().to_string(),
+        )
+}
+
+/// Prints per-language lines-per-file statistics as an aligned table.
+fn print_stats(report: &Report, cats: &[Category]) {
+    let rows: Vec<Vec<String>> = language_stats(report, |file| language_of(file, cats))
+        .into_iter()
+        .map(|stats| {
+            vec![
+                stats.name,
+                stats.files.to_string(),
+                stats.lines.to_string(),
+                stats.median.to_string(),
+                stats.p90.to_string(),
+                stats.p99.to_string(),
+            ]
+        })
+        .collect();
+
+    print_table_with_total(
+        &["language", "files", "lines", "median", "p90", "p99"],
+        &rows,
+        totals_row(report),
+    );
+
+    let mut metrics: BTreeMap<&str, u64> = BTreeMap::new();
+    for file in &report.files {
+        for (name, value) in &file.metrics {
+            let total = metrics.entry(name).or_default();
+            *total = total.saturating_add(*value);
+        }
+    }
+
+    if !metrics.is_empty() {
+        println!();
+        let rows: Vec<Vec<String>> = metrics
+            .into_iter()
+            .map(|(name, total)| vec![name.to_string(), total.to_string()])
+            .collect();
+        print_table(&["metric", "total"], &rows);
+    }
+}
+
+/// Prints the files and lines under each root as an aligned table.
+fn print_by_root(report: &Report, roots: &[PathBuf]) {
+    let rows: Vec<Vec<String>> = report
+        .by_root(roots)
+        .into_iter()
+        .map(|(root, totals)| {
+            share_row(
+                root.display().to_string(),
+                usize::try_from(totals.files).unwrap_or(usize::MAX),
+                totals.lines,
+                totals.kinds,
+                report,
+            )
+        })
+        .collect();
+
+    print_table_with_total(
+        &share_header("path", report),
+        &rows,
+        share_totals_row(report),
+    );
+}
+
+/// Prints the `count` files with the most lines as an aligned table,
+/// largest first and by path among equals.
+fn print_top(report: &Report, count: usize) {
+    let mut files: Vec<&FileCount> = report.files.iter().collect();
+    files.sort_by_key(|file| Reverse(file.lines));
+
+    let rows: Vec<Vec<String>> = files
+        .into_iter()
+        .take(count)
+        .enumerate()
+        .map(|(idx, file)| {
+            vec![
+                (idx + 1).to_string(),
+                file.path.display().to_string(),
+                file.lines.to_string(),
+            ]
+        })
+        .collect();
+
+    print_table(&["#", "path", "lines"], &rows);
+}
+
+/// Prints the files and lines of each extension as an aligned table,
+/// sorted by extension.
+fn print_by_ext(report: &Report) {
+    let rows: Vec<Vec<String>> = language_stats(report, |file| {
+        format!(".{}", file.extension().to_string_lossy())
+    })
+    .into_iter()
+    .map(|stats| share_row(stats.name, stats.files, stats.lines, stats.kinds, report))
+    .collect();
+
+    print_table_with_total(
+        &share_header("extension", report),
+        &rows,
+        share_totals_row(report),
+    );
+}
+
+/// Prints the files and lines of each directory, grouped up to `depth`
+/// levels below its root, as an aligned table sorted by directory.
+fn print_by_dir(report: &Report, roots: &[PathBuf], depth: usize) {
+    let rows: Vec<Vec<String>> = dir_totals(report, roots, depth, None::<&HashMap<PathBuf, u64>>)
+        .into_iter()
+        .map(|dir| {
+            share_row(
+                dir.dir.display().to_string(),
+                dir.files,
+                dir.lines,
+                dir.kinds,
+                report,
+            )
+        })
+        .collect();
+
+    print_table_with_total(
+        &share_header("directory", report),
+        &rows,
+        share_totals_row(report),
+    );
+}
+
+/// Prints the files and lines of each requested category, in the order
+/// given, then of each extension matched outside them, then the total.
+///
+/// Every file is counted once, under the first category listing its
+/// extension, so the rows add up to the total.
+fn print_by_category(report: &Report, cats: &[Category]) {
+    let mut stats = language_stats(report, |file| language_of(file, cats));
+
+    let mut rows: Vec<Vec<String>> = cats
+        .iter()
+        .map(|cat| {
+            let (files, lines, kinds) = stats
+                .iter()
+                .position(|stats| stats.name == cat.name())
+                .map_or((0, 0, None), |idx| {
+                    let stats = stats.remove(idx);
+                    (stats.files, stats.lines, stats.kinds)
+                });
+            share_row(cat.name().to_string(), files, lines, kinds, report)
+        })
+        .collect();
+    rows.extend(
+        stats
+            .into_iter()
+            .map(|stats| share_row(stats.name, stats.files, stats.lines, stats.kinds, report)),
+    );
+    print_table_with_total(
+        &share_header("category", report),
+        &rows,
+        share_totals_row(report),
+    );
+}
+
+/// Prints each file's lines, sorted by path, then the total, like `wc -l`.
+///
+/// Paths are elided to fit the terminal, like the tables.
+fn print_by_file(report: &Report) {
+    let total = report.total_lines().to_string();
+    let width = total.len();
+    let path_width = table_width().map(|max| max.saturating_sub(width + 1).max(12));
+
+    for file in &report.files {
+        let mut path = file.path.display().to_string();
+        if let Some(max) = path_width {
+            path = elide(&path, max);
+        }
+        println!("{:>width$} {path}", file.lines);
+    }
+    println!("{total:>width$} total");
+}
+
+/// Moves test fixtures out of `report` unless they are to be included,
+/// returning them if so.
+///
+/// Setting `--fixture-patterns` without `--fixtures` excludes fixtures.
+fn split_fixtures(report: &mut Report, output: &Output) -> Option<Report> {
+    match (output.fixtures, &output.fixture_rules) {
+        (Some(FixtureMode::Include), _) | (None, None) => None,
+        (_, Some(rules)) => Some(rules.split_off(report)),
+        (_, None) => Some(FixtureRules::default().split_off(report)),
+    }
+}
+
+/// Prints the result of the default command: the total, or what the query
+/// or templates select, followed by any requested statistics, per-root
+/// totals, chart, and outliers. With `--fixtures separate`, the `fixtures`
+/// split off the report are summarized last.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if the query fails.
+fn print_count(
+    report: &Report,
+    fixtures: Option<&Report>,
+    cats: &[Category],
+    roots: &[PathBuf],
+    output: &Output,
+) -> Result<(), CLCError> {
+    if let (Some(path), Some(format)) = (&output.report_file, output.format) {
+        write_output_file(path, format, report, cats, roots, output)?;
+    }
+
+    if output.quiet {
+        println!("{}", report.total_lines());
+        return Ok(());
     }
 
-    if exts.is_empty() {
-        println!("clc: missing operand\nTry 'clc --help' for more information on how to use clc.");
-        exit(0)
+    if let Some(query) = &output.query {
+        let value = report_value(report, |file| language_of(file, cats));
+        match query.eval(&value).map_err(CLCError::QueryError)? {
+            json::Value::String(s) => println!("{s}"),
+            value => println!("{}", value.pretty()),
+        }
+    } else if output.row_template.is_some() || output.summary_template.is_some() {
+        print_templates(report, cats, output);
+    } else if let (Some(format), None) = (output.format, &output.report_file) {
+        match write_formatted(
+            &mut io::stdout().lock(),
+            report,
+            cats,
+            roots,
+            output,
+            format,
+        ) {
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => {}
+            result => result?,
+        }
+    } else if output.by_file {
+        print_by_file(report);
+    } else if cats.len() > 1 || report.total_kinds().is_some() {
+        print_by_category(report, cats);
+    } else {
+        println!("{}", paint(report.total_lines(), BOLD, stdout_color()));
+    }
+
+    if output.summary {
+        println!(
+            "{} file(s) across {} director(ies)",
+            report.files.len(),
+            report.dirs_with_files()
+        );
+    }
+
+    if output.stats {
+        print_stats(report, cats);
+    }
+
+    if output.by_root {
+        print_by_root(report, roots);
+    }
+
+    if output.by_ext {
+        print_by_ext(report);
+    }
+
+    if let Some(depth) = output.by_dir {
+        print_by_dir(report, roots, depth);
+    }
+    if let Some(depth) = output.tree {
+        print!("{}", render_tree(report, roots, depth, stdout_color()));
     }
 
-    let lines = count_lines(
-        PathBuf::from("./"),
-        &exts,
-        hidden,
-        respect_git_ignore,
-        maxdepth,
-    )?;
+    if let Some(count) = output.top {
+        print_top(report, count);
+    }
 
-    println!("{lines}");
+    if let Some(bounds) = &output.histogram {
+        print_histogram(report, bounds, output.chart.unwrap_or_default());
+    }
+
+    if let Some(style) = output.chart {
+        print_chart(report, cats, style);
+    }
+
+    if output.outliers {
+        print_outliers(report, cats, output);
+    }
+
+    if let Some(cocomo) = &output.cocomo {
+        print_cocomo(report, cocomo);
+    }
+
+    if let Some(fixtures) = fixtures.filter(|_| output.fixtures == Some(FixtureMode::Separate)) {
+        println!(
+            "fixtures: {} lines in {} file(s), not counted above",
+            fixtures.total_lines(),
+            fixtures.files.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Exits with a usage error if `-q` is combined with `-V` or with flags
+/// printing more than the total.
+fn check_quiet(output: &Output) {
+    if output.quiet && output.verbose {
+        usage_error("-q and -V cannot be combined");
+    }
+    if output.quiet
+        && let Some(flag) = printing_flags(output).first()
+    {
+        usage_error(&format!(
+            "-q and {flag} cannot be combined; -q prints only the total"
+        ));
+    }
+}
+
+/// Lists the given flags that print more than, or instead of, the total,
+/// which `-q` cannot be combined with.
+fn printing_flags(output: &Output) -> Vec<&'static str> {
+    [
+        (
+            output.format_name.is_some() && output.report_file.is_none(),
+            "--format",
+        ),
+        (output.query.is_some(), "--query"),
+        (output.row_template.is_some(), "--format-template"),
+        (output.summary_template.is_some(), "--summary-template"),
+        (output.by_file, "--by-file"),
+        (output.summary, "--summary"),
+        (output.stats, "--stats"),
+        (output.by_root, "--by-root"),
+        (output.by_ext, "--by-ext"),
+        (output.by_dir.is_some(), "--by-dir"),
+        (output.tree.is_some(), "--tree"),
+        (output.top.is_some(), "--top"),
+        (output.histogram.is_some(), "--histogram"),
+        (output.chart.is_some(), "--chart"),
+        (output.outliers, "--outliers"),
+        (output.cocomo.is_some(), "--cocomo"),
+        (
+            output.fixtures == Some(FixtureMode::Separate),
+            "--fixtures separate",
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(given, flag)| given.then_some(flag))
+    .collect()
+}
+
+/// Formats the report can be printed in instead of the total.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    /// The structured report as JSON.
+    Json,
+    /// A line of JSON per file.
+    Jsonl,
+    /// The structured report as YAML.
+    Yaml,
+    /// The structured report as XML.
+    Xml,
+    /// A row per file.
+    Csv,
+    /// A Markdown table per language.
+    Md,
+    /// A standalone HTML page.
+    Html,
+    /// cloc's per-language table.
+    Cloc,
+    /// Prometheus metrics.
+    Prom,
+}
+
+impl OutputFormat {
+    /// Gets the format with the given name, as given to `--format`.
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "json" => Self::Json,
+            "jsonl" => Self::Jsonl,
+            "yaml" => Self::Yaml,
+            "xml" => Self::Xml,
+            "csv" => Self::Csv,
+            "md" => Self::Md,
+            "html" => Self::Html,
+            "cloc" => Self::Cloc,
+            "prom" => Self::Prom,
+            _ => return None,
+        })
+    }
+}
+
+/// Runs the `--exec` command for each counted file, one at a time in path
+/// order if `ordered`, or else in parallel.
+fn run_exec(template: &ExecTemplate, report: &Report, cats: &[Category], ordered: bool) {
+    let jobs = if ordered {
+        1
+    } else {
+        thread::available_parallelism().map_or(1, NonZeroUsize::get)
+    };
+    run_for_each(
+        template,
+        report,
+        |file| language_of(file, cats),
+        jobs,
+        |file, problem| {
+            print_warning(&format!(
+                "--exec failed for \"{}\": {problem}",
+                file.path.display()
+            ));
+        },
+    );
+}
+
+/// Appends the report to the `--sqlite` database as a new run, then prints
+/// the total.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if the database cannot be opened or written.
+#[cfg(feature = "sqlite")]
+fn print_export(
+    report: &Report,
+    cats: &[Category],
+    roots: &[PathBuf],
+    output: &Output,
+) -> Result<(), CLCError> {
+    let Some(path) = &output.sqlite else {
+        unreachable!("checked before counting");
+    };
+    let run = sqlite::export(path, report, roots, |file| language_of(file, cats))?;
+    println!("{}", report.total_lines());
+    print_note(&format!("saved run {run} to {}", path.display()));
+    Ok(())
+}
+
+/// Prints the output of a command once the lines are counted.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if the command fails.
+fn run_counted(
+    command: Command,
+    report: &Report,
+    fixtures: Option<&Report>,
+    cats: &[Category],
+    roots: &[PathBuf],
+    options: &CountOptions,
+    output: &Output,
+) -> Result<(), CLCError> {
+    match command {
+        Command::Count => print_count(report, fixtures, cats, roots, output)?,
+        Command::Hotspots => print_hotspots(report, roots, output),
+        Command::Score => print_score(report, options, output)?,
+        Command::Snapshot(SnapshotAction::Save(name)) => {
+            let value = report_value(report, |file| language_of(file, cats));
+            let path = snapshot::save(&name, &value)?;
+            println!("{}", report.total_lines());
+            print_note(&format!("saved snapshot \"{name}\" to {}", path.display()));
+        }
+        #[cfg(feature = "sqlite")]
+        Command::Export => print_export(report, cats, roots, output)?,
+        #[cfg(not(feature = "sqlite"))]
+        Command::Export => unreachable!("handled before counting"),
+        Command::Validate
+        | Command::Schema
+        | Command::File(_)
+        | Command::Diff(_)
+        | Command::Multi
+        | Command::Snapshot(SnapshotAction::Diff(..)) => {
+            unreachable!("handled before counting")
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints the JSON Schema of structured output, exiting if another schema
+/// format is asked for.
+fn print_schema(format: Option<&str>) {
+    match format {
+        None | Some("json-schema") => println!("{}", schema::json_schema()),
+        Some(format) => usage_error(&format!("unknown schema format \"{format}\"")),
+    }
+}
+
+/// Prints a line of JSON per file as soon as it is counted, shaped like
+/// the files of the structured report.
+///
+/// Files arrive in no particular order. Printing stops quietly once stdout
+/// is closed, such as by `head`. Returns the report of the printed and
+/// skipped files, for the diagnostics and the actions run after a count.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if the count cannot start or writing to stdout
+/// fails.
+fn print_jsonl(
+    roots: &[PathBuf],
+    exts: &[&OsStr],
+    cats: &[Category],
+    options: &CountOptions,
+) -> Result<Report, CLCError> {
+    let mut out = io::stdout().lock();
+    let mut counts = count_lines_iter(roots, exts, options)?;
+    let mut files = vec![];
+
+    for file in counts.detailed() {
+        match writeln!(out, "{}", file_value(&file, language_of(&file, cats))) {
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => break,
+            result => result?,
+        }
+        files.push(file);
+    }
+
+    let mut report = Report {
+        files,
+        ..counts.skipped().clone()
+    };
+    report.errors.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+    report.generated.sort_unstable();
+    report.minified.sort_unstable();
+    Ok(report)
+}
+
+/// Gets the output format of a file from its extension, if it names one.
+fn format_of_path(path: &Path) -> Option<OutputFormat> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    OutputFormat::from_name(match ext.as_str() {
+        "yml" => "yaml",
+        "markdown" => "md",
+        "htm" => "html",
+        ext => ext,
+    })
+}
+
+/// Writes the report in one of the [`OutputFormat`]s.
+///
+/// # Errors
+///
+/// Returns the I/O error if writing fails.
+fn write_formatted(
+    out: &mut impl io::Write,
+    report: &Report,
+    cats: &[Category],
+    roots: &[PathBuf],
+    output: &Output,
+    format: OutputFormat,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => writeln!(
+            out,
+            "{}",
+            report_value(report, |file| language_of(file, cats)).pretty()
+        ),
+        OutputFormat::Jsonl => report
+            .files
+            .iter()
+            .try_for_each(|file| writeln!(out, "{}", file_value(file, language_of(file, cats)))),
+        OutputFormat::Yaml => write!(
+            out,
+            "{}",
+            to_yaml(&report_value(report, |file| language_of(file, cats)))
+        ),
+        OutputFormat::Xml => write!(
+            out,
+            "{}",
+            to_xml(
+                &report_value(report, |file| language_of(file, cats)),
+                "report",
+                REPORT_MAPS
+            )
+        ),
+        OutputFormat::Csv => write_csv(out, report, cats),
+        OutputFormat::Md => write_markdown(out, report, cats),
+        OutputFormat::Html => write!(
+            out,
+            "{}",
+            render_html(report, roots, output.group_depth.unwrap_or(1), |file| {
+                language_of(file, cats)
+            })
+        ),
+        OutputFormat::Cloc => {
+            let counts = cloc_counts(report, |file| language_of(file, cats))?;
+            let banner = format!("clc {}", env!("CARGO_PKG_VERSION"));
+            if output.cloc_csv {
+                write_cloc_csv(out, &counts, &banner)
+            } else {
+                write_cloc_text(out, &counts, &banner)
+            }
+        }
+        OutputFormat::Prom => {
+            write_prometheus(out, &language_stats(report, |file| language_of(file, cats)))
+        }
+    }
+}
+
+/// Writes the report to the `-o` file in `format`, creating its parent
+/// directories.
+///
+/// # Errors
+///
+/// Returns the I/O error if the file cannot be created or written.
+fn write_output_file(
+    path: &Path,
+    format: OutputFormat,
+    report: &Report,
+    cats: &[Category],
+    roots: &[PathBuf],
+    output: &Output,
+) -> io::Result<()> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)?;
+    }
+    let mut out = BufWriter::new(File::create(path)?);
+    write_formatted(&mut out, report, cats, roots, output, format)?;
+    out.flush()
+}
+
+/// Writes a CSV row per counted file, then a row with the total lines.
+///
+/// If comments were detected, code, comment, and blank columns follow.
+///
+/// # Errors
+///
+/// Returns the I/O error if writing fails.
+fn write_csv(out: &mut impl io::Write, report: &Report, cats: &[Category]) -> io::Result<()> {
+    let total_kinds = report.total_kinds();
+    let kinds = |kinds: Option<LineKinds>| {
+        total_kinds.map_or_else(Vec::new, |_| {
+            let kinds = kinds.unwrap_or_default();
+            [kinds.code, kinds.comment, kinds.blank]
+                .map(|n| n.to_string())
+                .to_vec()
+        })
+    };
+
+    let mut header = vec!["path", "extension", "category", "lines"];
+    if total_kinds.is_some() {
+        header.extend(["code", "comment", "blank"]);
+    }
+    csv::write_row(&mut *out, &header)?;
+    for file in &report.files {
+        let mut row = vec![
+            file.path.to_string_lossy().into_owned(),
+            file.extension().to_string_lossy().into_owned(),
+            language_of(file, cats),
+            file.lines.to_string(),
+        ];
+        row.extend(kinds(file.kinds));
+        csv::write_row(&mut *out, &row)?;
+    }
+
+    let mut total = vec![
+        "total".to_string(),
+        String::new(),
+        String::new(),
+        report.total_lines().to_string(),
+    ];
+    total.extend(kinds(total_kinds));
+    csv::write_row(out, &total)
+}
+
+/// Writes a GitHub-flavored Markdown table of each language's files and
+/// lines, sorted by name, ending with the totals.
+///
+/// If comments were detected, code, comment, and blank columns follow.
+///
+/// # Errors
+///
+/// Returns the I/O error if writing fails.
+fn write_markdown(out: &mut impl io::Write, report: &Report, cats: &[Category]) -> io::Result<()> {
+    let with_kinds = report.total_kinds().is_some();
+    let row = |name: String, files: String, lines: u64, kinds: Option<LineKinds>| {
+        let mut row = vec![name, files, lines.to_string()];
+        if with_kinds {
+            let kinds = kinds.unwrap_or_default();
+            row.extend([kinds.code, kinds.comment, kinds.blank].map(|n| n.to_string()));
+        }
+        row
+    };
+
+    let mut header = vec!["language", "files", "lines"];
+    if with_kinds {
+        header.extend(["code", "comment", "blank"]);
+    }
+    let mut rows: Vec<Vec<String>> = language_stats(report, |file| language_of(file, cats))
+        .into_iter()
+        .map(|stats| {
+            row(
+                stats.name.replace('|', "\\|"),
+                stats.files.to_string(),
+                stats.lines,
+                stats.kinds,
+            )
+        })
+        .collect();
+    rows.push(row(
+        "**total**".to_string(),
+        report.files.len().to_string(),
+        report.total_lines(),
+        report.total_kinds(),
+    ));
+
+    let mut widths: Vec<usize> = header.iter().map(|cell| cell.len()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    // The first column is left-aligned and the counts right-aligned.
+    let line = |cells: &mut dyn Iterator<Item = &str>| {
+        let cells: Vec<String> = cells
+            .zip(&widths)
+            .enumerate()
+            .map(|(idx, (cell, &width))| {
+                if idx == 0 {
+                    format!("{cell:<width$}")
+                } else {
+                    format!("{cell:>width$}")
+                }
+            })
+            .collect();
+        format!("| {} |", cells.join(" | "))
+    };
+    let rule: Vec<String> = widths
+        .iter()
+        .enumerate()
+        .map(|(idx, &width)| {
+            if idx == 0 {
+                "-".repeat(width)
+            } else {
+                format!("{}:", "-".repeat(width - 1))
+            }
+        })
+        .collect();
+
+    writeln!(out, "{}", line(&mut header.iter().copied()))?;
+    writeln!(out, "| {} |", rule.join(" | "))?;
+    for row in &rows {
+        writeln!(out, "{}", line(&mut row.iter().map(String::as_str)))?;
+    }
+    Ok(())
+}
+
+/// Default multiple of the median above which a file is an outlier.
+const DEFAULT_OUTLIER_FACTOR: f64 = 10.0;
+
+/// Prints the files far longer than their language's median, longest
+/// relative to the median first.
+fn print_outliers(report: &Report, cats: &[Category], output: &Output) {
+    let factor = output.outlier_factor.unwrap_or(DEFAULT_OUTLIER_FACTOR);
+    let outliers = outliers(report, |file| language_of(file, cats), factor);
+    if outliers.is_empty() {
+        println!("no files over {factor} times their language's median");
+        return;
+    }
+
+    let rows: Vec<Vec<String>> = outliers
+        .into_iter()
+        .map(|outlier| {
+            #[allow(clippy::cast_precision_loss)]
+            let ratio = outlier.lines as f64 / outlier.median as f64;
+            vec![
+                outlier.path.display().to_string(),
+                outlier.language,
+                outlier.lines.to_string(),
+                outlier.median.to_string(),
+                format!("{ratio:.1}x"),
+            ]
+        })
+        .collect();
+
+    print_table(&["file", "language", "lines", "median", "ratio"], &rows);
+}
+
+/// Formats a whole number with commas between groups of three digits.
+fn grouped(number: u64) -> String {
+    let digits = number.to_string();
+    let mut grouped = String::with_capacity(digits.len() * 4 / 3);
+    for (idx, digit) in digits.chars().enumerate() {
+        if idx > 0 && (digits.len() - idx).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// Prints the basic COCOMO estimate of the effort, schedule, and cost of
+/// writing the counted lines, like scc.
+fn print_cocomo(report: &Report, cocomo: &Cocomo) {
+    let estimate = cocomo.estimate(report.total_lines());
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let cost = estimate.cost.round() as u64;
+
+    println!(
+        "Estimated cost to develop ({}): ${}",
+        cocomo.model.name(),
+        grouped(cost)
+    );
+    println!("Estimated effort: {:.2} person-months", estimate.effort);
+    println!("Estimated schedule: {:.2} months", estimate.schedule);
+    println!("Estimated people required: {:.2}", estimate.people);
+}
+
+/// Prints the report through the user's row and summary templates.
+///
+/// Rows are printed once per language, sorted by name, then the summary.
+/// Each is followed by a newline.
+fn print_templates(report: &Report, cats: &[Category], output: &Output) {
+    let languages = language_stats(report, |file| language_of(file, cats));
+
+    if let Some(template) = &output.row_template {
+        for stats in &languages {
+            println!(
+                "{}",
+                template.render(|field| match field {
+                    "lang" | "category" => stats.name.clone(),
+                    "files" => stats.files.to_string(),
+                    "lines" => stats.lines.to_string(),
+                    "median" => stats.median.to_string(),
+                    "p90" => stats.p90.to_string(),
+                    "p99" => stats.p99.to_string(),
+                    _ => unreachable!("placeholders are checked when parsing"),
+                })
+            );
+        }
+    }
+
+    if let Some(template) = &output.summary_template {
+        println!(
+            "{}",
+            template.render(|field| match field {
+                "languages" => languages.len().to_string(),
+                "files" => report.files.len().to_string(),
+                "lines" => report.total_lines().to_string(),
+                _ => unreachable!("placeholders are checked when parsing"),
+            })
+        );
+    }
+}
+
+/// Prints a bar chart of each language's share of lines, largest first.
+///
+/// Bars are colored as chosen by `--color`.
+fn print_chart(report: &Report, cats: &[Category], style: ChartStyle) {
+    let mut shares: Vec<(String, u64)> = language_stats(report, |file| language_of(file, cats))
+        .into_iter()
+        .map(|stats| (stats.name, stats.lines))
+        .collect();
+    shares.sort_by_key(|(_, lines)| Reverse(*lines));
+
+    print!("{}", render_chart(&shares, 40, style, stdout_color()));
+}
+
+/// Prints a histogram of the files by their lines, bucketed by `bounds`,
+/// drawn in `style`.
+fn print_histogram(report: &Report, bounds: &[u64], style: ChartStyle) {
+    let buckets: Vec<(String, u64)> = histogram(report, bounds)
+        .into_iter()
+        .map(|bucket| {
+            let range = bucket.max.map_or_else(
+                || format!("{}+", bucket.min),
+                |max| format!("{}-{max}", bucket.min),
+            );
+            (
+                format!("{range} lines"),
+                u64::try_from(bucket.files).unwrap_or(u64::MAX),
+            )
+        })
+        .collect();
+
+    print!("{}", render_chart(&buckets, 40, style, stdout_color()));
+}
+
+/// Gets the category equivalent to a language of tokei or cloc.
+fn category_for_language(language: &str) -> Option<CategoryID> {
+    match language.to_lowercase().as_str() {
+        "bash" | "sh" | "zsh" | "fish" | "bourne shell" | "bourne again shell" => {
+            Some(CategoryID::Shell)
+        }
+        "c header" => Some(CategoryID::C),
+        "c++ header" => Some(CategoryID::Cpp),
+        language => CategoryID::from_name(language),
+    }
+}
+
+/// Counts with settings equivalent to another tool's report and prints a
+/// per-language discrepancy table.
+///
+/// Each language of the report is compared against the category of the
+/// same name, counted on its own.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if the report cannot be read or understood, or if
+/// counting fails.
+fn run_validate(against: &Path, roots: &[PathBuf], options: &CountOptions) -> Result<(), CLCError> {
+    let report = fs::read_to_string(against).map_err(|source| CLCError::ReadFile {
+        path: against.to_path_buf(),
+        source,
+    })?;
+    let value = json::parse(&report)?;
+    let Some((tool, theirs)) = parse_external(&value) else {
+        return Err(CLCError::UnrecognizedReport(against.display().to_string()));
+    };
+
+    // tokei honours .gitignore by default, cloc does not.
+    let options = CountOptions {
+        respect_git_ignore: tool == Tool::Tokei,
+        ..options.clone()
+    };
+
+    let cats: Vec<Option<CategoryID>> = theirs
+        .iter()
+        .map(|count| category_for_language(&count.language))
+        .collect();
+    let exts: Vec<&OsStr> = cats
+        .iter()
+        .flatten()
+        .flat_map(|cat| cat.extensions().iter().map(OsStr::new))
+        .collect();
+    let report = count_lines(roots, &exts, &options)?;
+
+    let rows: Vec<Vec<String>> = theirs
+        .iter()
+        .zip(&cats)
+        .map(|(count, cat)| {
+            let expected = count.code.saturating_add(count.comments);
+            let (category, ours, diff) = cat.map_or_else(
+                || ("-".to_string(), "-".to_string(), "-".to_string()),
+                |cat| {
+                    let ours = lines_with_exts(&report, cat.extensions());
+                    let diff = i128::from(ours) - i128::from(expected);
+                    (
+                        cat.names()[0].to_string(),
+                        ours.to_string(),
+                        format!("{diff:+}"),
+                    )
+                },
+            );
+            vec![
+                count.language.clone(),
+                category,
+                count.code.to_string(),
+                count.comments.to_string(),
+                ours,
+                diff,
+            ]
+        })
+        .collect();
+
+    print_table(
+        &["language", "category", "code", "comments", "clc", "diff"],
+        &rows,
+    );
+    println!(
+        "\nclc counts every non-blank line, comments included, so \"diff\" compares\n\
+         against code + comments. Remaining differences come from categories\n\
+         covering other extensions than the language, or from comment-only\n\
+         lines the other tool classifies differently. Languages without a\n\
+         matching category are shown with \"-\"."
+    );
+
+    Ok(())
+}
+
+/// Counts every repository in `--repos-file` with the same settings and
+/// prints a row per repository, with its files, lines, and lines per
+/// language, followed by the grand totals.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if the repos file cannot be read.
+fn print_multi(
+    exts: &[&OsStr],
+    cats: &[Category],
+    options: &CountOptions,
+    output: &Output,
+) -> Result<(), CLCError> {
+    let Some(repos_file) = &output.repos_file else {
+        usage_error("multi needs --repos-file FILE");
+    };
+    let repos = read_repos_file(repos_file)?;
+    let jobs = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+
+    if let Some(kind) = output.leaderboard {
+        print_leaderboard(kind, &repos, exts, cats, options, jobs);
+        return Ok(());
+    }
+
+    let counts = counted_repos(count_repos(
+        &repos,
+        |_, root| {
+            let report = count_lines(&[root.to_path_buf()], exts, options)?;
+            let languages = language_stats(&report, |file| language_of(file, cats));
+            Ok((report.files.len(), report.total_lines(), languages))
+        },
+        jobs,
+    ));
+
+    let mut names: Vec<&str> = counts
+        .iter()
+        .flat_map(|(_, (_, _, languages))| languages.iter().map(|stats| stats.name.as_str()))
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+
+    let mut totals = vec![0; names.len()];
+    let mut total_files = 0;
+    let mut total_lines: u64 = 0;
+    let mut rows: Vec<Vec<String>> = vec![];
+    for (repo, (files, lines, languages)) in &counts {
+        total_files += files;
+        total_lines = total_lines.saturating_add(*lines);
+
+        let mut row = vec![repo.clone(), files.to_string(), lines.to_string()];
+        for (name, total) in names.iter().zip(&mut totals) {
+            let lines = languages
+                .iter()
+                .find(|stats| stats.name == *name)
+                .map_or(0, |stats| stats.lines);
+            *total = u64::saturating_add(*total, lines);
+            row.push(lines.to_string());
+        }
+        rows.push(row);
+    }
+
+    let mut row = vec![
+        "total".to_string(),
+        total_files.to_string(),
+        total_lines.to_string(),
+    ];
+    row.extend(totals.iter().map(ToString::to_string));
+
+    let mut header = vec!["repo", "files", "lines"];
+    header.extend(&names);
+    print_table_with_total(&header, &rows, row);
+
+    Ok(())
+}
+
+/// Warns about the repositories that could not be cloned or counted, and
+/// keeps the results of the others.
+fn counted_repos<T>(results: Vec<(String, Result<T, CLCError>)>) -> Vec<(String, T)> {
+    results
+        .into_iter()
+        .filter_map(|(repo, result)| match result {
+            Ok(result) => Some((repo, result)),
+            Err(err) => {
+                print_warning(&format!("skipping \"{repo}\": {err}"));
+                None
+            }
+        })
+        .collect()
+}
+
+/// Counts every repository and prints the repositories, languages, or
+/// teams ranked by lines, with their growth since each repository's latest
+/// snapshot and their duplication.
+fn print_leaderboard(
+    kind: Leaderboard,
+    repos: &[String],
+    exts: &[&OsStr],
+    cats: &[Category],
+    options: &CountOptions,
+    jobs: usize,
+) {
+    let standings = counted_repos(count_repos(
+        repos,
+        |repo, root| {
+            let report = count_lines(&[root.to_path_buf()], exts, options)?;
+            standings(
+                kind,
+                repo,
+                root,
+                &report,
+                &line_classifier(options),
+                |file| language_of(file, cats),
+            )
+        },
+        jobs,
+    ));
+
+    let rows: Vec<Vec<String>> = rank(standings.into_iter().flat_map(|(_, standings)| standings))
+        .into_iter()
+        .enumerate()
+        .map(|(idx, standing)| {
+            vec![
+                (idx + 1).to_string(),
+                standing.name.clone(),
+                standing.lines.to_string(),
+                standing
+                    .growth
+                    .map_or_else(|| "-".to_string(), |growth| format!("{growth:+}")),
+                format!("{:.1}%", standing.duplication() * 100.0),
+            ]
+        })
+        .collect();
+
+    print_table(
+        &["rank", kind.name(), "lines", "growth", "duplication"],
+        &rows,
+    );
+}
+
+/// Gets `lines` as a percentage of the report's total lines, to one
+/// decimal place, like `--chart`.
+fn share(lines: u64, report: &Report) -> String {
+    let total = u128::from(report.total_lines());
+    let tenths = (u128::from(lines) * 1000 + total / 2)
+        .checked_div(total)
+        .unwrap_or(0);
+    format!("{}.{}", tenths / 10, tenths % 10)
+}
+
+/// Gets the header of a files and lines breakdown with `first` naming the
+/// rows, with code, comment, and blank columns if the report has them.
+fn share_header<'a>(first: &'a str, report: &Report) -> Vec<&'a str> {
+    let mut header = vec![first, "files", "lines"];
+    if report.total_kinds().is_some() {
+        header.extend(["code", "comment", "blank"]);
+    }
+    header.push("%");
+    header
+}
+
+/// Gets the code, comment, and blank cells of a row, or none if the report
+/// has no such columns.
+fn kind_cells(kinds: Option<LineKinds>, report: &Report) -> Vec<String> {
+    report.total_kinds().map_or_else(Vec::new, |_| {
+        let kinds = kinds.unwrap_or_default();
+        [kinds.code, kinds.comment, kinds.blank]
+            .map(|n| n.to_string())
+            .to_vec()
+    })
+}
+
+/// Gets a row of a files and lines breakdown, with the lines' share of the
+/// total.
+fn share_row(
+    name: String,
+    files: usize,
+    lines: u64,
+    kinds: Option<LineKinds>,
+    report: &Report,
+) -> Vec<String> {
+    let mut row = vec![name, files.to_string(), lines.to_string()];
+    row.extend(kind_cells(kinds, report));
+    row.push(share(lines, report));
+    row
+}
+
+/// Gets the totals row of a files and lines breakdown.
+fn totals_row(report: &Report) -> Vec<String> {
+    vec![
+        "total".to_string(),
+        report.files.len().to_string(),
+        report.total_lines().to_string(),
+    ]
+}
+
+/// Gets the totals row of a breakdown headed by [`share_header`].
+fn share_totals_row(report: &Report) -> Vec<String> {
+    let mut total = totals_row(report);
+    total.extend(kind_cells(report.total_kinds(), report));
+    total
+}
+
+/// Prints rows under a header as a [`Table`].
+fn print_table(header: &[&str], rows: &[Vec<String>]) {
+    print!(
+        "{}",
+        Table::new(header)
+            .rows(rows)
+            .color(stdout_color())
+            .max_width(table_width())
+            .render()
+    );
+}
+
+/// Prints rows under a header as a [`Table`], ending with a totals row.
+fn print_table_with_total(header: &[&str], rows: &[Vec<String>], total: Vec<String>) {
+    print!(
+        "{}",
+        Table::new(header)
+            .rows(rows)
+            .total(total)
+            .color(stdout_color())
+            .max_width(table_width())
+            .render()
+    );
+}
+
+/// Prints directories ranked by the chosen key, largest first.
+fn print_hotspots(report: &Report, roots: &[PathBuf], output: &Output) {
+    let churn = if output.churn || output.sort == SortKey::Churn {
+        match git_churn(roots) {
+            Ok(churn) => Some(churn),
+            Err(err) => {
+                print_warning(&format!("churn unavailable: {err}"));
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let mut dirs = dir_totals(
+        report,
+        roots,
+        output.group_depth.unwrap_or(1),
+        churn.as_ref(),
+    );
+    dirs.sort_by_key(|dir| {
+        Reverse(match output.sort {
+            SortKey::Lines => dir.lines,
+            SortKey::Files => dir.files as u64,
+            SortKey::Churn => dir.churn.unwrap_or(0),
+        })
+    });
+
+    let mut header = vec!["#", "directory", "files", "lines"];
+    if churn.is_some() {
+        header.push("churn");
+    }
+
+    let rows: Vec<Vec<String>> = dirs
+        .into_iter()
+        .enumerate()
+        .map(|(idx, dir)| {
+            let mut row = vec![
+                (idx + 1).to_string(),
+                dir.dir.display().to_string(),
+                dir.files.to_string(),
+                dir.lines.to_string(),
+            ];
+            row.extend(dir.churn.map(|churn| churn.to_string()));
+            row
+        })
+        .collect();
+
+    print_table(&header, &rows);
+}
+
+/// Prints counting statistics, skipped files, and the time `elapsed`
+/// counting to stderr.
+fn print_verbose_stats(report: &Report, elapsed: Duration) {
+    eprintln!(
+        "clc: matched {} file(s) in {} director(ies), read {} byte(s)",
+        report.files_matched(),
+        report.dirs_visited,
+        report.bytes_read()
+    );
+    eprintln!("clc: counted {} file(s)", report.files.len());
+    eprintln!(
+        "clc: skipped {} file(s) that vanished and {} that could not be read",
+        report.vanished,
+        report.errors.len()
+    );
+    eprintln!(
+        "clc: {} file(s) were empty or contained only whitespace",
+        report.empty_files().count()
+    );
+    eprintln!(
+        "clc: {} file(s) started with a UTF-8 BOM, which was ignored",
+        report.bom_files()
+    );
+    for file in report.transcoded_files() {
+        eprintln!(
+            "clc: converted from {}: {}",
+            file.encoding.unwrap_or_default(),
+            file.path.display()
+        );
+    }
+    for path in &report.generated {
+        eprintln!("clc: skipped as generated: {}", path.display());
+    }
+    for path in &report.minified {
+        eprintln!("clc: skipped as minified: {}", path.display());
+    }
+    eprintln!("clc: took {:.3}s", elapsed.as_secs_f64());
+}
+
+/// Exit status of invalid invocations, as opposed to failed counts.
+const USAGE_EXIT_STATUS: i32 = 2;
+
+/// Prints a usage error to stderr and exits.
+fn usage_error(message: &str) -> ! {
+    eprintln!("clc: {message}\nTry 'clc --help' for more information on how to use clc.");
+    exit(USAGE_EXIT_STATUS)
+}
+
+/// Whether to color stdout and stderr, set once the options are parsed.
+static COLOR: OnceLock<(bool, bool)> = OnceLock::new();
+
+/// Checks whether to color what is printed to stdout.
+fn stdout_color() -> bool {
+    COLOR.get().is_some_and(|&(stdout, _)| stdout)
+}
+
+/// Width tables are fitted to, set once the options are parsed: the
+/// terminal's, unless stdout is not one or `--no-truncate` is given.
+static TABLE_WIDTH: OnceLock<Option<usize>> = OnceLock::new();
+
+/// Gets the width tables are fitted to, if any.
+fn table_width() -> Option<usize> {
+    TABLE_WIDTH.get().copied().flatten()
+}
+
+/// Whether warnings and notes are suppressed, set once the options are
+/// parsed.
+static QUIET: OnceLock<bool> = OnceLock::new();
+
+/// Checks whether warnings and notes are suppressed.
+fn quiet() -> bool {
+    QUIET.get().copied().unwrap_or(false)
+}
+
+/// Prints a note to stderr, unless quiet.
+fn print_note(message: &str) {
+    if !quiet() {
+        eprintln!("clc: note: {message}");
+    }
+}
+
+/// Prints a warning to stderr, unless quiet.
+fn print_warning(message: &str) {
+    if quiet() {
+        return;
+    }
+    let stderr = COLOR.get().is_some_and(|&(_, stderr)| stderr);
+    eprintln!("clc: {}: {message}", paint("warning", YELLOW, stderr));
+}
+
+/// Prints an error to stderr.
+fn print_error(message: &str) {
+    let stderr = COLOR.get().is_some_and(|&(_, stderr)| stderr);
+    eprintln!("clc: {}: {message}", paint("error", RED, stderr));
+}
+
+/// Parses the template given to `flag`, exiting if it is invalid.
+fn parse_template(flag: &str, template: &str, fields: &[&'static str]) -> Template {
+    Template::parse(template, fields)
+        .unwrap_or_else(|err| usage_error(&format!("invalid {flag}: {err}")))
+}
+
+/// Applies a single option to `options` or `output`.
+///
+/// Exits on `--help`, `--version`, and invalid options.
+fn parse_flag(
+    flag: &str,
+    options: &mut CountOptions,
+    output: &mut Output,
+    depth_re: &Regex,
+    registry: &CategoryRegistry,
+) {
+    match flag.as_bytes() {
+        b"--help" => {
+            println!("{}", gen_help(registry));
+            exit(0)
+        }
+        b"-v" | b"--version" => {
+            println!("clc {}", env!("CARGO_PKG_VERSION"));
+            exit(0)
+        }
+        b"-h" | b"--hidden" => options.hidden = true,
+        b"-q" | b"--quiet" => output.quiet = true,
+        b"-V" | b"--verbose" => output.verbose = true,
+        b"--list-empty" => output.list_empty = true,
+        b"--strict" => output.strict = true,
+        b"--no-comments" => options.skip_comments = true,
+        b"--breakdown" => options.classify = true,
+        b"--docstring-comments" => options.docstring_comments = true,
+        b"--count-blank" => options.count_blank = true,
+        b"--logical" => options.logical = true,
+        b"--skip-license-header" => options.skip_license_header = true,
+        b"--include-generated" => options.include_generated = true,
+        b"--include-minified" => options.include_minified = true,
+        b"--no-truncate" => output.no_truncate = true,
+        b"--ordered" => {
+            output.ordered = true;
+            options.ordered = true;
+        }
+        b"--stats" => output.stats = true,
+        b"--summary" => output.summary = true,
+        b"--by-root" => output.by_root = true,
+        b"--by-file" => output.by_file = true,
+        b"--by-dir" => output.by_dir = Some(1),
+        b"--tree" => output.tree = Some(usize::MAX),
+        b"--by-ext" => output.by_ext = true,
+        b"--churn" => output.churn = true,
+        b"--chart" => output.chart = Some(ChartStyle::Blocks),
+        b"--histogram" => output.histogram = Some(HISTOGRAM_BOUNDS.to_vec()),
+        b"--csv" => output.cloc_csv = true,
+        b"--outliers" => output.outliers = true,
+        b"--cocomo" => {
+            output.cocomo.get_or_insert_default();
+        }
+        b"--staged-diff" => output.staged_diff = true,
+        b"--estimate-review" => output.estimate_review = true,
+        b"-g" | b"--git" => options.respect_git_ignore = true,
+        b"-L" | b"--follow" => options.follow_links = true,
+        b"--resolve-symlinks" => options.resolve_symlinks = true,
+        flag_bytes if depth_re.is_match(flag_bytes) => {
+            options.max_depth = flag[2..].parse().ok();
+        }
+        _ if flag.starts_with("--max-memory=") => {
+            let size = &flag["--max-memory=".len()..];
+            let Some(size) = parse_size(size) else {
+                usage_error(&format!("invalid memory size \"{size}\""));
+            };
+            options.max_memory = Some(size);
+        }
+        _ if flag.starts_with("--threads=") => {
+            let threads = &flag["--threads=".len()..];
+            match threads.parse() {
+                Ok(threads) if threads > 0 => options.threads = Some(threads),
+                _ => usage_error(&format!("invalid thread count \"{threads}\"")),
+            }
+        }
+        _ if flag.starts_with("--min-depth=") => {
+            let depth = &flag["--min-depth=".len()..];
+            let Ok(depth) = depth.parse() else {
+                usage_error(&format!("invalid depth \"{depth}\""));
+            };
+            options.min_depth = Some(depth);
+        }
+        _ if flag.starts_with("--final-line=") => {
+            let policy = &flag["--final-line=".len()..];
+            let Some(policy) = FinalLine::from_name(policy) else {
+                usage_error(&format!("invalid final line policy \"{policy}\""));
+            };
+            options.final_line = policy;
+        }
+        _ if flag.starts_with("--blank-chars=") => {
+            let spec = &flag["--blank-chars=".len()..];
+            let Some(chars) = BlankChars::from_spec(spec) else {
+                usage_error(&format!("invalid blank characters \"{spec}\""));
+            };
+            options.blank_chars = chars;
+        }
+        _ if parse_output_flag(flag, output) => {}
+        _ => usage_error(&format!("flag \"{flag}\" not found")),
+    }
+}
+
+/// Parses the output format, given by `--format` or else by the
+/// extension of the `-o` file, exiting if it is unknown or the output
+/// format options do not make sense together.
+fn parse_output_format(command: &Command, output: &mut Output) {
+    // The schema command has formats of its own.
+    if *command == Command::Schema {
+        return;
+    }
+
+    output.format = match (&output.format_name, &output.report_file) {
+        (Some(name), _) => Some(OutputFormat::from_name(name).unwrap_or_else(|| {
+            usage_error(&format!("unknown output format \"{name}\""));
+        })),
+        (None, Some(path)) => Some(format_of_path(path).unwrap_or_else(|| {
+            usage_error(&format!(
+                "cannot tell the format of \"{}\" from its extension; use --format",
+                path.display()
+            ));
+        })),
+        (None, None) => None,
+    };
+    if output.cloc_csv && output.format != Some(OutputFormat::Cloc) {
+        usage_error("--csv only applies to --format cloc");
+    }
+}
+
+/// Parses the value of a numeric option, exiting if it is not valid.
+fn parse_number<T: FromStr>(value: &str, what: &str) -> T {
+    value
+        .parse()
+        .unwrap_or_else(|_| usage_error(&format!("invalid {what} \"{value}\"")))
+}
+
+/// Parses the value of an option naming one of several choices, exiting if
+/// `from_name` does not know it.
+fn parse_name<T>(value: &str, what: &str, from_name: impl Fn(&str) -> Option<T>) -> T {
+    from_name(value).unwrap_or_else(|| usage_error(&format!("invalid {what} \"{value}\"")))
+}
+
+/// Applies a single `--name=value` option affecting only the output.
+///
+/// Returns whether the option was one. Exits on invalid values.
+fn parse_output_flag(flag: &str, output: &mut Output) -> bool {
+    match flag {
+        _ if flag.starts_with("--exec=") => match ExecTemplate::parse(&flag["--exec=".len()..]) {
+            Ok(template) => output.exec = Some(template),
+            Err(err) => usage_error(&format!("invalid --exec command: {err}")),
+        },
+        _ if flag.starts_with("--weights=") => match Weights::parse(&flag["--weights=".len()..]) {
+            Ok(weights) => output.weights = weights,
+            Err(err) => usage_error(&format!("invalid --weights: {err}")),
+        },
+        _ if flag.starts_with("--chart=") => {
+            output.chart = Some(parse_name(
+                &flag["--chart=".len()..],
+                "chart style",
+                ChartStyle::from_name,
+            ));
+        }
+        _ if flag.starts_with("--color=") => {
+            output.color = parse_name(
+                &flag["--color=".len()..],
+                "color choice",
+                ColorChoice::from_name,
+            );
+        }
+        _ if flag.starts_with("--top=") => {
+            output.top = Some(parse_number(&flag["--top=".len()..], "file count"));
+        }
+        _ if flag.starts_with("--by-dir=") => {
+            output.by_dir = Some(parse_number(&flag["--by-dir=".len()..], "depth"));
+        }
+        _ if flag.starts_with("--histogram=") => {
+            output.histogram = Some(parse_bounds(&flag["--histogram=".len()..]));
+        }
+        _ if flag.starts_with("--tree=") => {
+            output.tree = Some(parse_number(&flag["--tree=".len()..], "depth"));
+        }
+        _ if flag.starts_with("--max-file-lines=") => {
+            output.max_file_lines = Some(parse_number(
+                &flag["--max-file-lines=".len()..],
+                "line count",
+            ));
+        }
+        _ if flag.starts_with("--outlier-factor=") => {
+            let factor = &flag["--outlier-factor=".len()..];
+            match factor.parse() {
+                Ok(factor) if f64::is_finite(factor) && factor > 0.0 => {
+                    output.outlier_factor = Some(factor);
+                    output.outliers = true;
+                }
+                _ => usage_error(&format!("invalid outlier factor \"{factor}\"")),
+            }
+        }
+        _ if flag.starts_with("--query=") => match Query::parse(&flag["--query=".len()..]) {
+            Ok(query) => output.query = Some(query),
+            Err(err) => usage_error(&format!("invalid --query: {err}")),
+        },
+        _ if flag.starts_with("--format-template=") || flag.starts_with("--format-str=") => {
+            let (name, template) = flag.split_once('=').unwrap_or_default();
+            output.row_template = Some(parse_template(name, template, ROW_FIELDS));
+        }
+        _ if flag.starts_with("--summary-template=") => {
+            output.summary_template = Some(parse_template(
+                "--summary-template",
+                &flag["--summary-template=".len()..],
+                SUMMARY_FIELDS,
+            ));
+        }
+        _ if flag.starts_with("-o=") || flag.starts_with("--output=") => {
+            let (_, path) = flag.split_once('=').unwrap_or_default();
+            output.report_file = Some(PathBuf::from(path));
+        }
+        _ if flag.starts_with("--format=") => {
+            output.format_name = Some(flag["--format=".len()..].to_string());
+        }
+        _ if flag.starts_with("--fixtures=") => {
+            output.fixtures = Some(parse_name(
+                &flag["--fixtures=".len()..],
+                "fixture mode",
+                FixtureMode::from_name,
+            ));
+        }
+        _ if flag.starts_with("--fixture-patterns=") => {
+            let patterns = &flag["--fixture-patterns=".len()..];
+            output.fixture_rules = Some(FixtureRules::parse(patterns));
+        }
+        _ if flag.starts_with("--export-scatter=") => {
+            output.export_scatter = Some(PathBuf::from(&flag["--export-scatter=".len()..]));
+        }
+        _ => return parse_cocomo_flag(flag, output) || parse_command_flag(flag, output),
+    }
+
+    true
+}
+
+/// Parses comma-separated, increasing bucket bounds, exiting if they are
+/// not.
+fn parse_bounds(value: &str) -> Vec<u64> {
+    let bounds: Vec<u64> = value
+        .split(',')
+        .map(|bound| parse_number(bound.trim(), "bucket bound"))
+        .collect();
+    if bounds.windows(2).any(|pair| pair[0] >= pair[1]) {
+        usage_error(&format!("bucket bounds \"{value}\" are not increasing"));
+    }
+    bounds
+}
+
+/// Parses the value of an option that must be a positive number, exiting
+/// if it is not.
+fn parse_positive(value: &str, what: &str) -> f64 {
+    match value.parse() {
+        Ok(number) if f64::is_finite(number) && number > 0.0 => number,
+        _ => usage_error(&format!("invalid {what} \"{value}\"")),
+    }
+}
+
+/// Applies a single `--name=value` option of the `--cocomo` estimate,
+/// which any of them turns on.
+///
+/// Returns whether the option was one. Exits on invalid values.
+fn parse_cocomo_flag(flag: &str, output: &mut Output) -> bool {
+    let Some((name, value)) = flag.split_once('=') else {
+        return false;
+    };
+    if !matches!(
+        name,
+        "--cocomo-model" | "--avg-wage" | "--overhead" | "--eaf"
+    ) {
+        return false;
+    }
+
+    let cocomo = output.cocomo.get_or_insert_default();
+    match name {
+        "--cocomo-model" => {
+            cocomo.model = parse_name(value, "COCOMO model", cocomo::Model::from_name);
+        }
+        "--avg-wage" => cocomo.avg_wage = parse_positive(value, "wage"),
+        "--overhead" => cocomo.overhead = parse_positive(value, "overhead"),
+        _ => cocomo.eaf = parse_positive(value, "effort adjustment factor"),
+    }
+    true
+}
+
+/// Applies a single `--name=value` option of a command, such as
+/// `hotspots` or `multi`.
+///
+/// Returns whether the option was one. Exits on invalid values.
+fn parse_command_flag(flag: &str, output: &mut Output) -> bool {
+    match flag {
+        _ if flag.starts_with("--repos-file=") => {
+            output.repos_file = Some(PathBuf::from(&flag["--repos-file=".len()..]));
+        }
+        _ if flag.starts_with("--leaderboard=") => {
+            output.leaderboard = Some(parse_name(
+                &flag["--leaderboard=".len()..],
+                "leaderboard",
+                Leaderboard::from_name,
+            ));
+        }
+        _ if flag.starts_with("--sqlite=") => {
+            output.sqlite = Some(PathBuf::from(&flag["--sqlite=".len()..]));
+        }
+        _ if flag.starts_with("--against=") => {
+            output.against = Some(PathBuf::from(&flag["--against=".len()..]));
+        }
+        _ if flag.starts_with("--group-depth=") => {
+            output.group_depth = Some(parse_number(&flag["--group-depth=".len()..], "depth"));
+        }
+        _ if flag.starts_with("--sort=") => {
+            output.sort = match &flag["--sort=".len()..] {
+                "lines" => SortKey::Lines,
+                "files" => SortKey::Files,
+                "churn" => SortKey::Churn,
+                key => usage_error(&format!("invalid sort key \"{key}\"")),
+            };
+        }
+        _ => return false,
+    }
+
+    true
+}
+
+/// Prints a note for every group of requested categories sharing extensions.
+fn print_category_overlaps(cats: &[Category]) {
+    for (group, shared) in category_overlaps(cats) {
+        let names: Vec<String> = group
+            .iter()
+            .map(|cat| format!("\"{}\"", cat.name()))
+            .collect();
+        let shared: Vec<String> = shared.iter().map(|ext| format!(".{ext}")).collect();
+        print_note(&format!(
+            "categories {} overlap on {}; matching files are counted once, \
+             under the first of these categories listing their extension",
+            names.join(", "),
+            shared.join(", "),
+        ));
+    }
+}
+
+/// A parsed command line.
+struct Invocation {
+    /// What to do.
+    command: Command,
+    /// Known categories, built in or defined in [`CATEGORIES_FILE`].
+    registry: CategoryRegistry,
+    /// Requested categories, in the order given, each once.
+    cats: Vec<Category>,
+    /// Extensions to match, from categories and `.EXT` arguments.
+    exts: Vec<OsString>,
+    /// Paths to search, without overlaps.
+    roots: Vec<PathBuf>,
+    /// Counting options.
+    options: CountOptions,
+    /// Output settings.
+    output: Output,
+}
+
+/// Parses the command line, exiting on usage errors.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if regex compilation fails or the category
+/// definitions cannot be read.
+fn parse_args(args: Vec<OsString>) -> Result<Invocation, CLCError> {
+    let mut registry = CategoryRegistry::builtin();
+    registry.load_file_if_exists(Path::new(CATEGORIES_FILE))?;
+
+    let mut exts: Vec<OsString> = vec![];
+    let mut options = CountOptions {
+        categories: registry.categories().to_vec(),
+        ..CountOptions::default()
+    };
+    let mut output = Output::default();
+    let depth_re = Regex::new(r"^\-d[0-9]+$")?;
+    let (command, args) = split_command(args);
+    let args = join_flag_values(args);
+
+    let [flags, extargs, cat_names, mut paths]: [Vec<&OsStr>; 4] =
+        args.iter().map(OsString::as_os_str).partition_n(|arg| {
+            let arg = arg.as_encoded_bytes();
+            if arg.starts_with(b"-") {
+                0
+            } else if is_path_arg(arg) {
+                3
+            } else if arg.starts_with(b".") {
+                1
+            } else {
+                2
+            }
+        });
+
+    for flag in flags {
+        parse_flag(
+            &flag.to_string_lossy(),
+            &mut options,
+            &mut output,
+            &depth_re,
+            &registry,
+        );
+    }
+    check_quiet(&output);
+    let _ = QUIET.set(output.quiet);
+    let _ = COLOR.set((
+        output.color.enabled(io::stdout().is_terminal()),
+        output.color.enabled(io::stderr().is_terminal()),
+    ));
+    let _ = TABLE_WIDTH.set(
+        (!output.no_truncate && io::stdout().is_terminal())
+            .then(terminal_width)
+            .flatten(),
+    );
+
+    let mut cats = vec![];
+    let mut unknown = vec![];
+
+    for arg in cat_names {
+        let cat_name = arg.to_string_lossy();
+        if let Some(cat) = registry.get(&cat_name) {
+            if Path::new(arg).is_dir() {
+                print_note(&format!(
+                    "\"{cat_name}\" is counted as a category; write \"{cat_name}/\" to search the directory"
+                ));
+            }
+            if !cats.contains(cat) {
+                exts.extend(cat.extensions().iter().map(OsString::from));
+                cats.push(cat.clone());
+            }
+        } else if Path::new(arg).exists() {
+            paths.push(arg);
+        } else {
+            unknown.push(cat_name.into_owned());
+        }
+    }
+
+    // Unknown categories may still be languages provided by plugins.
+    #[cfg(feature = "plugins")]
+    {
+        options.plugins = load_plugins(&cats, &unknown, &registry)?;
+    }
+    #[cfg(not(feature = "plugins"))]
+    if let Some(Err(err)) = unknown.first().map(|cat_name| registry.lookup(cat_name)) {
+        usage_error(&err.to_string());
+    }
+
+    print_category_overlaps(&cats);
+
+    for ext in extargs {
+        exts.push(strip_ext_dot(ext).to_os_string());
+    }
+
+    let roots = if paths.is_empty() {
+        vec![PathBuf::from("./")]
+    } else {
+        paths.into_iter().map(PathBuf::from).collect()
+    };
+
+    let (roots, overlaps) = dedup_roots(roots);
+    for overlap in overlaps {
+        print_warning(&format!(
+            "path \"{}\" overlaps \"{}\" and is only searched once",
+            overlap.root.display(),
+            overlap.covered_by.display()
+        ));
+    }
+
+    parse_output_format(&command, &mut output);
+
+    Ok(Invocation {
+        command,
+        registry,
+        cats,
+        exts,
+        roots,
+        options,
+        output,
+    })
+}
+
+/// Prints warnings and requested diagnostics about a finished count.
+fn print_diagnostics(report: &Report, output: &Output, elapsed: Duration) {
+    if report.vanished > 0 {
+        print_warning(&format!(
+            "skipped {} file(s) that vanished before they could be read",
+            report.vanished
+        ));
+    }
+
+    for error in &report.errors {
+        print_warning(&format!(
+            "could not read \"{}\": {}",
+            error.path.display(),
+            error.message
+        ));
+    }
+
+    if !report.generated.is_empty() {
+        print_note(&format!(
+            "skipped {} generated file(s); --include-generated counts them",
+            report.generated.len()
+        ));
+    }
+    if !report.minified.is_empty() {
+        print_note(&format!(
+            "skipped {} minified file(s); --include-minified counts them",
+            report.minified.len()
+        ));
+    }
+
+    if output.verbose {
+        print_verbose_stats(report, elapsed);
+    }
+
+    if output.list_empty {
+        for file in report.empty_files() {
+            eprintln!("clc: empty: {}", file.path.display());
+        }
+    }
+
+    if is_saturated(report.total_lines()) {
+        print_warning("line total too large, showing the saturated value");
+    }
+}
+
+/// Counts the lines under `roots`, printing a progress snapshot on
+/// `SIGUSR1` and, when the report goes to a terminal, a live progress line.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if the count fails.
+fn run_count(
+    roots: &[PathBuf],
+    exts: &[&OsStr],
+    options: &CountOptions,
+    output: &Output,
+) -> Result<Report, CLCError> {
+    let show_line = !output.quiet && io::stdout().is_terminal() && io::stderr().is_terminal();
+    let current = Arc::new(Mutex::new(PathBuf::new()));
+    let progress = if show_line {
+        let current = Arc::clone(&current);
+        Progress::new().on_progress(move |path, _| {
+            if let (Ok(mut current), Some(dir)) = (current.try_lock(), path.parent()) {
+                dir.clone_into(&mut current);
+            }
+        })
+    } else {
+        Progress::new()
+    };
+    let progress = Arc::new(progress);
+
+    #[cfg(unix)]
+    let sigusr1 = progress::dump_on_sigusr1(Arc::clone(&progress))?;
+    let line = show_line.then(|| ProgressLine::show(Arc::clone(&progress), current));
+
+    let report = count_lines_with_progress(roots, exts, options, &progress);
+
+    #[cfg(unix)]
+    sigusr1.close();
+    if let Some(line) = line {
+        line.close();
+    }
+    report
+}
+
+#[doc(hidden)]
+fn main() -> Result<(), CLCError> {
+    let Invocation {
+        command,
+        registry,
+        cats,
+        exts,
+        roots,
+        options,
+        output,
+    } = parse_args(Args::parse().args)?;
+
+    if (matches!(command, Command::Diff(_)) || output.staged_diff) && roots.len() > 1 {
+        usage_error("diff takes a single path, inside the repository to diff");
+    }
+
+    match &command {
+        Command::Diff(range) => {
+            return print_diff(range.as_deref(), &roots[0], &cats, &exts, &output);
+        }
+        Command::Count if output.staged_diff => {
+            return print_diff(None, &roots[0], &cats, &exts, &output);
+        }
+        Command::File(path) => return print_file_inspection(path, &registry, &options),
+        Command::Snapshot(SnapshotAction::Diff(old, new)) => {
+            return print_snapshot_diff(old, new);
+        }
+        Command::Validate => {
+            let Some(against) = &output.against else {
+                usage_error("validate needs --against FILE");
+            };
+            return run_validate(against, &roots, &options);
+        }
+        Command::Schema => {
+            print_schema(output.format_name.as_deref());
+            return Ok(());
+        }
+        Command::Export if cfg!(not(feature = "sqlite")) => {
+            usage_error("export needs clc built with the sqlite feature");
+        }
+        Command::Export if output.sqlite.is_none() => usage_error("export needs --sqlite FILE"),
+        Command::Count
+        | Command::Hotspots
+        | Command::Score
+        | Command::Multi
+        | Command::Export
+        | Command::Snapshot(SnapshotAction::Save(_)) => {}
+    }
+
+    #[cfg(feature = "plugins")]
+    let has_plugins = options.plugins.is_some();
+    #[cfg(not(feature = "plugins"))]
+    let has_plugins = false;
+
+    if exts.is_empty() && !has_plugins {
+        usage_error("missing operand");
+    }
+
+    let exts: Vec<&OsStr> = exts.iter().map(OsString::as_os_str).collect();
+
+    if command == Command::Multi {
+        return print_multi(&exts, &cats, &options, &output);
+    }
+    let streamed = command == Command::Count
+        && output.format == Some(OutputFormat::Jsonl)
+        && output.report_file.is_none();
+
+    let started = Instant::now();
+    let mut report = if streamed {
+        print_jsonl(&roots, &exts, &cats, &options)?
+    } else {
+        run_count(&roots, &exts, &options, &output)?
+    };
+    let fixtures = split_fixtures(&mut report, &output);
+
+    print_diagnostics(&report, &output, started.elapsed());
+
+    let total = report.total_lines();
+    if total == 0 && !output.quiet {
+        print_zero_match_hints(&roots, &exts, &options, &report)?;
+    }
+
+    if let Some(path) = &output.export_scatter {
+        write_scatter(path, &report, |file| language_of(file, &cats))?;
+    }
+
+    if let Some(template) = &output.exec {
+        run_exec(template, &report, &cats, output.ordered);
+    }
+
+    if !streamed {
+        run_counted(
+            command,
+            &report,
+            fixtures.as_ref(),
+            &cats,
+            &roots,
+            &options,
+            &output,
+        )?;
+    }
+
+    if output.strict && !report.errors.is_empty() {
+        print_error(&format!(
+            "{} file(s) could not be read",
+            report.errors.len()
+        ));
+        exit(1);
+    }
 
     Ok(())
 }
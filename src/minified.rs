@@ -0,0 +1,48 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use memchr::memchr_iter;
+
+/// Average line length, in bytes, over which a file is minified, as
+/// assumed by scc.
+pub const MINIFIED_LINE_LENGTH: u64 = 255;
+
+/// Gets the average length of the lines of a file's contents, in bytes,
+/// line endings included, or 0 if it has no lines.
+///
+/// # Example
+///
+/// ```
+/// use code_line_count::minified::average_line_length;
+///
+/// assert_eq!(average_line_length(b"ab\ncdef\n"), 4);
+/// assert_eq!(average_line_length(b""), 0);
+/// ```
+#[must_use]
+pub fn average_line_length(contents: &[u8]) -> u64 {
+    let lines = memchr_iter(b'\n', contents).count() + usize::from(!contents.ends_with(b"\n"));
+    let lines = u64::try_from(lines).unwrap_or(u64::MAX);
+    let len = u64::try_from(contents.len()).unwrap_or(u64::MAX);
+    len.checked_div(lines).unwrap_or(0)
+}
+
+/// Checks whether a file's contents are minified, such as a JavaScript or
+/// CSS bundle: its lines are over [`MINIFIED_LINE_LENGTH`] bytes long on
+/// average.
+///
+/// # Example
+///
+/// ```
+/// use code_line_count::minified::is_minified;
+///
+/// let bundle = format!("!function(){{{}}}();\n", "var a=1;".repeat(100));
+/// assert!(is_minified(bundle.as_bytes()));
+/// assert!(!is_minified(b"function f() {\n    return 1;\n}\n"));
+/// ```
+#[must_use]
+pub fn is_minified(contents: &[u8]) -> bool {
+    average_line_length(contents) > MINIFIED_LINE_LENGTH
+}
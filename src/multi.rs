@@ -0,0 +1,139 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use code_line_count::CLCError;
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+};
+
+/// Reads the repositories listed in a repos file, one path or URL per line.
+///
+/// Blank lines and lines starting with `#` are skipped.
+///
+/// # Errors
+///
+/// Returns the I/O error if the file cannot be read.
+pub fn read_repos_file(path: &Path) -> io::Result<Vec<String>> {
+    Ok(fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(ToString::to_string)
+        .collect())
+}
+
+/// URL schemes repositories may be cloned over.
+const URL_SCHEMES: &[&str] = &["https", "ssh", "git", "file"];
+
+/// Checks whether a repository is given as a URL to clone rather than a
+/// local path, with one of the [`URL_SCHEMES`] or as `git@host:path`.
+#[must_use]
+pub fn is_url(repo: &str) -> bool {
+    repo.split_once("://")
+        .is_some_and(|(scheme, _)| URL_SCHEMES.contains(&scheme))
+        || (repo.starts_with("git@") && repo.contains(':'))
+}
+
+/// Shallowly clones a repository into `dest`.
+fn clone_repo(url: &str, dest: &Path) -> io::Result<()> {
+    let output = Command::new("git")
+        .args(["clone", "--quiet", "--depth", "1", "--", url])
+        .arg(dest)
+        .stdin(Stdio::null())
+        .output()?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "git clone failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+/// Runs `count` on one repository, cloning it into `scratch` first if it
+/// is a URL. The clone, even a partial one, is removed afterwards.
+fn count_repo<T>(
+    repo: &str,
+    scratch: &Path,
+    count: &impl Fn(&str, &Path) -> Result<T, CLCError>,
+) -> Result<T, CLCError> {
+    if repo.contains("://") && !is_url(repo) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "unsupported URL scheme; use https, ssh, git, or file",
+        )
+        .into());
+    }
+    if !is_url(repo) {
+        let path = Path::new(repo);
+        if !path.is_dir() {
+            return Err(io::Error::new(io::ErrorKind::NotFound, "not a directory").into());
+        }
+        return count(repo, path);
+    }
+
+    let result = clone_repo(repo, scratch)
+        .map_err(CLCError::from)
+        .and_then(|()| count(repo, scratch));
+    let _ = fs::remove_dir_all(scratch);
+    result
+}
+
+/// Runs `count` on each repository, at most `jobs` at a time, and gathers
+/// the results in the order the repositories were given.
+///
+/// URLs are shallowly cloned into a temporary directory, which is removed
+/// once counted, so `count` must read all it needs from the files. Each
+/// repository is counted at its own root, which is passed to `count` along
+/// with the repository as given. Repositories that cannot be cloned or
+/// counted come with their error.
+pub fn count_repos<T: Send>(
+    repos: &[String],
+    count: impl Fn(&str, &Path) -> Result<T, CLCError> + Sync,
+    jobs: usize,
+) -> Vec<(String, Result<T, CLCError>)> {
+    let scratch = env::temp_dir().join(format!("clc-multi-{}", process::id()));
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<(usize, Result<T, CLCError>)>> = Mutex::new(vec![]);
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, repos.len().max(1)) {
+            scope.spawn(|| {
+                loop {
+                    let idx = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(repo) = repos.get(idx) else { break };
+                    let dest: PathBuf = scratch.join(idx.to_string());
+
+                    let result = count_repo(repo, &dest, &count);
+                    results
+                        .lock()
+                        .unwrap_or_else(std::sync::PoisonError::into_inner)
+                        .push((idx, result));
+                }
+            });
+        }
+    });
+
+    let _ = fs::remove_dir(&scratch);
+
+    let mut results = results
+        .into_inner()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    results.sort_by_key(|(idx, _)| *idx);
+    results
+        .into_iter()
+        .map(|(idx, result)| (repos[idx].clone(), result))
+        .collect()
+}
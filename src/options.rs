@@ -0,0 +1,265 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+#[cfg(feature = "plugins")]
+use crate::plugins::PluginSet;
+use crate::{CancellationToken, Category, SharedLineCounter};
+use std::{fmt, path::Path, sync::Arc};
+
+/// How to treat the last line of a file when it has no trailing newline.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FinalLine {
+    /// Count an unterminated final line like any other.
+    #[default]
+    Count,
+    /// Never count an unterminated final line.
+    Ignore,
+    /// Count newline characters like `wc -l`, blank lines included.
+    WcCompat,
+}
+
+impl FinalLine {
+    /// Gets a policy from its command line name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "count" => Some(Self::Count),
+            "ignore" => Some(Self::Ignore),
+            "wc-compat" => Some(Self::WcCompat),
+            _ => None,
+        }
+    }
+}
+
+/// Characters a blank line may hold, which decide what lines are skipped
+/// as blank.
+///
+/// A line's ending, `\n` or `\r\n`, is never part of what is checked.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum BlankChars {
+    /// Whitespace: characters with the Unicode `White_Space` property, such
+    /// as spaces, tabs, form feeds, no-break spaces, and ideographic
+    /// spaces, or only ASCII whitespace on lines that are not UTF-8.
+    #[default]
+    Whitespace,
+    /// Only the given characters, so a line holding any other character,
+    /// including other whitespace, is not blank. Lines that are not UTF-8
+    /// are not blank either.
+    Only(Vec<char>),
+}
+
+impl BlankChars {
+    /// Parses a set of characters as given on the command line: literal
+    /// characters and the escapes `\t`, `\r`, `\f`, `\v`, `\\`, and
+    /// `\u{XXXX}`. Returns `None` if the set is empty or holds a newline
+    /// or an unknown escape.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use code_line_count::BlankChars;
+    ///
+    /// let chars = BlankChars::from_spec(r" \t\u{a0}");
+    /// assert_eq!(chars, Some(BlankChars::Only(vec![' ', '\t', '\u{a0}'])));
+    /// assert_eq!(BlankChars::from_spec(r"\x"), None);
+    /// ```
+    #[must_use]
+    pub fn from_spec(spec: &str) -> Option<Self> {
+        let mut chars = Vec::new();
+        let mut rest = spec.chars();
+        while let Some(c) = rest.next() {
+            let c = if c == '\\' {
+                match rest.next()? {
+                    't' => '\t',
+                    'r' => '\r',
+                    'f' => '\x0c',
+                    'v' => '\x0b',
+                    '\\' => '\\',
+                    'u' => {
+                        let hex = rest.as_str().strip_prefix('{')?;
+                        let (hex, after) = hex.split_once('}')?;
+                        let c = char::from_u32(u32::from_str_radix(hex, 16).ok()?)?;
+                        rest = after.chars();
+                        c
+                    }
+                    _ => return None,
+                }
+            } else {
+                c
+            };
+            if c == '\n' {
+                return None;
+            }
+            if !chars.contains(&c) {
+                chars.push(c);
+            }
+        }
+        (!chars.is_empty()).then_some(Self::Only(chars))
+    }
+
+    /// Checks whether a line, with or without its ending, is blank.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use code_line_count::BlankChars;
+    ///
+    /// let whitespace = BlankChars::Whitespace;
+    /// assert!(whitespace.is_blank(b" \t\x0c\r\n"));
+    /// assert!(whitespace.is_blank("\u{a0}\u{3000}\n".as_bytes()));
+    /// assert!(!whitespace.is_blank(b"  x\n"));
+    /// // Lines that are not UTF-8 only have ASCII whitespace.
+    /// assert!(!whitespace.is_blank(b"\xa0\n"));
+    ///
+    /// let spaces = BlankChars::Only(vec![' ']);
+    /// assert!(spaces.is_blank(b"   \r\n"));
+    /// assert!(!spaces.is_blank(b" \t \n"));
+    /// ```
+    #[must_use]
+    pub fn is_blank(&self, line: &[u8]) -> bool {
+        match self {
+            Self::Whitespace => std::str::from_utf8(line).map_or_else(
+                |_| line.trim_ascii().is_empty(),
+                |line| line.trim().is_empty(),
+            ),
+            Self::Only(chars) => {
+                let line = line.strip_suffix(b"\n").unwrap_or(line);
+                let line = line.strip_suffix(b"\r").unwrap_or(line);
+                std::str::from_utf8(line).is_ok_and(|line| line.chars().all(|c| chars.contains(&c)))
+            }
+        }
+    }
+}
+
+/// Predicate deciding whether a file is counted, on top of extension
+/// matching.
+///
+/// Clones share the predicate, and filters compare equal if they share it.
+///
+/// # Example
+///
+/// ```
+/// use code_line_count::FileFilter;
+///
+/// let filter = FileFilter::new(|path| !path.to_string_lossy().contains(".generated."));
+/// ```
+#[derive(Clone)]
+pub struct FileFilter(Arc<dyn Fn(&Path) -> bool + Send + Sync>);
+
+impl FileFilter {
+    /// Creates a filter counting the files for which `predicate` is true.
+    pub fn new(predicate: impl Fn(&Path) -> bool + Send + Sync + 'static) -> Self {
+        Self(Arc::new(predicate))
+    }
+
+    /// Checks whether a file passes the filter.
+    #[must_use]
+    pub fn allows(&self, path: &Path) -> bool {
+        (self.0)(path)
+    }
+}
+
+impl fmt::Debug for FileFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("FileFilter")
+    }
+}
+
+impl PartialEq for FileFilter {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for FileFilter {}
+
+/// Options controlling how files are found and counted.
+///
+/// The default searches everything except hidden files, ignores
+/// `.gitignore` files, has no depth limit, skips links, does not limit
+/// memory use, uses a thread per CPU, counts unterminated final lines, and
+/// leaves out generated and minified files.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CountOptions {
+    /// Include hidden files and directories.
+    pub hidden: bool,
+    /// Respect `.gitignore` files.
+    pub respect_git_ignore: bool,
+    /// Maximum search depth.
+    pub max_depth: Option<usize>,
+    /// Minimum depth of counted files; shallower files are skipped, but
+    /// their directories are still searched.
+    pub min_depth: Option<usize>,
+    /// Follow symlinks, junctions and reparse points.
+    pub follow_links: bool,
+    /// Categorize file symlinks by their target's name instead of their own.
+    pub resolve_symlinks: bool,
+    /// Match extensions ASCII case-insensitively, so `rs` also matches `RS`.
+    pub ignore_ext_case: bool,
+    /// Maximum number of file bytes held in memory at once.
+    pub max_memory: Option<u64>,
+    /// Number of threads walking and reading files; by default, one per
+    /// available CPU. One thread makes a count gentle on shared machines.
+    pub threads: Option<usize>,
+    /// Make streaming functions such as
+    /// [`count_lines_iter`](crate::count_lines_iter) wait for the walk to
+    /// finish and yield files sorted by path, so their output is the same
+    /// across runs. A [`Report`](crate::Report) is always sorted.
+    pub ordered: bool,
+    /// How to treat a final line without a trailing newline.
+    pub final_line: FinalLine,
+    /// Skip lines holding only comments, as written in each file's
+    /// language and followed across lines in block comments, counting only
+    /// code. See
+    /// [`CodeLines`](crate::CodeLines).
+    pub skip_comments: bool,
+    /// Split each file's lines into code, comment, and blank lines, in
+    /// [`FileCount::kinds`](crate::FileCount::kinds). Contents counted
+    /// without a path, as by [`count_bytes`](crate::count_bytes), are not
+    /// split.
+    pub classify: bool,
+    /// Count blank lines too, rather than skipping them. With
+    /// [`CountOptions::skip_comments`], blank lines are counted and only
+    /// comments are skipped.
+    pub count_blank: bool,
+    /// Characters a line may hold and still be blank, and skipped.
+    pub blank_chars: BlankChars,
+    /// Count docstrings, such as Python's, as comments rather than code,
+    /// for [`CountOptions::skip_comments`] and [`CountOptions::classify`].
+    pub docstring_comments: bool,
+    /// Count statements instead of lines in languages where they can be
+    /// told apart, counting other files as usual. See
+    /// [`LogicalLines`](crate::LogicalLines).
+    pub logical: bool,
+    /// Leave out the license header at the top of each file, such as an
+    /// MPL, Apache, or MIT notice. See
+    /// [`WithoutLicenseHeader`](crate::WithoutLicenseHeader).
+    pub skip_license_header: bool,
+    /// Count files marked as generated, such as by `// @generated` or
+    /// `DO NOT EDIT`, instead of leaving them out into
+    /// [`Report::generated`](crate::Report::generated).
+    pub include_generated: bool,
+    /// Count minified files, such as JavaScript and CSS bundles, instead of
+    /// leaving them out into [`Report::minified`](crate::Report::minified).
+    pub include_minified: bool,
+    /// Categories whose comment syntax is used before the built-in one,
+    /// for [`CountOptions::skip_comments`] and [`CountOptions::classify`],
+    /// such as those of a [`CategoryRegistry`](crate::CategoryRegistry).
+    pub categories: Vec<Category>,
+    /// Strategy for counting each file's lines, instead of counting
+    /// non-empty lines. [`CountOptions::final_line`] and
+    /// [`CountOptions::skip_comments`] do not apply to it.
+    pub line_counter: Option<SharedLineCounter>,
+    /// Predicate every file must pass to be counted, given the file's path
+    /// as walked.
+    pub filter: Option<FileFilter>,
+    /// Token that stops the count early when cancelled.
+    pub cancel: Option<CancellationToken>,
+    /// Plugins consulted before the built-in extension matching.
+    #[cfg(feature = "plugins")]
+    pub plugins: Option<Arc<PluginSet>>,
+}
@@ -0,0 +1,314 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use std::{
+    env, fmt, fs,
+    path::{Path, PathBuf},
+    ptr,
+    sync::{Mutex, PoisonError},
+};
+use wasmtime::{
+    Config, Engine, Store, Trap,
+    component::{Component, Linker},
+};
+
+/// Bindings generated from the plugin interface.
+#[allow(missing_docs)]
+mod bindings {
+    wasmtime::component::bindgen!({
+        path: "wit/plugin.wit",
+        world: "counter",
+    });
+}
+
+use bindings::{Counter, CounterPre};
+
+/// Fuel each call into a plugin starts with, about one unit per
+/// WebAssembly instruction, so a plugin stuck in a loop runs out and fails
+/// instead of hanging the count.
+const FUEL_PER_CALL: u64 = 100_000_000;
+
+/// Fuel added to a call for each byte of the contents it is given.
+const FUEL_PER_BYTE: u64 = 1_000;
+
+/// Counts reported by a plugin for one file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PluginCount {
+    /// Language the plugin classified the file as.
+    pub language: String,
+    /// Lines of code.
+    pub lines: u64,
+    /// Extra named metrics.
+    pub metrics: Vec<(String, u64)>,
+}
+
+/// A plugin that claimed a file, ready to count it.
+pub struct Claim<'a> {
+    /// Name of the claiming plugin.
+    pub plugin: &'a str,
+    /// Language the file was classified as.
+    pub language: String,
+    /// Instance that classified the file.
+    instance: Instance,
+    /// Plugin the instance belongs to.
+    owner: &'a Plugin,
+    /// Set the plugin belongs to.
+    pub(crate) set: &'a PluginSet,
+}
+
+impl Claim<'_> {
+    /// Counts the file's contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns the plugin's error if it traps or runs out of fuel.
+    pub fn count(mut self, path: &str, contents: &[u8]) -> wasmtime::Result<PluginCount> {
+        let fuel = FUEL_PER_BYTE
+            .saturating_mul(contents.len() as u64)
+            .saturating_add(FUEL_PER_CALL);
+        let Instance { store, counter } = &mut self.instance;
+
+        store.set_fuel(fuel)?;
+        let lines = counter.call_count_lines(&mut *store, path, contents)?;
+        store.set_fuel(fuel)?;
+        let metrics = counter.call_metrics(&mut *store, path, contents)?;
+
+        self.owner.release(self.instance);
+        Ok(PluginCount {
+            language: self.language,
+            lines,
+            metrics,
+        })
+    }
+}
+
+/// A plugin instantiated in a store of its own.
+struct Instance {
+    /// Store the plugin was instantiated in.
+    store: Store<()>,
+    /// Instantiated plugin.
+    counter: Counter,
+}
+
+/// Callback told about a plugin failure, given a message describing it.
+type FailureCallback = Box<dyn Fn(&str) + Send + Sync>;
+
+/// A loaded plugin.
+struct Plugin {
+    /// File name of the component.
+    name: String,
+    /// Component, linked and ready to instantiate.
+    pre: CounterPre<()>,
+    /// Instances not in use, kept for the next file. There are about as
+    /// many as threads calling the plugin at once.
+    idle: Mutex<Vec<Instance>>,
+}
+
+impl Plugin {
+    /// Takes an idle instance, or instantiates the plugin if none is idle.
+    fn instance(&self, engine: &Engine) -> wasmtime::Result<Instance> {
+        let idle = self
+            .idle
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pop();
+        if let Some(instance) = idle {
+            return Ok(instance);
+        }
+
+        let mut store = Store::new(engine, ());
+        let counter = self.pre.instantiate(&mut store)?;
+        Ok(Instance { store, counter })
+    }
+
+    /// Keeps an instance that finished a call for the next file. Instances
+    /// whose call failed are dropped instead, since a trap may leave them
+    /// unusable.
+    fn release(&self, instance: Instance) {
+        self.idle
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(instance);
+    }
+}
+
+/// Plugins loaded from the plugins directory.
+///
+/// Each plugin is a WebAssembly component implementing the `counter` world
+/// of `wit/plugin.wit`. Plugins are asked in file name order, and the first
+/// one to classify a file claims it. Instances are reused across files, and
+/// each call runs on a fixed amount of fuel, growing with the size of the
+/// file, so a plugin stuck in a loop fails instead of hanging the count.
+pub struct PluginSet {
+    /// Engine all plugins were compiled with.
+    engine: Engine,
+    /// Plugins, sorted by file name.
+    plugins: Vec<Plugin>,
+    /// Languages requested on the command line; files classified as
+    /// anything else are left to the built-in matching.
+    languages: Vec<String>,
+    /// Callback told about plugin failures.
+    on_failure: Option<FailureCallback>,
+}
+
+impl PluginSet {
+    /// Gets the plugins directory.
+    ///
+    /// That is `$CLC_PLUGIN_DIR` if set, otherwise `clc/plugins` under the
+    /// user's configuration directory.
+    #[must_use]
+    pub fn dir() -> Option<PathBuf> {
+        if let Some(dir) = env::var_os("CLC_PLUGIN_DIR") {
+            return Some(PathBuf::from(dir));
+        }
+
+        let config = env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+            .or_else(|| env::var_os("APPDATA").map(PathBuf::from))?;
+
+        Some(config.join("clc").join("plugins"))
+    }
+
+    /// Loads every `.wasm` component in `dir`.
+    ///
+    /// A missing directory loads no plugins.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read or a component
+    /// fails to compile or does not implement the `counter` world.
+    pub fn load(dir: &Path, languages: Vec<String>) -> wasmtime::Result<Self> {
+        let engine = Engine::new(Config::new().consume_fuel(true))?;
+        let linker = Linker::new(&engine);
+
+        let mut paths = match fs::read_dir(dir) {
+            Ok(entries) => entries
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => vec![],
+            Err(err) => return Err(err.into()),
+        };
+        paths.retain(|path| path.extension().is_some_and(|ext| ext == "wasm"));
+        paths.sort();
+
+        let mut plugins = vec![];
+        for path in paths {
+            let name = path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned();
+            let component = Component::from_file(&engine, &path)
+                .map_err(|err| err.context(format!("loading plugin {name}")))?;
+            let pre = CounterPre::new(linker.instantiate_pre(&component)?)
+                .map_err(|err| err.context(format!("loading plugin {name}")))?;
+            plugins.push(Plugin {
+                name,
+                pre,
+                idle: Mutex::new(vec![]),
+            });
+        }
+
+        Ok(Self {
+            engine,
+            plugins,
+            languages,
+            on_failure: None,
+        })
+    }
+
+    /// Sets a callback told about every plugin that fails to classify or
+    /// count a file, given a message naming the plugin or file.
+    ///
+    /// A count carries on past such failures: the file is left to the
+    /// built-in matching, or counted without the plugin. Without a callback,
+    /// they go unreported.
+    #[must_use]
+    pub fn on_failure(mut self, callback: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        self.on_failure = Some(Box::new(callback));
+        self
+    }
+
+    /// Tells the failure callback, if any, about a plugin failure.
+    pub(crate) fn report_failure(&self, message: &str) {
+        if let Some(callback) = &self.on_failure {
+            callback(message);
+        }
+    }
+
+    /// Checks whether no plugins were loaded.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.plugins.is_empty()
+    }
+
+    /// Asks each plugin in turn to classify `path`.
+    ///
+    /// Returns the first claim whose language was requested.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first plugin that fails to instantiate,
+    /// traps, or runs out of fuel.
+    pub fn classify(&self, path: &str) -> wasmtime::Result<Option<Claim<'_>>> {
+        for plugin in &self.plugins {
+            let mut instance = plugin.instance(&self.engine)?;
+            instance.store.set_fuel(FUEL_PER_CALL)?;
+            let Some(language) = instance
+                .counter
+                .call_classify(&mut instance.store, path)?
+                .filter(|language| self.languages.contains(language))
+            else {
+                plugin.release(instance);
+                continue;
+            };
+
+            return Ok(Some(Claim {
+                plugin: &plugin.name,
+                language,
+                instance,
+                owner: plugin,
+                set: self,
+            }));
+        }
+
+        Ok(None)
+    }
+}
+
+/// Describes a plugin failure, plainly if the plugin ran out of fuel.
+pub(crate) fn describe_failure(err: &wasmtime::Error) -> String {
+    if err.downcast_ref::<Trap>() == Some(&Trap::OutOfFuel) {
+        "ran out of fuel; it may be stuck in a loop".to_string()
+    } else {
+        format!("{err:#}")
+    }
+}
+
+impl fmt::Debug for PluginSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PluginSet")
+            .field(
+                "plugins",
+                &self.plugins.iter().map(|p| &p.name).collect::<Vec<_>>(),
+            )
+            .field("languages", &self.languages)
+            .field("on_failure", &self.on_failure.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Plugin sets are equal only to themselves, since compiled components
+/// cannot be compared.
+impl PartialEq for PluginSet {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self, other)
+    }
+}
+
+impl Eq for PluginSet {}
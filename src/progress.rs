@@ -0,0 +1,260 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use std::{
+    fmt,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Condvar, Mutex,
+        atomic::{AtomicU64, Ordering::Relaxed},
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+/// Time a count runs before [`ProgressLine`] starts drawing, so quick
+/// counts never show it.
+pub const PROGRESS_DELAY: Duration = Duration::from_millis(200);
+
+/// Time between redraws of a [`ProgressLine`].
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Characters of the current directory shown by a [`ProgressLine`], keeping
+/// the end of longer paths.
+const DIR_CHARS: usize = 50;
+
+/// Callback told about every counted file, given its path and lines.
+type Callback = Box<dyn Fn(&Path, u64) + Send + Sync>;
+
+/// Live counters updated by walker threads while a count is running.
+pub struct Progress {
+    files: AtomicU64,
+    lines: AtomicU64,
+    started: Instant,
+    callback: Option<Callback>,
+}
+
+/// A point-in-time copy of [`Progress`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Files counted so far.
+    pub files: u64,
+    /// Lines counted so far.
+    pub lines: u64,
+    /// Time since the count started.
+    pub elapsed: Duration,
+}
+
+impl Progress {
+    /// Creates empty counters, starting the clock now.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            files: AtomicU64::new(0),
+            lines: AtomicU64::new(0),
+            started: Instant::now(),
+            callback: None,
+        }
+    }
+
+    /// Sets a callback told about every counted file as soon as it is
+    /// counted, for showing live progress.
+    ///
+    /// The callback runs on the walker threads, concurrently and in no
+    /// particular order, so it should return quickly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use code_line_count::{CategoryID, Counter, Progress};
+    ///
+    /// let progress = Progress::new().on_progress(|path, lines| {
+    ///     eprintln!("{lines:>6} {}", path.display());
+    /// });
+    /// let report = Counter::new()
+    ///     .path("src")
+    ///     .category(CategoryID::Rust)
+    ///     .run_with_progress(&progress)?;
+    /// # Ok::<(), code_line_count::CLCError>(())
+    /// ```
+    #[must_use]
+    pub fn on_progress(mut self, callback: impl Fn(&Path, u64) + Send + Sync + 'static) -> Self {
+        self.callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Records a counted file.
+    pub fn record(&self, path: &Path, lines: u64) {
+        self.files.fetch_add(1, Relaxed);
+        let _ = self
+            .lines
+            .fetch_update(Relaxed, Relaxed, |total| Some(total.saturating_add(lines)));
+        if let Some(callback) = &self.callback {
+            callback(path, lines);
+        }
+    }
+
+    /// Takes a snapshot of the counters.
+    ///
+    /// Counters are read independently, so a snapshot taken mid-count may
+    /// be off by the files being recorded at that moment.
+    #[must_use]
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            files: self.files.load(Relaxed),
+            lines: self.lines.load(Relaxed),
+            elapsed: self.started.elapsed(),
+        }
+    }
+}
+
+impl fmt::Debug for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Progress")
+            .field("files", &self.files)
+            .field("lines", &self.lines)
+            .field("started", &self.started)
+            .field("callback", &self.callback.is_some())
+            .finish()
+    }
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Prints a progress snapshot to stderr on every `SIGUSR1`.
+///
+/// Runs on a background thread until the returned handle is closed.
+///
+/// # Errors
+///
+/// Returns the I/O error if the signal handler cannot be registered.
+#[cfg(unix)]
+pub fn dump_on_sigusr1(
+    progress: std::sync::Arc<Progress>,
+) -> std::io::Result<signal_hook::iterator::Handle> {
+    use signal_hook::{consts::SIGUSR1, iterator::Signals};
+
+    let mut signals = Signals::new([SIGUSR1])?;
+    let handle = signals.handle();
+
+    std::thread::spawn(move || {
+        for _ in signals.forever() {
+            let snapshot = progress.snapshot();
+            eprintln!(
+                "clc: progress: {} file(s), {} line(s), {:.1}s elapsed",
+                snapshot.files,
+                snapshot.lines,
+                snapshot.elapsed.as_secs_f64()
+            );
+        }
+    });
+
+    Ok(handle)
+}
+
+/// A progress line on stderr, redrawn by a background thread until closed.
+///
+/// The line shows the files and lines counted so far and the directory of
+/// the latest counted file. It is cleared when closed or dropped.
+#[derive(Debug)]
+pub struct ProgressLine {
+    done: Arc<(Mutex<bool>, Condvar)>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ProgressLine {
+    /// Starts drawing `progress` and the directory in `current` once the
+    /// count has run for [`PROGRESS_DELAY`].
+    #[must_use]
+    pub fn show(progress: Arc<Progress>, current: Arc<Mutex<PathBuf>>) -> Self {
+        let done = Arc::new((Mutex::new(false), Condvar::new()));
+        let thread = {
+            let done = Arc::clone(&done);
+            thread::spawn(move || {
+                let delay = PROGRESS_DELAY.saturating_sub(progress.snapshot().elapsed);
+                if wait_done(&done, delay) {
+                    return;
+                }
+                loop {
+                    draw(&progress, &current);
+                    if wait_done(&done, REDRAW_INTERVAL) {
+                        eprint!("\r\x1b[K");
+                        return;
+                    }
+                }
+            })
+        };
+
+        Self {
+            done,
+            thread: Some(thread),
+        }
+    }
+
+    /// Stops drawing and clears the line.
+    pub fn close(mut self) {
+        self.stop();
+    }
+
+    /// Tells the drawing thread to stop and waits for it.
+    fn stop(&mut self) {
+        let (lock, cvar) = &*self.done;
+        if let Ok(mut done) = lock.lock() {
+            *done = true;
+        }
+        cvar.notify_all();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for ProgressLine {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Waits up to `timeout` for `done` to be set, returning whether it was.
+fn wait_done((lock, cvar): &(Mutex<bool>, Condvar), timeout: Duration) -> bool {
+    let Ok(done) = lock.lock() else {
+        return true;
+    };
+    cvar.wait_timeout_while(done, timeout, |done| !*done)
+        .map_or(true, |(done, _)| *done)
+}
+
+/// Redraws the progress line over the previous one.
+fn draw(progress: &Progress, current: &Mutex<PathBuf>) {
+    let snapshot = progress.snapshot();
+    let dir = current
+        .lock()
+        .map(|dir| tail(&dir.display().to_string(), DIR_CHARS))
+        .unwrap_or_default();
+
+    let mut stderr = io::stderr().lock();
+    let _ = write!(
+        stderr,
+        "\r\x1b[Kclc: {} file(s), {} line(s) in {dir}",
+        snapshot.files, snapshot.lines
+    );
+    let _ = stderr.flush();
+}
+
+/// Gets the last `max` characters of `text`, marking a cut with `…`.
+fn tail(text: &str, max: usize) -> String {
+    let len = text.chars().count();
+    if len <= max {
+        return text.to_string();
+    }
+    let kept: String = text.chars().skip(len - max + 1).collect();
+    format!("…{kept}")
+}
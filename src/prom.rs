@@ -0,0 +1,89 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use crate::stats::LanguageStats;
+use std::io::{self, Write};
+
+/// Escapes a label value of the Prometheus text format.
+fn label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Writes one gauge with a sample per language.
+fn write_metric(
+    out: &mut impl Write,
+    name: &str,
+    help: &str,
+    languages: &[LanguageStats],
+    value: impl Fn(&LanguageStats) -> u64,
+) -> io::Result<()> {
+    writeln!(out, "# HELP {name} {help}\n# TYPE {name} gauge")?;
+    for stats in languages {
+        writeln!(
+            out,
+            "{name}{{category=\"{}\"}} {}",
+            label(&stats.name),
+            value(stats)
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes per-language lines and files in the Prometheus text exposition
+/// format, as read by the node exporter's textfile collector.
+///
+/// Each metric is a gauge labeled by `category`, since counts can shrink
+/// between runs. Code, comment, and blank lines are added if comments were
+/// detected.
+///
+/// # Errors
+///
+/// Returns the I/O error if writing fails.
+pub fn write_prometheus(out: &mut impl Write, languages: &[LanguageStats]) -> io::Result<()> {
+    write_metric(
+        out,
+        "clc_lines_total",
+        "Non-empty lines of code counted by clc.",
+        languages,
+        |stats| stats.lines,
+    )?;
+    write_metric(
+        out,
+        "clc_files_total",
+        "Files counted by clc.",
+        languages,
+        |stats| u64::try_from(stats.files).unwrap_or(u64::MAX),
+    )?;
+
+    if languages.iter().any(|stats| stats.kinds.is_some()) {
+        let kinds = |stats: &LanguageStats| stats.kinds.unwrap_or_default();
+        write_metric(
+            out,
+            "clc_code_lines_total",
+            "Lines with code counted by clc.",
+            languages,
+            |stats| kinds(stats).code,
+        )?;
+        write_metric(
+            out,
+            "clc_comment_lines_total",
+            "Lines with only a comment counted by clc.",
+            languages,
+            |stats| kinds(stats).comment,
+        )?;
+        write_metric(
+            out,
+            "clc_blank_lines_total",
+            "Lines with only whitespace counted by clc.",
+            languages,
+            |stats| kinds(stats).blank,
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,192 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use crate::{
+    CLCError, CancellationToken, CountOptions, FileError, LeftOut, LineCounter, Report,
+    classifier_of, count_contents, has_extension, is_one_of, line_counter_of,
+    shebang::shebang_extension,
+};
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    ffi::OsStr,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+/// Source of the files to count, in place of walking the filesystem.
+///
+/// This lets the counting logic run where there is no filesystem to walk,
+/// such as in a browser on `wasm32-unknown-unknown`, or over files fetched
+/// from elsewhere.
+pub trait FileProvider {
+    /// Lists the paths of all files.
+    fn paths(&self) -> Vec<PathBuf>;
+
+    /// Reads a file's contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns the I/O error if the file cannot be read. A
+    /// [`NotFound`](ErrorKind::NotFound) error counts the file as vanished.
+    fn read(&self, path: &Path) -> io::Result<Cow<'_, [u8]>>;
+}
+
+/// Files held in memory, by path.
+///
+/// # Example
+///
+/// ```
+/// use code_line_count::{CountOptions, MemoryFiles, count_provided};
+/// use std::ffi::OsStr;
+///
+/// let files = MemoryFiles::from_iter([
+///     ("src/main.rs", "fn main() {\n\n    println!(\"hi\");\n}\n"),
+///     ("README.md", "# Hi\n"),
+/// ]);
+/// let report = count_provided(&files, &[OsStr::new("rs")], &CountOptions::default())?;
+/// assert_eq!(report.total_lines(), 3);
+/// # Ok::<(), code_line_count::CLCError>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryFiles {
+    /// Contents of each file.
+    files: BTreeMap<PathBuf, Vec<u8>>,
+}
+
+impl MemoryFiles {
+    /// Creates an empty set of files.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file, returning the contents it replaces, if any.
+    pub fn insert(
+        &mut self,
+        path: impl Into<PathBuf>,
+        contents: impl Into<Vec<u8>>,
+    ) -> Option<Vec<u8>> {
+        self.files.insert(path.into(), contents.into())
+    }
+
+    /// Removes a file, returning its contents.
+    pub fn remove(&mut self, path: &Path) -> Option<Vec<u8>> {
+        self.files.remove(path)
+    }
+
+    /// Gets the number of files.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Checks whether there are no files.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+impl<P: Into<PathBuf>, C: Into<Vec<u8>>> FromIterator<(P, C)> for MemoryFiles {
+    fn from_iter<I: IntoIterator<Item = (P, C)>>(files: I) -> Self {
+        Self {
+            files: files
+                .into_iter()
+                .map(|(path, contents)| (path.into(), contents.into()))
+                .collect(),
+        }
+    }
+}
+
+impl FileProvider for MemoryFiles {
+    fn paths(&self) -> Vec<PathBuf> {
+        self.files.keys().cloned().collect()
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Cow<'_, [u8]>> {
+        self.files
+            .get(path)
+            .map(|contents| Cow::Borrowed(&contents[..]))
+            .ok_or_else(|| io::Error::from(ErrorKind::NotFound))
+    }
+}
+
+/// Counts non-empty lines like [`count_lines`](crate::count_lines), but
+/// over the files of a [`FileProvider`] instead of a directory walk.
+///
+/// Files are counted one at a time on the calling thread, so this works on
+/// targets without threads. Of the [`CountOptions`], those about walking
+/// the filesystem, such as [`CountOptions::hidden`] and
+/// [`CountOptions::max_depth`], do not apply; [`CountOptions::filter`],
+/// [`CountOptions::ignore_ext_case`], [`CountOptions::line_counter`],
+/// [`CountOptions::final_line`], and [`CountOptions::cancel`] do.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if regex compilation fails.
+pub fn count_provided(
+    provider: &impl FileProvider,
+    exts: &[&OsStr],
+    options: &CountOptions,
+) -> Result<Report, CLCError> {
+    let strategy = line_counter_of(options)?;
+    let classifier = classifier_of(options);
+    let mut files = vec![];
+    let mut vanished: u64 = 0;
+    let mut errors = vec![];
+    let mut left_out = LeftOut::default();
+
+    for path in provider.paths() {
+        if options
+            .cancel
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            break;
+        }
+
+        if options
+            .filter
+            .as_ref()
+            .is_some_and(|filter| !filter.allows(&path))
+            || (path.extension().is_some() && !has_extension(&path, exts, options.ignore_ext_case))
+        {
+            continue;
+        }
+
+        match provider.read(&path) {
+            // A file without an extension matches by its `#!` line.
+            Ok(bytes)
+                if path.extension().is_none()
+                    && !shebang_extension(&bytes).is_some_and(|ext| {
+                        is_one_of(OsStr::new(ext), exts, options.ignore_ext_case)
+                    }) => {}
+            Ok(bytes) => {
+                let count = count_contents(&path, &bytes, None, classifier.as_ref(), |contents| {
+                    strategy.count_file(&path, contents)
+                });
+                files.extend(left_out.sift(count, options));
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => vanished = vanished.saturating_add(1),
+            Err(err) => errors.push(FileError {
+                message: err.to_string(),
+                path,
+            }),
+        }
+    }
+    errors.sort_unstable_by(|a: &FileError, b| a.path.cmp(&b.path));
+
+    Ok(left_out.into_report(Report {
+        vanished,
+        errors,
+        cancelled: options
+            .cancel
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled),
+        ..Report::from_files(files)
+    }))
+}
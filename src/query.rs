@@ -0,0 +1,316 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use code_line_count::json::Value;
+use std::{cmp::Ordering, iter::Peekable, str::Chars};
+
+/// One stage of a query pipeline.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Stage {
+    /// Select a nested value by object keys and array indexes.
+    Path(Vec<String>),
+    /// Sort an array by a path of its items.
+    SortBy(Vec<String>),
+    /// Replace each item of an array by a path of it.
+    Map(Vec<String>),
+    /// Keep the first items of an array.
+    Take(usize),
+    /// Reverse an array.
+    Reverse,
+    /// Count items, entries, or characters.
+    Length,
+    /// List the keys of an object.
+    Keys,
+    /// Add up an array of numbers.
+    Sum,
+}
+
+/// A query over a structured report, such as `languages.rust.lines` or
+/// `files | sort_by(lines) | reverse | take(5)`.
+///
+/// A query is a pipeline of stages separated by `|`, each applied to the
+/// result of the previous one:
+///
+/// - `a.b.0` selects key `a`, then key `b`, then the first item; keys that
+///   are not plain words are quoted, as in `languages.".rs".lines`, and a
+///   lone `.` is the value itself,
+/// - `sort_by(path)` sorts an array by a path of its items, ascending,
+/// - `map(path)` replaces each item of an array by a path of it,
+/// - `take(n)` keeps the first `n` items of an array,
+/// - `reverse`, `length`, `keys`, and `sum` do what they say.
+///
+/// Missing keys select `null`, like `jq`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Query {
+    /// Stages, in order.
+    stages: Vec<Stage>,
+}
+
+impl Query {
+    /// Parses a query.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem if the query is malformed.
+    pub fn parse(query: &str) -> Result<Self, String> {
+        let mut chars = query.chars().peekable();
+        let mut stages = vec![];
+
+        loop {
+            stages.push(stage(&mut chars)?);
+            skip_whitespace(&mut chars);
+            match chars.next() {
+                Some('|') => {}
+                None => break,
+                Some(c) => return Err(format!("unexpected '{c}'")),
+            }
+        }
+
+        Ok(Self { stages })
+    }
+
+    /// Runs the query on a value.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem if a stage does not apply to
+    /// its input, such as `take` on an object.
+    pub fn eval(&self, value: &Value) -> Result<Value, String> {
+        let mut value = value.clone();
+
+        for stage in &self.stages {
+            value = match stage {
+                Stage::Path(path) => select(&value, path)?,
+                Stage::SortBy(path) => {
+                    let items = array(value, "sort_by")?;
+                    let mut keyed = items
+                        .into_iter()
+                        .map(|item| Ok((select(&item, path)?, item)))
+                        .collect::<Result<Vec<_>, String>>()?;
+                    keyed.sort_by(|(a, _), (b, _)| compare(a, b));
+                    Value::Array(keyed.into_iter().map(|(_, item)| item).collect())
+                }
+                Stage::Map(path) => Value::Array(
+                    array(value, "map")?
+                        .iter()
+                        .map(|item| select(item, path))
+                        .collect::<Result<_, _>>()?,
+                ),
+                Stage::Take(n) => {
+                    Value::Array(array(value, "take")?.into_iter().take(*n).collect())
+                }
+                Stage::Reverse => {
+                    Value::Array(array(value, "reverse")?.into_iter().rev().collect())
+                }
+                Stage::Length => {
+                    let length = match &value {
+                        Value::Null => 0,
+                        Value::Array(items) => items.len(),
+                        Value::Object(entries) => entries.len(),
+                        Value::String(s) => s.chars().count(),
+                        _ => return Err(format!("length: cannot count {}", kind(&value))),
+                    };
+                    Value::Number(length.to_string())
+                }
+                Stage::Keys => match value {
+                    Value::Object(entries) => Value::Array(
+                        entries
+                            .into_iter()
+                            .map(|(key, _)| Value::String(key))
+                            .collect(),
+                    ),
+                    value => return Err(format!("keys: expected an object, got {}", kind(&value))),
+                },
+                Stage::Sum => {
+                    let mut sum: u64 = 0;
+                    for item in array(value, "sum")? {
+                        let n = item
+                            .as_u64()
+                            .ok_or_else(|| format!("sum: cannot add {}", kind(&item)))?;
+                        sum = sum.saturating_add(n);
+                    }
+                    Value::Number(sum.to_string())
+                }
+            };
+        }
+
+        Ok(value)
+    }
+}
+
+/// Names a value's type for error messages.
+const fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// Gets the items of an array for the stage `name`.
+fn array(value: Value, name: &str) -> Result<Vec<Value>, String> {
+    match value {
+        Value::Array(items) => Ok(items),
+        value => Err(format!("{name}: expected an array, got {}", kind(&value))),
+    }
+}
+
+/// Selects a nested value.
+fn select(value: &Value, path: &[String]) -> Result<Value, String> {
+    let mut value = value;
+
+    for key in path {
+        value = match value {
+            Value::Null => return Ok(Value::Null),
+            Value::Object(_) => match value.get(key) {
+                Some(value) => value,
+                None => return Ok(Value::Null),
+            },
+            Value::Array(items) => {
+                let idx: usize = key
+                    .parse()
+                    .map_err(|_| format!("cannot index an array with \"{key}\""))?;
+                match items.get(idx) {
+                    Some(value) => value,
+                    None => return Ok(Value::Null),
+                }
+            }
+            _ => return Err(format!("cannot index {} with \"{key}\"", kind(value))),
+        };
+    }
+
+    Ok(value.clone())
+}
+
+/// Orders values like `jq`: null, booleans, numbers, strings, arrays, then
+/// objects.
+fn compare(a: &Value, b: &Value) -> Ordering {
+    const fn rank(value: &Value) -> u8 {
+        match value {
+            Value::Null => 0,
+            Value::Bool(_) => 1,
+            Value::Number(_) => 2,
+            Value::String(_) => 3,
+            Value::Array(_) => 4,
+            Value::Object(_) => 5,
+        }
+    }
+
+    match (a, b) {
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::Number(a), Value::Number(b)) => match (a.parse::<u64>(), b.parse::<u64>()) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            _ => a
+                .parse::<f64>()
+                .unwrap_or(f64::NAN)
+                .total_cmp(&b.parse::<f64>().unwrap_or(f64::NAN)),
+        },
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Array(a), Value::Array(b)) => a
+            .iter()
+            .zip(b)
+            .map(|(a, b)| compare(a, b))
+            .find(|ord| ord.is_ne())
+            .unwrap_or_else(|| a.len().cmp(&b.len())),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+/// Skips whitespace.
+fn skip_whitespace(chars: &mut Peekable<Chars<'_>>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+/// Reads a plain word: letters, digits, `_`, `-`, `+`, and `#`.
+fn word(chars: &mut Peekable<Chars<'_>>) -> String {
+    let mut word = String::new();
+    while let Some(c) = chars.next_if(|&c| c.is_alphanumeric() || "_-+#".contains(c)) {
+        word.push(c);
+    }
+    word
+}
+
+/// Parses a path such as `a.b.0`, `.a`, `."x y"`, or `.`.
+fn path(chars: &mut Peekable<Chars<'_>>) -> Result<Vec<String>, String> {
+    let mut segments = vec![];
+    let leading_dot = chars.next_if_eq(&'.').is_some();
+
+    loop {
+        let key = if chars.next_if_eq(&'"').is_some() {
+            let mut key = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => key.extend(chars.next()),
+                    Some(c) => key.push(c),
+                    None => return Err("unterminated quoted key".to_string()),
+                }
+            }
+            key
+        } else {
+            let key = word(chars);
+            if key.is_empty() {
+                if segments.is_empty() && leading_dot {
+                    return Ok(segments);
+                }
+                return Err(chars.peek().map_or_else(
+                    || "unexpected end of query".to_string(),
+                    |c| format!("unexpected '{c}'"),
+                ));
+            }
+            key
+        };
+
+        segments.push(key);
+
+        if chars.next_if_eq(&'.').is_none() {
+            return Ok(segments);
+        }
+    }
+}
+
+/// Parses one stage of a pipeline.
+fn stage(chars: &mut Peekable<Chars<'_>>) -> Result<Stage, String> {
+    skip_whitespace(chars);
+
+    let mut lookahead = chars.clone();
+    let name = word(&mut lookahead);
+    skip_whitespace(&mut lookahead);
+    let call = lookahead.peek() == Some(&'(');
+
+    let stage = match (name.as_str(), call) {
+        ("reverse", false) => Stage::Reverse,
+        ("length", false) => Stage::Length,
+        ("keys", false) => Stage::Keys,
+        ("sum", false) => Stage::Sum,
+        ("sort_by" | "map" | "take", true) => {
+            lookahead.next();
+            skip_whitespace(&mut lookahead);
+            let stage = match name.as_str() {
+                "sort_by" => Stage::SortBy(path(&mut lookahead)?),
+                "map" => Stage::Map(path(&mut lookahead)?),
+                _ => Stage::Take(
+                    word(&mut lookahead)
+                        .parse()
+                        .map_err(|_| "take: expected a count".to_string())?,
+                ),
+            };
+            skip_whitespace(&mut lookahead);
+            if lookahead.next() != Some(')') {
+                return Err(format!("{name}: expected ')'"));
+            }
+            stage
+        }
+        (name, true) => return Err(format!("unknown function {name}()")),
+        _ => return Ok(Stage::Path(path(chars)?)),
+    };
+
+    *chars = lookahead;
+    Ok(stage)
+}
@@ -0,0 +1,391 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use crate::{CLCError, CategoryID, comments::CommentSyntax};
+use std::{fs, io, path::Path, slice};
+
+/// File of category definitions that `clc` applies when run from the
+/// directory containing it.
+pub const CATEGORIES_FILE: &str = ".clc/categories";
+
+/// A category of files: its names, the extensions it matches, and the
+/// comment syntax of those extensions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Category {
+    /// Names the category is requested by, the first one being the name
+    /// it is reported under.
+    names: Vec<String>,
+    /// Extensions matched, without the leading dot.
+    extensions: Vec<String>,
+    /// Comment syntax of the extensions that have one.
+    comments: Vec<(String, CommentSyntax)>,
+}
+
+impl Category {
+    /// Creates a category. Leading dots of extensions are stripped.
+    ///
+    /// Returns `None` if `names` is empty.
+    #[must_use]
+    pub fn new(
+        names: impl IntoIterator<Item = impl Into<String>>,
+        extensions: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Option<Self> {
+        let names: Vec<String> = names.into_iter().map(Into::into).collect();
+        if names.is_empty() {
+            return None;
+        }
+
+        let extensions = extensions
+            .into_iter()
+            .map(|ext| {
+                let ext: String = ext.into();
+                ext.strip_prefix('.').unwrap_or(&ext).to_string()
+            })
+            .collect();
+
+        Some(Self {
+            names,
+            extensions,
+            comments: vec![],
+        })
+    }
+
+    /// Gives every extension of the category the comment syntax `syntax`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use code_line_count::{Category, comments::CommentSyntax};
+    ///
+    /// let terraform = Category::new(["terraform"], ["tf"])
+    ///     .unwrap()
+    ///     .with_comments(CommentSyntax::HASH);
+    /// assert_eq!(terraform.comment_syntax("tf"), Some(CommentSyntax::HASH));
+    /// ```
+    #[must_use]
+    pub fn with_comments(mut self, syntax: CommentSyntax) -> Self {
+        self.comments = self
+            .extensions
+            .iter()
+            .map(|ext| (ext.clone(), syntax))
+            .collect();
+        self
+    }
+
+    /// Gets the name the category is reported under.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.names[0]
+    }
+
+    /// Gets all names of the category.
+    #[must_use]
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Gets the extensions the category matches, without the leading dot.
+    #[must_use]
+    pub fn extensions(&self) -> &[String] {
+        &self.extensions
+    }
+
+    /// Gets the comment syntax the category gives files with extension
+    /// `ext`, if any.
+    #[must_use]
+    pub fn comment_syntax(&self, ext: &str) -> Option<CommentSyntax> {
+        self.comments
+            .iter()
+            .find(|(e, _)| e == ext)
+            .map(|&(_, syntax)| syntax)
+    }
+}
+
+impl From<CategoryID> for Category {
+    fn from(id: CategoryID) -> Self {
+        Self {
+            names: id.names().iter().map(ToString::to_string).collect(),
+            extensions: id.extensions().iter().map(ToString::to_string).collect(),
+            comments: id
+                .extensions()
+                .iter()
+                .filter_map(|ext| Some((ext.to_string(), id.comment_syntax(ext)?)))
+                .collect(),
+        }
+    }
+}
+
+/// Categories known at runtime, which can be added to, removed from, or
+/// overridden, such as from a file.
+///
+/// Unlike [`CategoryID`], which is fixed when clc is built, a registry can
+/// hold categories of its user's own. Categories keep the order they were
+/// added in.
+///
+/// # Example
+///
+/// ```
+/// use code_line_count::{Category, CategoryRegistry};
+///
+/// let mut registry = CategoryRegistry::builtin();
+/// registry.add(Category::new(["terraform", "tf"], ["tf", "tfvars"]).unwrap());
+/// registry.remove("markup");
+/// assert_eq!(registry.get("tf").unwrap().extensions(), ["tf", "tfvars"]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CategoryRegistry {
+    /// Registered categories, in the order they were added.
+    categories: Vec<Category>,
+}
+
+impl CategoryRegistry {
+    /// Creates an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry of the built-in categories.
+    #[must_use]
+    pub fn builtin() -> Self {
+        Self {
+            categories: CategoryID::all_ids()
+                .iter()
+                .map(|&id| Category::from(id))
+                .collect(),
+        }
+    }
+
+    /// Adds a category, replacing every category sharing one of its names.
+    ///
+    /// The category takes the place of the first one it replaces, or is
+    /// added last if it replaces none.
+    pub fn add(&mut self, category: Category) {
+        let clashes = |existing: &Category| {
+            existing
+                .names
+                .iter()
+                .any(|name| category.names.contains(name))
+        };
+
+        let first = self.categories.iter().position(clashes);
+        self.categories.retain(|existing| !clashes(existing));
+        match first {
+            Some(idx) => self.categories.insert(idx, category),
+            None => self.categories.push(category),
+        }
+    }
+
+    /// Removes the category with the given name, returning it.
+    pub fn remove(&mut self, name: &str) -> Option<Category> {
+        let idx = self
+            .categories
+            .iter()
+            .position(|category| category.names.iter().any(|n| n == name))?;
+        Some(self.categories.remove(idx))
+    }
+
+    /// Gets the category with the given name. Names are case-sensitive.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Category> {
+        self.categories
+            .iter()
+            .find(|category| category.names.iter().any(|n| n == name))
+    }
+
+    /// Gets the category with the given name like
+    /// [`CategoryRegistry::get`], failing with the closest known name when
+    /// there is none.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CLCError::UnknownCategory`] if no category has the name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use code_line_count::{CLCError, CategoryRegistry};
+    ///
+    /// let registry = CategoryRegistry::builtin();
+    /// match registry.lookup("rsut") {
+    ///     Err(CLCError::UnknownCategory { suggestion, .. }) => {
+    ///         assert_eq!(suggestion.as_deref(), Some("rust"));
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn lookup(&self, name: &str) -> Result<&Category, CLCError> {
+        self.get(name).ok_or_else(|| CLCError::UnknownCategory {
+            name: name.to_string(),
+            suggestion: self.closest_name(name),
+        })
+    }
+
+    /// Gets the known name closest to `name`, if it is close enough to be a
+    /// typo of it.
+    fn closest_name(&self, name: &str) -> Option<String> {
+        let name = name.to_lowercase();
+        let max_distance = (name.chars().count() / 3).max(1);
+
+        self.categories
+            .iter()
+            .flat_map(|category| &category.names)
+            .map(|known| (edit_distance(&name, &known.to_lowercase()), known))
+            .filter(|&(distance, _)| distance <= max_distance)
+            .min_by_key(|&(distance, _)| distance)
+            .map(|(_, known)| known.clone())
+    }
+
+    /// Gets all categories, in the order they were added.
+    #[must_use]
+    pub fn categories(&self) -> &[Category] {
+        &self.categories
+    }
+
+    /// Iterates over all categories, in the order they were added.
+    pub fn iter(&self) -> slice::Iter<'_, Category> {
+        self.categories.iter()
+    }
+
+    /// Applies category definitions, one per line:
+    ///
+    /// ```text
+    /// # Adds a category, or overrides those sharing a name with it.
+    /// terraform, tf = tf, tfvars
+    /// # Adds one whose files have comments starting with `#`.
+    /// nix = nix : hash
+    /// # Removes a category.
+    /// !markup
+    /// ```
+    ///
+    /// The comment syntax after `:` is one named by
+    /// [`CommentSyntax::from_name`]. Blank lines and lines starting with
+    /// `#` are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CLCError::CategoryError`] for the first line that is not a
+    /// valid definition. Lines before it are applied.
+    pub fn load(&mut self, definitions: &str) -> Result<(), CLCError> {
+        let list = |items: &str| -> Vec<String> {
+            items
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(ToString::to_string)
+                .collect()
+        };
+
+        for (idx, line) in definitions.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = |reason: &str| {
+                CLCError::CategoryError(format!("line {}: {reason}: {line}", idx + 1))
+            };
+
+            if let Some(name) = line.strip_prefix('!') {
+                if self.remove(name.trim()).is_none() {
+                    return Err(invalid("no such category"));
+                }
+                continue;
+            }
+
+            let Some((names, rest)) = line.split_once('=') else {
+                return Err(invalid("expected NAMES = EXTENSIONS [: COMMENTS] or !NAME"));
+            };
+            let (extensions, comments) = match rest.split_once(':') {
+                Some((extensions, name)) => (
+                    extensions,
+                    Some(
+                        CommentSyntax::from_name(name.trim())
+                            .ok_or_else(|| invalid("unknown comment syntax"))?,
+                    ),
+                ),
+                None => (rest, None),
+            };
+            let category =
+                Category::new(list(names), list(extensions)).ok_or_else(|| invalid("no name"))?;
+            self.add(match comments {
+                Some(syntax) => category.with_comments(syntax),
+                None => category,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Applies the category definitions in a file, as with
+    /// [`CategoryRegistry::load`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CLCError::ReadFile`] if the file cannot be read, or
+    /// [`CLCError::CategoryError`] if it holds an invalid definition.
+    pub fn load_file(&mut self, path: &Path) -> Result<(), CLCError> {
+        match fs::read_to_string(path) {
+            Ok(definitions) => self.load(&definitions),
+            Err(source) => Err(CLCError::ReadFile {
+                path: path.to_path_buf(),
+                source,
+            }),
+        }
+    }
+
+    /// Applies the category definitions in a file if it exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CLCError::ReadFile`] if the file exists but cannot be read,
+    /// or [`CLCError::CategoryError`] if it holds an invalid definition.
+    pub fn load_file_if_exists(&mut self, path: &Path) -> Result<(), CLCError> {
+        match fs::read_to_string(path) {
+            Ok(definitions) => self.load(&definitions),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(source) => Err(CLCError::ReadFile {
+                path: path.to_path_buf(),
+                source,
+            }),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a CategoryRegistry {
+    type Item = &'a Category;
+    type IntoIter = slice::Iter<'a, Category>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Counts the single-character insertions, deletions, substitutions, and
+/// swaps of adjacent characters turning `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // Rows of distances from prefixes of `a` to every prefix of `b`.
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut row = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        row[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (prev[j] + 1).min(row[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(before[j - 2] + 1);
+            }
+        }
+        (before, prev, row) = (prev, row, before);
+    }
+
+    prev[b.len()]
+}
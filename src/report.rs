@@ -0,0 +1,351 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use crate::Category;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// Lines split into code, comments, and blank lines.
+///
+/// Counts only carry these when comment detection classified their lines;
+/// [`FileCount::lines`] stays the headline count either way.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineKinds {
+    /// Lines with code, including code followed by a comment.
+    pub code: u64,
+    /// Lines with only a comment.
+    pub comment: u64,
+    /// Lines with only whitespace.
+    pub blank: u64,
+}
+
+impl LineKinds {
+    /// Adds the lines of another file or group, saturating on overflow.
+    pub const fn add(&mut self, other: Self) {
+        self.code = self.code.saturating_add(other.code);
+        self.comment = self.comment.saturating_add(other.comment);
+        self.blank = self.blank.saturating_add(other.blank);
+    }
+}
+
+/// Sums the line kinds of the files that have them, or gets `None` if none
+/// does.
+pub fn sum_kinds(kinds: impl IntoIterator<Item = Option<LineKinds>>) -> Option<LineKinds> {
+    kinds.into_iter().flatten().reduce(|mut sum, kinds| {
+        sum.add(kinds);
+        sum
+    })
+}
+
+/// Line count of a single file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileCount {
+    /// Path of the file, as reached by the walker.
+    pub path: PathBuf,
+    /// Number of non-empty lines.
+    pub lines: u64,
+    /// Number of bytes read from the file.
+    pub bytes: u64,
+    /// Whether the file started with a UTF-8 byte order mark.
+    pub bom: bool,
+    /// When the file was last modified, if the platform reports it.
+    pub modified: Option<SystemTime>,
+    /// Language assigned by the plugin that counted the file, if any.
+    pub language: Option<String>,
+    /// Extra named metrics contributed by a plugin.
+    pub metrics: Vec<(String, u64)>,
+    /// Encoding the file was converted to UTF-8 from before counting, if
+    /// it was.
+    pub encoding: Option<&'static str>,
+    /// Code, comment, and blank lines, if the count classified them.
+    pub kinds: Option<LineKinds>,
+    /// Whether the file is marked as generated, as found by
+    /// [`is_generated`](crate::generated::is_generated).
+    pub generated: bool,
+    /// Whether the file is minified, as found by
+    /// [`is_minified`](crate::minified::is_minified).
+    pub minified: bool,
+    /// Extension the file's `#!` line gives it, if it has none of its own,
+    /// as found by [`shebang_extension`](crate::shebang::shebang_extension).
+    pub shebang: Option<&'static str>,
+}
+
+impl FileCount {
+    /// Gets the extension the file is attributed to: its own, or else the
+    /// one its `#!` line gives it, or an empty one if neither does.
+    #[must_use]
+    pub fn extension(&self) -> &OsStr {
+        self.path
+            .extension()
+            .or_else(|| self.shebang.map(OsStr::new))
+            .unwrap_or_default()
+    }
+}
+
+/// Sums values, saturating at [`u64::MAX`] instead of wrapping.
+#[must_use]
+pub fn saturating_sum(values: impl IntoIterator<Item = u64>) -> u64 {
+    values.into_iter().fold(0, u64::saturating_add)
+}
+
+/// Checks whether an aggregated value hit the saturation limit.
+///
+/// A saturated value is only a lower bound of the real one and should be
+/// reported as too large rather than printed as-is.
+#[must_use]
+pub const fn is_saturated(value: u64) -> bool {
+    value == u64::MAX
+}
+
+/// Result of a count.
+///
+/// Files are kept sorted by path, so anything rendered from a report is
+/// byte-identical across runs regardless of the order in which the worker
+/// threads finished.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    /// Counted files, sorted by path.
+    pub files: Vec<FileCount>,
+    /// Matched files that were deleted before they could be read.
+    pub vanished: u64,
+    /// Matched files that could not be read for another reason, sorted by
+    /// path.
+    pub errors: Vec<FileError>,
+    /// Matched files left out because they are generated, sorted by path.
+    /// Empty with [`CountOptions::include_generated`](crate::CountOptions::include_generated).
+    pub generated: Vec<PathBuf>,
+    /// Matched files left out because they are minified, sorted by path.
+    /// Empty with [`CountOptions::include_minified`](crate::CountOptions::include_minified).
+    pub minified: Vec<PathBuf>,
+    /// Number of directories the walker visited, roots included.
+    pub dirs_visited: u64,
+    /// Whether the count was cancelled, leaving out files not yet counted.
+    pub cancelled: bool,
+}
+
+impl Report {
+    /// Creates a report from per-file counts in any order.
+    #[must_use]
+    pub fn from_files(mut files: Vec<FileCount>) -> Self {
+        files.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+        Self {
+            files,
+            ..Self::default()
+        }
+    }
+
+    /// Gets the number of matched files, whether they were counted, vanished,
+    /// could not be read, or were left out.
+    #[must_use]
+    pub const fn files_matched(&self) -> u64 {
+        (self.files.len() as u64)
+            .saturating_add(self.vanished)
+            .saturating_add(self.errors.len() as u64)
+            .saturating_add(self.generated.len() as u64)
+            .saturating_add(self.minified.len() as u64)
+    }
+
+    /// Gets the total bytes read from the counted files, saturating on
+    /// overflow.
+    #[must_use]
+    pub fn bytes_read(&self) -> u64 {
+        saturating_sum(self.files.iter().map(|file| file.bytes))
+    }
+
+    /// Gets the number of counted files that started with a UTF-8 BOM.
+    #[must_use]
+    pub fn bom_files(&self) -> usize {
+        self.files.iter().filter(|file| file.bom).count()
+    }
+
+    /// Gets the counted files that were converted to UTF-8 before counting.
+    pub fn transcoded_files(&self) -> impl Iterator<Item = &FileCount> {
+        self.files.iter().filter(|file| file.encoding.is_some())
+    }
+
+    /// Gets the counted files without any code.
+    ///
+    /// These are files that are empty or contain only whitespace.
+    pub fn empty_files(&self) -> impl Iterator<Item = &FileCount> {
+        self.files.iter().filter(|file| file.lines == 0)
+    }
+
+    /// Gets the total number of non-empty lines, saturating on overflow.
+    #[must_use]
+    pub fn total_lines(&self) -> u64 {
+        saturating_sum(self.files.iter().map(|file| file.lines))
+    }
+
+    /// Gets the code, comment, and blank lines of the files that have them,
+    /// or `None` if no file does.
+    #[must_use]
+    pub fn total_kinds(&self) -> Option<LineKinds> {
+        sum_kinds(self.files.iter().map(|file| file.kinds))
+    }
+
+    /// Gets the number of distinct directories directly containing counted
+    /// files.
+    #[must_use]
+    pub fn dirs_with_files(&self) -> usize {
+        self.files
+            .iter()
+            .filter_map(|file| file.path.parent())
+            .collect::<BTreeSet<_>>()
+            .len()
+    }
+
+    /// Gets the files and lines under each of the roots the report was
+    /// counted from, in the order given.
+    ///
+    /// A file counts towards the first root its path starts with, so roots
+    /// should not overlap (see [`dedup_roots`](crate::roots::dedup_roots)).
+    #[must_use]
+    pub fn by_root<'a>(&self, roots: &'a [PathBuf]) -> Vec<(&'a Path, Totals)> {
+        let mut totals = vec![Totals::default(); roots.len()];
+        for file in &self.files {
+            if let Some(idx) = roots.iter().position(|root| file.path.starts_with(root)) {
+                totals[idx].add(file.lines, file.kinds);
+            }
+        }
+
+        roots.iter().map(PathBuf::as_path).zip(totals).collect()
+    }
+}
+
+/// A matched file that could not be read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FileError {
+    /// Path of the file.
+    pub path: PathBuf,
+    /// Why it could not be read.
+    pub message: String,
+}
+
+/// Files and lines of one group of a [`CountReport`] or
+/// [`Report::by_root`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Totals {
+    /// Number of files.
+    pub files: u64,
+    /// Total lines, saturating on overflow.
+    pub lines: u64,
+    /// Code, comment, and blank lines of the files that have them.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub kinds: Option<LineKinds>,
+}
+
+impl Totals {
+    /// Adds a file with `lines` lines, split into `kinds` if known.
+    const fn add(&mut self, lines: u64, kinds: Option<LineKinds>) {
+        self.files += 1;
+        self.lines = self.lines.saturating_add(lines);
+        if let Some(kinds) = kinds {
+            match &mut self.kinds {
+                Some(total) => total.add(kinds),
+                None => self.kinds = Some(kinds),
+            }
+        }
+    }
+}
+
+/// Summary of a count, broken down by extension and category.
+///
+/// Unlike [`Report`], it has no per-file counts, so it stays small and can
+/// be persisted to compare runs. With the `serde` feature, it implements
+/// `Serialize` and `Deserialize`.
+///
+/// # Example
+///
+/// ```
+/// use code_line_count::{CategoryID, CategoryRegistry, CountReport, Counter};
+///
+/// let report = Counter::new().path("src").category(CategoryID::Rust).run()?;
+/// let summary = CountReport::new(&report, CategoryRegistry::builtin().categories());
+/// println!("{} lines in {} files", summary.total_lines, summary.files_counted);
+/// # Ok::<(), code_line_count::CLCError>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CountReport {
+    /// Total lines, saturating on overflow.
+    pub total_lines: u64,
+    /// Number of counted files.
+    pub files_counted: u64,
+    /// Number of matched files, whether they were counted, skipped, or
+    /// could not be read.
+    pub files_matched: u64,
+    /// Total bytes read from the counted files, saturating on overflow.
+    pub bytes_read: u64,
+    /// Number of directories the walker visited, roots included.
+    pub dirs_visited: u64,
+    /// Totals per extension, without the leading dot. Files without an
+    /// extension are under the empty string.
+    pub extensions: BTreeMap<String, Totals>,
+    /// Totals per category, by the category's first name. A file counts
+    /// towards every category listing its extension.
+    pub categories: BTreeMap<String, Totals>,
+    /// Matched files that were skipped because they vanished before they
+    /// could be read.
+    pub skipped: u64,
+    /// Matched files that could not be read for another reason, sorted by
+    /// path.
+    pub errors: Vec<FileError>,
+    /// Whether the count was cancelled, leaving out files not yet counted.
+    pub cancelled: bool,
+}
+
+impl CountReport {
+    /// Summarizes a report, breaking it down by the given categories, such
+    /// as those of a [`CategoryRegistry`](crate::CategoryRegistry).
+    ///
+    /// Categories without any counted file are left out.
+    #[must_use]
+    pub fn new(report: &Report, cats: &[Category]) -> Self {
+        let mut summary = Self {
+            total_lines: report.total_lines(),
+            files_counted: report.files.len() as u64,
+            files_matched: report.files_matched(),
+            bytes_read: report.bytes_read(),
+            dirs_visited: report.dirs_visited,
+            skipped: report.vanished,
+            errors: report.errors.clone(),
+            cancelled: report.cancelled,
+            ..Self::default()
+        };
+
+        for file in &report.files {
+            let ext = file.extension();
+            summary
+                .extensions
+                .entry(ext.to_string_lossy().into_owned())
+                .or_default()
+                .add(file.lines, file.kinds);
+
+            for cat in cats
+                .iter()
+                .filter(|cat| cat.extensions().iter().any(|e| OsStr::new(e) == ext))
+            {
+                summary
+                    .categories
+                    .entry(cat.name().to_string())
+                    .or_default()
+                    .add(file.lines, file.kinds);
+            }
+        }
+
+        summary
+    }
+}
@@ -0,0 +1,139 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Lines a reviewer reads per hour at an even pace.
+pub const REVIEW_LINES_PER_HOUR: f64 = 300.0;
+
+/// Tokens that mark a line as branching logic, which takes longer to review.
+const BRANCH_TOKENS: &[&str] = &[
+    "if ", "if(", "else", "for ", "for(", "while", "match ", "switch", "case ", "catch", "&&",
+    "||", "? ",
+];
+
+/// Lines changed in one file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FileChange {
+    /// Path of the file, relative to the diffed directory.
+    pub path: PathBuf,
+    /// Lines added.
+    pub added: u64,
+    /// Lines deleted.
+    pub deleted: u64,
+    /// Added lines containing branching logic.
+    pub branching: u64,
+}
+
+impl FileChange {
+    /// Gets the lines a reviewer effectively reads.
+    ///
+    /// Added lines count fully and branching ones half again as much.
+    /// Deleted lines count a fifth, since they only need to be recognized.
+    /// The result is scaled by `weight`, the file type's review weight.
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn review_lines(&self, weight: f64) -> f64 {
+        let added = (self.branching as f64).mul_add(0.5, self.added as f64);
+        (self.deleted as f64).mul_add(0.2, added) * weight
+    }
+}
+
+/// Gets how much a line of a file with extension `ext` takes to review,
+/// relative to a line of code.
+///
+/// Configuration, markup, and lock files are mostly skimmed.
+#[must_use]
+pub fn review_weight(ext: &str) -> f64 {
+    match ext {
+        "lock" => 0.05,
+        "toml" | "yaml" | "yml" | "json" | "cfg" | "md" | "html" | "txt" => 0.3,
+        "css" | "scss" | "sass" | "less" | "styl" => 0.5,
+        _ => 1.0,
+    }
+}
+
+/// Runs `git diff` under `dir` and collects the lines changed per file.
+///
+/// `range` is anything `git diff` accepts, such as `main..HEAD`; without
+/// one, unstaged changes are diffed, or staged ones if `staged` is set.
+/// Paths are relative to `dir` and only changes under it are included.
+///
+/// # Errors
+///
+/// Returns an error if git cannot be run or fails, e.g. outside a
+/// repository or with an unknown revision.
+pub fn git_changes(dir: &Path, range: Option<&str>, staged: bool) -> io::Result<Vec<FileChange>> {
+    let mut command = Command::new("git");
+    command
+        .arg("-C")
+        .arg(dir)
+        .args(["diff", "--no-color", "--no-ext-diff", "--relative", "-U0"]);
+    if staged {
+        command.arg("--cached");
+    }
+    if let Some(range) = range {
+        command.arg(range);
+    }
+
+    let output = command.output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "git diff failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(parse_diff(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Collects the lines changed per file from a unified diff, sorted by path.
+fn parse_diff(diff: &str) -> Vec<FileChange> {
+    let mut changes: Vec<FileChange> = vec![];
+    let mut in_hunk = false;
+
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            in_hunk = false;
+            changes.push(FileChange::default());
+        } else if let Some(path) = line
+            .strip_prefix("+++ ")
+            .filter(|path| !in_hunk && *path != "/dev/null")
+        {
+            if let Some(change) = changes.last_mut() {
+                change.path = PathBuf::from(path.strip_prefix("b/").unwrap_or(path));
+            }
+        } else if let Some(path) = line
+            .strip_prefix("--- ")
+            .filter(|path| !in_hunk && *path != "/dev/null")
+        {
+            // Deleted files only name the old path.
+            if let Some(change) = changes.last_mut() {
+                change.path = PathBuf::from(path.strip_prefix("a/").unwrap_or(path));
+            }
+        } else if line.starts_with("@@") {
+            in_hunk = true;
+        } else if let Some(change) = changes.last_mut().filter(|_| in_hunk) {
+            if let Some(added) = line.strip_prefix('+') {
+                change.added += 1;
+                if BRANCH_TOKENS.iter().any(|token| added.contains(token)) {
+                    change.branching += 1;
+                }
+            } else if line.starts_with('-') {
+                change.deleted += 1;
+            }
+        }
+    }
+
+    // Binary files and pure renames have no changed lines.
+    changes.retain(|change| change.added + change.deleted > 0);
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    changes
+}
@@ -0,0 +1,71 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use std::{fs, path::PathBuf};
+
+/// A root that was dropped because another root already covers it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Overlap {
+    /// The dropped root, as given.
+    pub root: PathBuf,
+    /// The kept root containing it, as given.
+    pub covered_by: PathBuf,
+}
+
+/// Removes roots that are equal to or nested inside another root.
+///
+/// Roots are compared by their canonical paths, so `.` and `./src` overlap
+/// even though they are spelled differently. Roots that cannot be
+/// canonicalized (e.g. because they do not exist) are kept as given.
+/// The kept roots retain their original order.
+#[must_use]
+pub fn dedup_roots(roots: Vec<PathBuf>) -> (Vec<PathBuf>, Vec<Overlap>) {
+    let canonical: Vec<Option<PathBuf>> = roots.iter().map(|r| fs::canonicalize(r).ok()).collect();
+
+    // Ancestors first, so every nested root finds its outermost container.
+    let mut order: Vec<usize> = (0..roots.len()).collect();
+    order.sort_by_key(|&idx| {
+        canonical[idx]
+            .as_ref()
+            .map_or(0, |c| c.components().count())
+    });
+
+    let mut kept: Vec<usize> = vec![];
+    let mut covered_by = vec![None; roots.len()];
+
+    for idx in order {
+        let container = canonical[idx].as_ref().and_then(|path| {
+            kept.iter().copied().find(|&k| {
+                canonical[k]
+                    .as_ref()
+                    .is_some_and(|outer| path.starts_with(outer))
+            })
+        });
+
+        match container {
+            Some(k) => covered_by[idx] = Some(k),
+            None => kept.push(idx),
+        }
+    }
+
+    let mut overlaps = vec![];
+    for (idx, container) in covered_by.iter().enumerate() {
+        if let Some(k) = container {
+            overlaps.push(Overlap {
+                root: roots[idx].clone(),
+                covered_by: roots[*k].clone(),
+            });
+        }
+    }
+
+    let roots = roots
+        .into_iter()
+        .zip(covered_by)
+        .filter_map(|(root, container)| container.is_none().then_some(root))
+        .collect();
+
+    (roots, overlaps)
+}
@@ -0,0 +1,44 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use crate::{csv, timestamp::iso8601};
+use code_line_count::report::{FileCount, Report};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+/// Writes a size-versus-age dataset as CSV.
+///
+/// There is one `path,lines,last_modified,language` row per counted file,
+/// with `last_modified` as an ISO 8601 UTC timestamp (empty if unknown).
+///
+/// # Errors
+///
+/// Returns the I/O error if the file cannot be created or written.
+pub fn write_scatter(
+    path: &Path,
+    report: &Report,
+    language_of: impl Fn(&FileCount) -> String,
+) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+
+    csv::write_row(&mut out, &["path", "lines", "last_modified", "language"])?;
+    for file in &report.files {
+        csv::write_row(
+            &mut out,
+            &[
+                file.path.to_string_lossy().into_owned(),
+                file.lines.to_string(),
+                file.modified.map(iso8601).unwrap_or_default(),
+                language_of(file),
+            ],
+        )?;
+    }
+
+    out.flush()
+}
@@ -0,0 +1,126 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+/// Version of the structured output schema.
+///
+/// Adding fields keeps the version; renaming, removing, or changing the
+/// meaning of a field bumps it.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// JSON Schema (draft 2020-12) of the structured report.
+///
+/// Counts that saturate (see [`code_line_count::report::is_saturated`]) are flagged
+/// with `too_large`, since the number itself is only a lower bound.
+#[must_use]
+pub fn json_schema() -> String {
+    JSON_SCHEMA.replace("{version}", &SCHEMA_VERSION.to_string())
+}
+
+/// Schema template; `{version}` is replaced by [`SCHEMA_VERSION`].
+const JSON_SCHEMA: &str = r##"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "$id": "https://github.com/c1ph3rC4t/code-line-count/schema/v{version}.json",
+  "title": "clc report",
+  "type": "object",
+  "required": ["schema_version", "total", "languages", "extensions", "files"],
+  "properties": {
+    "schema_version": {
+      "description": "Version of this schema. Fields are only ever added within a version.",
+      "const": {version}
+    },
+    "total": { "$ref": "#/$defs/counts" },
+    "languages": {
+      "description": "Counts per category, or per extension (\".ext\") for files matched by a bare extension.",
+      "type": "object",
+      "additionalProperties": { "$ref": "#/$defs/counts" }
+    },
+    "extensions": {
+      "description": "Counts per file extension, without the leading dot.",
+      "type": "object",
+      "additionalProperties": { "$ref": "#/$defs/counts" }
+    },
+    "files": {
+      "description": "Counted files, sorted by path.",
+      "type": "array",
+      "items": {
+        "type": "object",
+        "required": ["path", "extension", "language", "lines"],
+        "properties": {
+          "path": {
+            "description": "Path as found by the walker. Invalid UTF-8 is replaced with U+FFFD.",
+            "type": "string"
+          },
+          "extension": { "type": "string" },
+          "language": { "type": "string" },
+          "lines": { "$ref": "#/$defs/count" },
+          "encoding": {
+            "description": "Encoding the file was converted to UTF-8 from, if it was.",
+            "type": "string"
+          },
+          "code": { "$ref": "#/$defs/code" },
+          "comment": { "$ref": "#/$defs/comment" },
+          "blank": { "$ref": "#/$defs/blank" }
+        }
+      }
+    },
+    "vanished": {
+      "description": "Matched files deleted before they could be read.",
+      "$ref": "#/$defs/count"
+    },
+    "errors": {
+      "description": "Matched files that could not be read for another reason, sorted by path.",
+      "type": "array",
+      "items": {
+        "type": "object",
+        "required": ["path", "message"],
+        "properties": {
+          "path": { "type": "string" },
+          "message": { "type": "string" }
+        }
+      }
+    },
+    "generated": {
+      "description": "Matched files left out because they are marked as generated, sorted by path.",
+      "type": "array",
+      "items": { "type": "string" }
+    },
+    "minified": {
+      "description": "Matched files left out because they are minified, sorted by path.",
+      "type": "array",
+      "items": { "type": "string" }
+    }
+  },
+  "$defs": {
+    "count": { "type": "integer", "minimum": 0 },
+    "counts": {
+      "type": "object",
+      "required": ["files", "lines"],
+      "properties": {
+        "files": { "$ref": "#/$defs/count" },
+        "lines": { "$ref": "#/$defs/count" },
+        "too_large": {
+          "description": "Present and true if lines saturated; the value is then a lower bound.",
+          "type": "boolean"
+        },
+        "code": { "$ref": "#/$defs/code" },
+        "comment": { "$ref": "#/$defs/comment" },
+        "blank": { "$ref": "#/$defs/blank" }
+      }
+    },
+    "code": {
+      "description": "Lines with code, present if comments were detected.",
+      "$ref": "#/$defs/count"
+    },
+    "comment": {
+      "description": "Lines with only a comment, present if comments were detected.",
+      "$ref": "#/$defs/count"
+    },
+    "blank": {
+      "description": "Lines with only whitespace, present if comments were detected.",
+      "$ref": "#/$defs/count"
+    }
+  }
+}"##;
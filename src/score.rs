@@ -0,0 +1,300 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use code_line_count::{
+    comments::{LineClassifier, LineKind},
+    report::Report,
+};
+use std::{
+    collections::HashMap,
+    fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    io,
+    path::{Component, Path},
+};
+
+/// Shortest trimmed line considered when looking for duplication, so that
+/// braces and other boilerplate do not count as duplicated code.
+pub const MIN_DUPLICATE_LEN: usize = 10;
+
+/// Comment ratio at which the comments metric scores full marks.
+const TARGET_COMMENT_RATIO: f64 = 0.15;
+
+/// Test-to-code ratio at which the tests metric scores full marks.
+const TARGET_TEST_RATIO: f64 = 0.5;
+
+/// A metric making up part of the health score.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    /// Share of comment lines.
+    Comments,
+    /// Lines of tests per line of other code.
+    Tests,
+    /// Share of files over the size threshold.
+    Size,
+    /// Share of duplicated lines.
+    Duplication,
+}
+
+/// Weights of the score metrics.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Weights {
+    /// Weight of the comments metric.
+    pub comments: f64,
+    /// Weight of the tests metric.
+    pub tests: f64,
+    /// Weight of the size metric.
+    pub size: f64,
+    /// Weight of the duplication metric.
+    pub duplication: f64,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Self {
+            comments: 1.0,
+            tests: 1.0,
+            size: 1.0,
+            duplication: 1.0,
+        }
+    }
+}
+
+impl Weights {
+    /// Parses weights such as `comments=2,duplication=0`; metrics not
+    /// mentioned keep their default weight of 1.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem if a metric is unknown or a
+    /// weight is not a non-negative number.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut weights = Self::default();
+
+        for pair in spec.split(',').filter(|pair| !pair.is_empty()) {
+            let (name, weight) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("expected NAME=WEIGHT, got \"{pair}\""))?;
+            let weight: f64 = weight
+                .parse()
+                .ok()
+                .filter(|w: &f64| w.is_finite() && *w >= 0.0)
+                .ok_or_else(|| format!("invalid weight \"{weight}\""))?;
+            let slot = match name {
+                "comments" => &mut weights.comments,
+                "tests" => &mut weights.tests,
+                "size" => &mut weights.size,
+                "duplication" => &mut weights.duplication,
+                _ => return Err(format!("unknown score metric \"{name}\"")),
+            };
+            *slot = weight;
+        }
+
+        Ok(weights)
+    }
+
+    /// Gets the weight of a metric.
+    #[must_use]
+    pub const fn of(&self, metric: Metric) -> f64 {
+        match metric {
+            Metric::Comments => self.comments,
+            Metric::Tests => self.tests,
+            Metric::Size => self.size,
+            Metric::Duplication => self.duplication,
+        }
+    }
+}
+
+/// Raw measurements behind the score.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Measures {
+    /// Non-blank lines in all counted files.
+    pub lines: u64,
+    /// Lines holding only comments.
+    pub comment_lines: u64,
+    /// Lines in test files.
+    pub test_lines: u64,
+    /// Counted files.
+    pub files: u64,
+    /// Files with more lines than the size threshold.
+    pub large_files: u64,
+    /// Lines long enough to be checked for duplication.
+    pub checked_lines: u64,
+    /// Checked lines that also appear elsewhere.
+    pub duplicated_lines: u64,
+}
+
+/// One scored metric.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MetricScore {
+    /// Which metric.
+    pub metric: Metric,
+    /// The measured ratio, between 0 and 1 except for tests.
+    pub value: f64,
+    /// The metric's score, from 0 to 100.
+    pub score: f64,
+    /// The metric's weight.
+    pub weight: f64,
+}
+
+impl Metric {
+    /// Gets the metric's name, as used in weights.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Comments => "comments",
+            Self::Tests => "tests",
+            Self::Size => "size",
+            Self::Duplication => "duplication",
+        }
+    }
+}
+
+/// Checks whether a file looks like a test, by its directory or name.
+///
+/// Files under a `test`, `tests`, `spec`, or `__tests__` directory count,
+/// as do names like `foo_test.go`, `test_foo.py`, `foo.test.ts`,
+/// `foo.spec.js`, `FooTest.java`, and `foo_spec.rb`.
+#[must_use]
+pub fn is_test_file(path: &Path) -> bool {
+    let in_test_dir = path.parent().is_some_and(|dir| {
+        dir.components().any(|c| {
+            matches!(c, Component::Normal(name)
+                if matches!(name.to_str(), Some("test" | "tests" | "spec" | "__tests__")))
+        })
+    });
+
+    let stem = path
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned();
+
+    in_test_dir
+        || stem.ends_with("_test")
+        || stem.starts_with("test_")
+        || Path::new(&stem)
+            .extension()
+            .is_some_and(|ext| ext == "test" || ext == "spec")
+        || stem.ends_with("_spec")
+        || (stem.len() > 4 && stem.ends_with("Test"))
+}
+
+/// Gets the hash of a line for duplicate detection.
+fn line_hash(line: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    line.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Measures the counted files, reading each one again.
+///
+/// Comment lines are told apart by `classifier`, as with `--no-comments`.
+/// Files that vanished since the count are skipped.
+///
+/// # Errors
+///
+/// Returns the I/O error if a file cannot be read.
+pub fn measure(
+    report: &Report,
+    max_file_lines: u64,
+    classifier: &LineClassifier,
+) -> io::Result<Measures> {
+    let mut measures = Measures::default();
+    let mut seen: HashMap<u64, u32> = HashMap::new();
+
+    for file in &report.files {
+        let bytes = match fs::read(&file.path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err),
+        };
+        let syntax = classifier.syntax_of(&file.path, &bytes);
+
+        measures.files += 1;
+        measures.lines = measures.lines.saturating_add(file.lines);
+        if file.lines > max_file_lines {
+            measures.large_files += 1;
+        }
+        if is_test_file(&file.path) {
+            measures.test_lines = measures.test_lines.saturating_add(file.lines);
+        }
+
+        classifier.classify_lines(&bytes, syntax.as_ref(), |line, kind| match kind {
+            LineKind::Comment => measures.comment_lines += 1,
+            LineKind::Code if line.trim_ascii().len() >= MIN_DUPLICATE_LEN => {
+                measures.checked_lines += 1;
+                *seen.entry(line_hash(line.trim_ascii())).or_default() += 1;
+            }
+            LineKind::Code | LineKind::Blank => {}
+        });
+    }
+
+    measures.duplicated_lines = seen
+        .into_values()
+        .filter(|&count| count > 1)
+        .map(u64::from)
+        .sum();
+
+    Ok(measures)
+}
+
+/// Divides, treating a zero denominator as a ratio of zero.
+#[allow(clippy::cast_precision_loss)]
+fn ratio(numerator: u64, denominator: u64) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+/// Scores each metric from 0 to 100.
+///
+/// Comments and tests score full marks at 15% comment lines and half a line
+/// of tests per line of other code. Size and duplication lose marks in
+/// proportion to the share of large files and duplicated lines.
+#[must_use]
+pub fn score_metrics(measures: &Measures, weights: &Weights) -> Vec<MetricScore> {
+    let comments = ratio(measures.comment_lines, measures.lines);
+    let tests = ratio(
+        measures.test_lines,
+        measures.lines.saturating_sub(measures.test_lines),
+    );
+    let size = ratio(measures.large_files, measures.files);
+    let duplication = ratio(measures.duplicated_lines, measures.checked_lines);
+
+    [
+        (
+            Metric::Comments,
+            comments,
+            (comments / TARGET_COMMENT_RATIO).min(1.0),
+        ),
+        (Metric::Tests, tests, (tests / TARGET_TEST_RATIO).min(1.0)),
+        (Metric::Size, size, 1.0 - size),
+        (Metric::Duplication, duplication, 1.0 - duplication),
+    ]
+    .into_iter()
+    .map(|(metric, value, score)| MetricScore {
+        metric,
+        value,
+        score: score * 100.0,
+        weight: weights.of(metric),
+    })
+    .collect()
+}
+
+/// Combines metric scores into their weighted mean.
+///
+/// Returns 0 if all weights are 0.
+#[must_use]
+pub fn total_score(metrics: &[MetricScore]) -> f64 {
+    let weight: f64 = metrics.iter().map(|c| c.weight).sum();
+    if weight == 0.0 {
+        return 0.0;
+    }
+    metrics.iter().map(|c| c.score * c.weight).sum::<f64>() / weight
+}
@@ -0,0 +1,119 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use std::{
+    ffi::OsStr,
+    fs::File,
+    io::{BufRead, BufReader, Read},
+    path::Path,
+};
+
+/// Longest `#!` line read from a file, in bytes.
+pub const MAX_SHEBANG_LEN: u64 = 256;
+
+/// Interpreters a `#!` line may name, without a version suffix such as the
+/// `3.11` of `python3.11`, and the extension of their language's files.
+const INTERPRETERS: &[(&str, &str)] = &[
+    ("python", "py"),
+    ("pypy", "py"),
+    ("sh", "sh"),
+    ("ash", "sh"),
+    ("dash", "sh"),
+    ("ksh", "sh"),
+    ("mksh", "sh"),
+    ("bash", "bash"),
+    ("zsh", "zsh"),
+    ("fish", "fish"),
+    ("node", "js"),
+    ("nodejs", "js"),
+    ("bun", "js"),
+    ("deno", "ts"),
+    ("ts-node", "ts"),
+    ("tsx", "ts"),
+    ("ruby", "rb"),
+    ("jruby", "rb"),
+    ("php", "php"),
+    ("elixir", "exs"),
+    ("runghc", "hs"),
+    ("runhaskell", "hs"),
+    ("kotlin", "kts"),
+    ("perl", "pl"),
+    ("lua", "lua"),
+    ("tclsh", "tcl"),
+];
+
+/// Gets the extension of the language of the interpreter a script's `#!`
+/// line names, or `None` if it has no `#!` line or names an unknown
+/// interpreter.
+///
+/// The interpreter is the program the line runs, or the one it hands to
+/// `env`, after `env`'s options such as `-S` and variable assignments.
+/// Version suffixes are ignored, so `python3.11` is Python.
+///
+/// # Example
+///
+/// ```
+/// use code_line_count::shebang::shebang_extension;
+///
+/// assert_eq!(shebang_extension(b"#!/usr/bin/env python3\nprint(1)\n"), Some("py"));
+/// assert_eq!(shebang_extension(b"#!/bin/bash -e\necho hi\n"), Some("bash"));
+/// assert_eq!(shebang_extension(b"#!/usr/bin/env -S node --no-warnings\n"), Some("js"));
+/// assert_eq!(shebang_extension(b"#!/usr/bin/make -f\n"), None);
+/// assert_eq!(shebang_extension(b"print(1)\n"), None);
+/// ```
+#[must_use]
+pub fn shebang_extension(contents: &[u8]) -> Option<&'static str> {
+    let line = contents.strip_prefix(b"#!")?;
+    let line = &line[..line.iter().position(|&b| b == b'\n').unwrap_or(line.len())];
+    let mut words = line
+        .split(u8::is_ascii_whitespace)
+        .filter(|word| !word.is_empty());
+
+    let mut program = program_name(words.next()?);
+    if program == b"env" {
+        program =
+            program_name(words.find(|word| !word.starts_with(b"-") && !word.contains(&b'='))?);
+    }
+
+    let name = program
+        .iter()
+        .rposition(|b| !b.is_ascii_digit() && *b != b'.')
+        .map_or(program, |end| &program[..=end]);
+    INTERPRETERS
+        .iter()
+        .find(|(interpreter, _)| interpreter.as_bytes() == name)
+        .map(|&(_, ext)| ext)
+}
+
+/// Gets every extension a `#!` line can give a file.
+pub(crate) fn script_extensions() -> impl Iterator<Item = &'static str> {
+    INTERPRETERS.iter().map(|&(_, ext)| ext)
+}
+
+/// Gets the file name of a program from its path.
+fn program_name(path: &[u8]) -> &[u8] {
+    path.rsplit(|&b| b == b'/').next().unwrap_or(path)
+}
+
+/// Gets the extension a file is attributed to: its own, or if it has none,
+/// the one its `#!` line gives it, as found by [`shebang_extension`].
+#[must_use]
+pub fn script_extension<'a>(path: &'a Path, contents: &[u8]) -> Option<&'a OsStr> {
+    path.extension()
+        .or_else(|| shebang_extension(contents).map(OsStr::new))
+}
+
+/// Reads the `#!` line of the file at `path` and gets the extension it
+/// gives the file, as with [`shebang_extension`], or `None` if it cannot be
+/// read.
+pub(crate) fn read_shebang_extension(path: &Path) -> Option<&'static str> {
+    let mut line = Vec::new();
+    BufReader::new(File::open(path).ok()?)
+        .take(MAX_SHEBANG_LEN)
+        .read_until(b'\n', &mut line)
+        .ok()?;
+    shebang_extension(&line)
+}
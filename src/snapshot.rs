@@ -0,0 +1,178 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use code_line_count::{CLCError, json};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+
+/// Directory snapshots are stored in, relative to the working directory.
+pub const SNAPSHOT_DIR: &str = ".clc/snapshots";
+
+/// What to do with snapshots.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SnapshotAction {
+    /// Count and save the report under a name.
+    Save(String),
+    /// Compare two saved reports.
+    Diff(String, String),
+}
+
+/// Checks that a snapshot name is usable as a file name.
+///
+/// # Errors
+///
+/// Returns a description of the problem if the name is empty, starts with a
+/// dot, or contains a path separator.
+pub fn check_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.starts_with('.') || name.contains(std::path::is_separator) {
+        Err(format!("invalid snapshot name \"{name}\""))
+    } else {
+        Ok(())
+    }
+}
+
+/// Gets the file a snapshot is stored in.
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(SNAPSHOT_DIR).join(format!("{name}.json.gz"))
+}
+
+/// Checks whether a snapshot has been saved.
+#[must_use]
+pub fn exists(name: &str) -> bool {
+    snapshot_path(name).is_file()
+}
+
+/// Saves a structured report as a gzip-compressed snapshot, replacing any
+/// snapshot of the same name.
+///
+/// # Errors
+///
+/// Returns the I/O error if the snapshot cannot be written.
+pub fn save(name: &str, report: &json::Value) -> io::Result<PathBuf> {
+    let path = snapshot_path(name);
+    fs::create_dir_all(SNAPSHOT_DIR)?;
+
+    let mut out = GzEncoder::new(BufWriter::new(File::create(&path)?), Compression::best());
+    write!(out, "{report}")?;
+    out.finish()?.flush()?;
+
+    Ok(path)
+}
+
+/// Loads a saved snapshot.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if the snapshot cannot be read or is not valid JSON.
+pub fn load(name: &str) -> Result<json::Value, CLCError> {
+    load_path(&snapshot_path(name))
+}
+
+/// Loads a snapshot from its file.
+fn load_path(path: &Path) -> Result<json::Value, CLCError> {
+    let read_file = |source| CLCError::ReadFile {
+        path: path.to_path_buf(),
+        source,
+    };
+
+    let mut text = String::new();
+    GzDecoder::new(BufReader::new(File::open(path).map_err(read_file)?))
+        .read_to_string(&mut text)
+        .map_err(read_file)?;
+    Ok(json::parse(&text)?)
+}
+
+/// Loads the most recently saved snapshot of the project at `root`, if it
+/// has any.
+///
+/// # Errors
+///
+/// Returns [`CLCError`] if the snapshot cannot be read or is not valid JSON.
+pub fn latest(root: &Path) -> Result<Option<json::Value>, CLCError> {
+    let Ok(entries) = fs::read_dir(root.join(SNAPSHOT_DIR)) else {
+        return Ok(None);
+    };
+
+    let newest = entries
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().ends_with(".json.gz"))
+        .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+        .max();
+
+    newest.map(|(_, path)| load_path(&path)).transpose()
+}
+
+/// Lines of one language in two snapshots.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LanguageDiff {
+    /// Language name.
+    pub name: String,
+    /// Lines in the older snapshot, 0 if the language was absent.
+    pub before: u64,
+    /// Lines in the newer snapshot, 0 if the language was absent.
+    pub after: u64,
+}
+
+impl LanguageDiff {
+    /// Gets the change in lines, negative if lines were removed.
+    #[must_use]
+    pub fn change(&self) -> i128 {
+        i128::from(self.after) - i128::from(self.before)
+    }
+}
+
+/// Compares the per-language lines of two snapshots, sorted by language.
+///
+/// The totals are appended as a last entry named `total`.
+#[must_use]
+pub fn diff(before: &json::Value, after: &json::Value) -> Vec<LanguageDiff> {
+    let lines = |snapshot: &json::Value| -> BTreeMap<String, u64> {
+        snapshot
+            .get("languages")
+            .and_then(json::Value::as_object)
+            .unwrap_or_default()
+            .iter()
+            .map(|(name, counts)| {
+                let lines = counts.get("lines").and_then(json::Value::as_u64);
+                (name.clone(), lines.unwrap_or(0))
+            })
+            .collect()
+    };
+
+    let before_lines = lines(before);
+    let after_lines = lines(after);
+
+    let mut names: Vec<&String> = before_lines.keys().chain(after_lines.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    let total = |snapshot: &json::Value| {
+        snapshot
+            .get("total")
+            .and_then(|total| total.get("lines"))
+            .and_then(json::Value::as_u64)
+            .unwrap_or(0)
+    };
+
+    names
+        .into_iter()
+        .map(|name| LanguageDiff {
+            name: name.clone(),
+            before: before_lines.get(name).copied().unwrap_or(0),
+            after: after_lines.get(name).copied().unwrap_or(0),
+        })
+        .chain(std::iter::once(LanguageDiff {
+            name: "total".to_string(),
+            before: total(before),
+            after: total(after),
+        }))
+        .collect()
+}
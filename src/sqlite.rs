@@ -0,0 +1,114 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use crate::{stats::language_stats, timestamp::iso8601};
+use code_line_count::report::{FileCount, Report};
+use rusqlite::{Connection, params};
+use std::{
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// Tables runs are exported to, created if missing.
+///
+/// Every export adds a row to `runs`; the files and categories of a run
+/// refer to it by `run_id`. Timestamps are ISO 8601 UTC, so they sort
+/// chronologically as text.
+pub const SCHEMA: &str = "\
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    timestamp TEXT NOT NULL,
+    roots TEXT NOT NULL,
+    files INTEGER NOT NULL,
+    lines INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS categories (
+    run_id INTEGER NOT NULL REFERENCES runs (id),
+    name TEXT NOT NULL,
+    files INTEGER NOT NULL,
+    lines INTEGER NOT NULL,
+    PRIMARY KEY (run_id, name)
+);
+CREATE TABLE IF NOT EXISTS files (
+    run_id INTEGER NOT NULL REFERENCES runs (id),
+    path TEXT NOT NULL,
+    extension TEXT NOT NULL,
+    category TEXT NOT NULL,
+    lines INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS files_run_id ON files (run_id);";
+
+/// Converts a count to a database integer, clamping values too large for
+/// one.
+fn integer(value: impl TryInto<i64>) -> i64 {
+    value.try_into().unwrap_or(i64::MAX)
+}
+
+/// Appends a report to a database file as a new run, creating the
+/// database and the tables of [`SCHEMA`] if needed.
+///
+/// `language_of` attributes each file to a category. Returns the id of the
+/// new run.
+///
+/// # Errors
+///
+/// Returns [`rusqlite::Error`] if the database cannot be opened or written.
+pub fn export(
+    path: &Path,
+    report: &Report,
+    roots: &[PathBuf],
+    language_of: impl Fn(&FileCount) -> String,
+) -> rusqlite::Result<i64> {
+    let mut conn = Connection::open(path)?;
+    conn.execute_batch(SCHEMA)?;
+    let tx = conn.transaction()?;
+
+    let roots: Vec<String> = roots
+        .iter()
+        .map(|root| root.display().to_string())
+        .collect();
+    tx.execute(
+        "INSERT INTO runs (timestamp, roots, files, lines) VALUES (?1, ?2, ?3, ?4)",
+        params![
+            iso8601(SystemTime::now()),
+            roots.join("\n"),
+            integer(report.files.len()),
+            integer(report.total_lines()),
+        ],
+    )?;
+    let run_id = tx.last_insert_rowid();
+
+    {
+        let mut insert = tx.prepare(
+            "INSERT INTO categories (run_id, name, files, lines) VALUES (?1, ?2, ?3, ?4)",
+        )?;
+        for stats in language_stats(report, &language_of) {
+            insert.execute(params![
+                run_id,
+                stats.name,
+                integer(stats.files),
+                integer(stats.lines)
+            ])?;
+        }
+
+        let mut insert = tx.prepare(
+            "INSERT INTO files (run_id, path, extension, category, lines) \
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+        )?;
+        for file in &report.files {
+            insert.execute(params![
+                run_id,
+                file.path.to_string_lossy(),
+                file.extension().to_string_lossy(),
+                language_of(file),
+                integer(file.lines),
+            ])?;
+        }
+    }
+
+    tx.commit()?;
+    Ok(run_id)
+}
@@ -0,0 +1,182 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use code_line_count::report::{FileCount, LineKinds, Report, saturating_sum, sum_kinds};
+use std::{collections::BTreeMap, path::PathBuf};
+
+/// Distribution of lines per file for one language.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LanguageStats {
+    /// Language name.
+    pub name: String,
+    /// Number of files.
+    pub files: usize,
+    /// Total lines, saturating on overflow.
+    pub lines: u64,
+    /// Median lines per file.
+    pub median: u64,
+    /// 90th percentile of lines per file.
+    pub p90: u64,
+    /// 99th percentile of lines per file.
+    pub p99: u64,
+    /// Code, comment, and blank lines of the files that have them.
+    pub kinds: Option<LineKinds>,
+}
+
+/// Gets the nearest-rank percentile of sorted values.
+///
+/// Returns 0 for no values.
+#[must_use]
+pub fn percentile(sorted: &[u64], percent: usize) -> u64 {
+    let rank = (sorted.len() * percent).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied().unwrap_or(0)
+}
+
+/// Computes per-language statistics, sorted by language name.
+///
+/// `language_of` attributes each file to a language. Percentiles use the
+/// nearest-rank method, so every value is an actual file's line count.
+pub fn language_stats(
+    report: &Report,
+    language_of: impl Fn(&FileCount) -> String,
+) -> Vec<LanguageStats> {
+    let mut by_language: BTreeMap<String, Vec<&FileCount>> = BTreeMap::new();
+
+    for file in &report.files {
+        by_language.entry(language_of(file)).or_default().push(file);
+    }
+
+    by_language
+        .into_iter()
+        .map(|(name, files)| {
+            let mut lines: Vec<u64> = files.iter().map(|file| file.lines).collect();
+            lines.sort_unstable();
+            LanguageStats {
+                name,
+                files: lines.len(),
+                lines: saturating_sum(lines.iter().copied()),
+                median: percentile(&lines, 50),
+                p90: percentile(&lines, 90),
+                p99: percentile(&lines, 99),
+                kinds: sum_kinds(files.iter().map(|file| file.kinds)),
+            }
+        })
+        .collect()
+}
+
+/// A file far longer than is typical for its language.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outlier {
+    /// Path of the file.
+    pub path: PathBuf,
+    /// Language the file is attributed to.
+    pub language: String,
+    /// Lines in the file.
+    pub lines: u64,
+    /// Median lines per file of the language.
+    pub median: u64,
+}
+
+/// Finds files with more than `factor` times the median lines per file of
+/// their language, longest relative to the median first.
+///
+/// `language_of` attributes each file to a language, as in
+/// [`language_stats`]. Languages with a median of 0 have no outliers.
+pub fn outliers(
+    report: &Report,
+    language_of: impl Fn(&FileCount) -> String,
+    factor: f64,
+) -> Vec<Outlier> {
+    let mut by_language: BTreeMap<String, Vec<&FileCount>> = BTreeMap::new();
+    for file in &report.files {
+        by_language.entry(language_of(file)).or_default().push(file);
+    }
+
+    let mut outliers: Vec<Outlier> = vec![];
+    for (language, files) in by_language {
+        let mut lines: Vec<u64> = files.iter().map(|file| file.lines).collect();
+        lines.sort_unstable();
+        let median = percentile(&lines, 50);
+        if median == 0 {
+            continue;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let threshold = median as f64 * factor;
+        outliers.extend(
+            files
+                .into_iter()
+                .filter(|file| {
+                    #[allow(clippy::cast_precision_loss)]
+                    let lines = file.lines as f64;
+                    lines > threshold
+                })
+                .map(|file| Outlier {
+                    path: file.path.clone(),
+                    language: language.clone(),
+                    lines: file.lines,
+                    median,
+                }),
+        );
+    }
+
+    // Compares lines / median exactly: a/b < c/d iff a*d < c*b.
+    outliers.sort_by(|a, b| {
+        let ratio_a = u128::from(a.lines) * u128::from(b.median);
+        let ratio_b = u128::from(b.lines) * u128::from(a.median);
+        ratio_b.cmp(&ratio_a).then_with(|| a.path.cmp(&b.path))
+    });
+    outliers
+}
+
+/// Upper bounds of the default buckets of [`histogram`]: files of up to 50,
+/// 200, and 500 lines, then longer ones.
+pub const HISTOGRAM_BOUNDS: &[u64] = &[50, 200, 500];
+
+/// A range of lines per file and the files in it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bucket {
+    /// Fewest lines of a file in the bucket.
+    pub min: u64,
+    /// Most lines of a file in the bucket, or `None` if unbounded.
+    pub max: Option<u64>,
+    /// Number of files in the bucket.
+    pub files: usize,
+    /// Total lines of those files, saturating on overflow.
+    pub lines: u64,
+}
+
+/// Buckets files by their lines.
+///
+/// `bounds` are the inclusive upper bounds of the buckets, in increasing
+/// order; a last, unbounded bucket holds longer files. Every bucket is
+/// returned, even if empty.
+#[must_use]
+pub fn histogram(report: &Report, bounds: &[u64]) -> Vec<Bucket> {
+    let mut buckets: Vec<Bucket> = bounds
+        .iter()
+        .map(|&max| Some(max))
+        .chain([None])
+        .scan(0, |min, max| {
+            let bucket = Bucket {
+                min: *min,
+                max,
+                files: 0,
+                lines: 0,
+            };
+            *min = max.map_or(0, |max| max.saturating_add(1));
+            Some(bucket)
+        })
+        .collect();
+
+    for file in &report.files {
+        let idx = bounds.partition_point(|&max| max < file.lines);
+        let bucket = &mut buckets[idx];
+        bucket.files += 1;
+        bucket.lines = bucket.lines.saturating_add(file.lines);
+    }
+    buckets
+}
@@ -0,0 +1,162 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use crate::schema::SCHEMA_VERSION;
+use code_line_count::{
+    json::Value,
+    report::{FileCount, LineKinds, Report, is_saturated},
+};
+use std::{collections::BTreeMap, path::PathBuf};
+
+/// Files and lines of a group of files.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Counts {
+    /// Number of files.
+    files: u64,
+    /// Total lines, saturating on overflow.
+    lines: u64,
+    /// Code, comment, and blank lines of the files that have them.
+    kinds: Option<LineKinds>,
+}
+
+impl Counts {
+    /// Adds a file.
+    const fn add(&mut self, file: &FileCount) {
+        self.files = self.files.saturating_add(1);
+        self.lines = self.lines.saturating_add(file.lines);
+        if let Some(kinds) = file.kinds {
+            match &mut self.kinds {
+                Some(total) => total.add(kinds),
+                None => self.kinds = Some(kinds),
+            }
+        }
+    }
+
+    /// Converts the counts to their structured form.
+    fn to_value(self) -> Value {
+        let mut entries = vec![
+            ("files".to_string(), number(self.files)),
+            ("lines".to_string(), number(self.lines)),
+        ];
+        if is_saturated(self.lines) {
+            entries.push(("too_large".to_string(), Value::Bool(true)));
+        }
+        push_kinds(&mut entries, self.kinds);
+        Value::Object(entries)
+    }
+}
+
+/// Appends the `code`, `comment`, and `blank` fields of classified lines,
+/// if any.
+fn push_kinds(entries: &mut Vec<(String, Value)>, kinds: Option<LineKinds>) {
+    if let Some(kinds) = kinds {
+        entries.extend([
+            ("code".to_string(), number(kinds.code)),
+            ("comment".to_string(), number(kinds.comment)),
+            ("blank".to_string(), number(kinds.blank)),
+        ]);
+    }
+}
+
+/// Converts a count to a JSON number.
+fn number(value: u64) -> Value {
+    Value::Number(value.to_string())
+}
+
+/// Converts grouped counts to an object keyed by group name.
+fn groups(groups: BTreeMap<String, Counts>) -> Value {
+    Value::Object(
+        groups
+            .into_iter()
+            .map(|(name, counts)| (name, counts.to_value()))
+            .collect(),
+    )
+}
+
+/// Gets the extension a file is attributed to, without the leading dot, or
+/// an empty string if it has none.
+fn extension_of(file: &FileCount) -> String {
+    file.extension().to_string_lossy().into_owned()
+}
+
+/// Builds the structured form of a counted file, as listed under `files`
+/// in the structured report.
+#[must_use]
+pub fn file_value(file: &FileCount, language: String) -> Value {
+    let mut entry = vec![
+        (
+            "path".to_string(),
+            Value::String(file.path.to_string_lossy().into_owned()),
+        ),
+        ("extension".to_string(), Value::String(extension_of(file))),
+        ("language".to_string(), Value::String(language)),
+        ("lines".to_string(), number(file.lines)),
+    ];
+    if let Some(encoding) = file.encoding {
+        entry.push(("encoding".to_string(), Value::String(encoding.to_string())));
+    }
+    push_kinds(&mut entry, file.kinds);
+    Value::Object(entry)
+}
+
+/// Builds the structured form of a report, as described by
+/// [`crate::schema::json_schema`].
+///
+/// `language_of` attributes each file to a language.
+pub fn report_value(report: &Report, language_of: impl Fn(&FileCount) -> String) -> Value {
+    let mut total = Counts::default();
+    let mut languages: BTreeMap<String, Counts> = BTreeMap::new();
+    let mut extensions: BTreeMap<String, Counts> = BTreeMap::new();
+    let mut files = Vec::with_capacity(report.files.len());
+
+    for file in &report.files {
+        let language = language_of(file);
+
+        total.add(file);
+        languages.entry(language.clone()).or_default().add(file);
+        extensions.entry(extension_of(file)).or_default().add(file);
+        files.push(file_value(file, language));
+    }
+
+    Value::Object(vec![
+        ("schema_version".to_string(), number(SCHEMA_VERSION.into())),
+        ("total".to_string(), total.to_value()),
+        ("languages".to_string(), groups(languages)),
+        ("extensions".to_string(), groups(extensions)),
+        ("files".to_string(), Value::Array(files)),
+        ("vanished".to_string(), number(report.vanished)),
+        (
+            "errors".to_string(),
+            Value::Array(
+                report
+                    .errors
+                    .iter()
+                    .map(|error| {
+                        Value::Object(vec![
+                            (
+                                "path".to_string(),
+                                Value::String(error.path.to_string_lossy().into_owned()),
+                            ),
+                            ("message".to_string(), Value::String(error.message.clone())),
+                        ])
+                    })
+                    .collect(),
+            ),
+        ),
+        ("generated".to_string(), paths(&report.generated)),
+        ("minified".to_string(), paths(&report.minified)),
+    ])
+}
+
+/// Gets an array of paths.
+fn paths(paths: &[PathBuf]) -> Value {
+    Value::Array(
+        paths
+            .iter()
+            .map(|path| Value::String(path.to_string_lossy().into_owned()))
+            .collect(),
+    )
+}
@@ -0,0 +1,170 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use crate::color::{BOLD, CYAN, paint};
+use std::fmt::Write;
+
+/// Narrowest a text column is elided to when fitting a table to a width.
+const MIN_ELIDED_WIDTH: usize = 12;
+
+/// Shortens text to at most `width` characters by replacing its middle
+/// with `…`, keeping more of the end, such as a path's file name.
+#[must_use]
+pub fn elide(text: &str, width: usize) -> String {
+    let len = text.chars().count();
+    if len <= width {
+        return text.to_string();
+    }
+    let keep = width.saturating_sub(1);
+    let head = keep / 3;
+    let tail = keep - head;
+    let mut elided: String = text.chars().take(head).collect();
+    elided.push('…');
+    elided.extend(text.chars().skip(len - tail));
+    elided
+}
+
+/// Rows of aligned columns under a header, optionally ending with a totals
+/// row.
+///
+/// Text columns are left-aligned and numeric columns right-aligned; a
+/// column is numeric if every cell below the header parses as a number. A
+/// rule separates the header from the rows, and the rows from the totals.
+/// Rows may leave out trailing cells, such as totals of columns that cannot
+/// be summed. With color, the header and totals are bold and the first
+/// column, naming each row, is cyan. With a maximum width, the widest text
+/// columns are narrowed to fit, eliding the middle of longer cells.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Table {
+    /// Column names.
+    header: Vec<String>,
+    /// Rows, with at most one cell per column.
+    rows: Vec<Vec<String>>,
+    /// Totals row, if any.
+    total: Option<Vec<String>>,
+    /// Whether to color the table.
+    color: bool,
+    /// Width the table is narrowed to, if any.
+    max_width: Option<usize>,
+}
+
+impl Table {
+    /// Creates a table without any rows.
+    #[must_use]
+    pub fn new(header: &[&str]) -> Self {
+        Self {
+            header: header.iter().map(ToString::to_string).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Adds rows.
+    #[must_use]
+    pub fn rows(mut self, rows: &[Vec<String>]) -> Self {
+        self.rows.extend_from_slice(rows);
+        self
+    }
+
+    /// Sets the totals row.
+    #[must_use]
+    pub fn total(mut self, total: Vec<String>) -> Self {
+        self.total = Some(total);
+        self
+    }
+
+    /// Sets whether to color the table with ANSI escape sequences.
+    #[must_use]
+    pub const fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets the width to narrow the table to, if any, such as the
+    /// terminal's.
+    #[must_use]
+    pub const fn max_width(mut self, max_width: Option<usize>) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    /// Narrows the widest text columns until the table fits `max_width` or
+    /// they are all [`MIN_ELIDED_WIDTH`] wide.
+    fn fit(widths: &mut [usize], numeric: &[bool], max_width: usize) {
+        let total = widths.iter().sum::<usize>() + 2 * widths.len().saturating_sub(1);
+        let mut excess = total.saturating_sub(max_width);
+        while excess > 0 {
+            let Some((col, width)) = widths
+                .iter()
+                .copied()
+                .enumerate()
+                .filter(|&(col, width)| !numeric[col] && width > MIN_ELIDED_WIDTH)
+                .max_by_key(|&(_, width)| width)
+            else {
+                break;
+            };
+            let shrink = excess.min(width - MIN_ELIDED_WIDTH);
+            widths[col] -= shrink;
+            excess -= shrink;
+        }
+    }
+
+    /// Renders the table, one line per row.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let body = || self.rows.iter().chain(&self.total);
+
+        let numeric: Vec<bool> = (0..self.header.len())
+            .map(|col| {
+                body().all(|row| row.get(col).is_none_or(|cell| cell.parse::<f64>().is_ok()))
+            })
+            .collect();
+
+        let mut widths: Vec<usize> = self
+            .header
+            .iter()
+            .map(|cell| cell.chars().count())
+            .collect();
+        for row in body() {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        if let Some(max_width) = self.max_width {
+            Self::fit(&mut widths, &numeric, max_width);
+        }
+
+        let rule = "-".repeat(widths.iter().sum::<usize>() + 2 * widths.len().saturating_sub(1));
+        let line = |row: &[String], sgr: Option<&str>| {
+            let mut line = String::new();
+            for (idx, (cell, width)) in row.iter().zip(&widths).enumerate() {
+                let sep = if idx == 0 { "" } else { "  " };
+                // Padding is added outside the escape sequences, so they do
+                // not count towards the width.
+                let cell = elide(cell, *width);
+                let pad = " ".repeat(width.saturating_sub(cell.chars().count()));
+                let sgr = sgr.or_else(|| (idx == 0).then_some(CYAN));
+                let cell = sgr.map_or_else(|| cell.clone(), |sgr| paint(&cell, sgr, self.color));
+                let _ = if numeric[idx] {
+                    write!(line, "{sep}{pad}{cell}")
+                } else {
+                    write!(line, "{sep}{cell}{pad}")
+                };
+            }
+            format!("{}\n", line.trim_end())
+        };
+
+        let mut out = line(&self.header, Some(BOLD));
+        let _ = writeln!(out, "{rule}");
+        for row in &self.rows {
+            out.push_str(&line(row, None));
+        }
+        if let Some(total) = &self.total {
+            let _ = writeln!(out, "{rule}");
+            out.push_str(&line(total, Some(BOLD)));
+        }
+        out
+    }
+}
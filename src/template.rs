@@ -0,0 +1,99 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+/// Placeholders available in per-language row templates. `category` is
+/// another name for `lang`.
+pub const ROW_FIELDS: &[&str] = &["lang", "category", "files", "lines", "median", "p90", "p99"];
+
+/// Placeholders available in summary templates.
+pub const SUMMARY_FIELDS: &[&str] = &["languages", "files", "lines"];
+
+/// Part of a template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Piece {
+    /// Literal text.
+    Text(String),
+    /// A placeholder, by name.
+    Field(&'static str),
+}
+
+/// A user-supplied output template such as `{lang}\t{lines}`.
+///
+/// `{name}` is replaced by a value, `{{` and `}}` stand for literal braces,
+/// and `\t`, `\n`, and `\\` are unescaped, so templates can be written
+/// without relying on the shell to produce tabs and newlines.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Template {
+    /// Parts of the template, in order.
+    pieces: Vec<Piece>,
+}
+
+impl Template {
+    /// Parses a template allowing the placeholders in `fields`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem if a placeholder is unknown or
+    /// unterminated, or an escape sequence is invalid.
+    pub fn parse(template: &str, fields: &[&'static str]) -> Result<Self, String> {
+        let mut pieces = vec![];
+        let mut text = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.next_if_eq(&'{').is_some() => text.push('{'),
+                '}' if chars.next_if_eq(&'}').is_some() => text.push('}'),
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(format!("unterminated placeholder {{{name}")),
+                        }
+                    }
+                    let Some(field) = fields.iter().find(|field| **field == name) else {
+                        return Err(format!(
+                            "unknown placeholder {{{name}}}, expected one of {{{}}}",
+                            fields.join("}, {")
+                        ));
+                    };
+                    if !text.is_empty() {
+                        pieces.push(Piece::Text(std::mem::take(&mut text)));
+                    }
+                    pieces.push(Piece::Field(field));
+                }
+                '}' => return Err("unmatched }, write }} for a literal brace".to_string()),
+                '\\' => match chars.next() {
+                    Some('t') => text.push('\t'),
+                    Some('n') => text.push('\n'),
+                    Some('\\') => text.push('\\'),
+                    Some(c) => return Err(format!("unknown escape \\{c}")),
+                    None => return Err("trailing backslash".to_string()),
+                },
+                c => text.push(c),
+            }
+        }
+
+        if !text.is_empty() {
+            pieces.push(Piece::Text(text));
+        }
+
+        Ok(Self { pieces })
+    }
+
+    /// Renders the template, getting placeholder values from `value`.
+    pub fn render(&self, value: impl Fn(&str) -> String) -> String {
+        self.pieces
+            .iter()
+            .map(|piece| match piece {
+                Piece::Text(text) => text.clone(),
+                Piece::Field(field) => value(field),
+            })
+            .collect()
+    }
+}
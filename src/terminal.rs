@@ -0,0 +1,45 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use std::env;
+
+/// Gets the width of the terminal stdout is written to, in columns.
+///
+/// A positive `COLUMNS` variable takes precedence, as with `ls`. Otherwise
+/// the terminal is asked on Unix, and `None` is returned elsewhere or if
+/// stdout is not a terminal.
+#[must_use]
+pub fn terminal_width() -> Option<usize> {
+    if let Some(columns) = env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse().ok())
+        .filter(|&columns| columns > 0)
+    {
+        return Some(columns);
+    }
+    window_width()
+}
+
+/// Asks the terminal on stdout for its width.
+#[cfg(unix)]
+fn window_width() -> Option<usize> {
+    let mut size = libc::winsize {
+        ws_row: 0,
+        ws_col: 0,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    // SAFETY: `TIOCGWINSZ` only writes a `winsize` through the pointer,
+    // which is valid for the duration of the call.
+    let status = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &raw mut size) };
+    (status == 0 && size.ws_col > 0).then_some(usize::from(size.ws_col))
+}
+
+/// Asks the terminal on stdout for its width, which is not supported here.
+#[cfg(not(unix))]
+const fn window_width() -> Option<usize> {
+    None
+}
@@ -0,0 +1,48 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use chardetng::EncodingDetector;
+use encoding_rs::{Encoding, UTF_8};
+use std::borrow::Cow;
+
+/// Converts a file's contents to UTF-8 if they are in another encoding.
+///
+/// UTF-16 is recognized by its byte order mark. Contents that are not
+/// valid UTF-8 are taken to be in the legacy encoding they most likely
+/// are in, such as Shift-JIS, GBK, or windows-1252 (a superset of
+/// Latin-1). Contents with NUL bytes are left alone, as they are probably
+/// binary.
+///
+/// Returns the name of the encoding the contents were converted from, if
+/// they were converted.
+#[must_use]
+pub fn to_utf8(bytes: &[u8]) -> (Option<&'static str>, Cow<'_, [u8]>) {
+    if let Some((encoding, bom_len)) = Encoding::for_bom(bytes)
+        && encoding != UTF_8
+    {
+        return decode(encoding, &bytes[bom_len..]);
+    }
+
+    if bytes.is_ascii() || memchr::memchr(0, bytes).is_some() || str::from_utf8(bytes).is_ok() {
+        return (None, Cow::Borrowed(bytes));
+    }
+
+    let mut detector = EncodingDetector::new();
+    detector.feed(bytes, true);
+    match detector.guess(None, true) {
+        encoding if encoding == UTF_8 => (None, Cow::Borrowed(bytes)),
+        encoding => decode(encoding, bytes),
+    }
+}
+
+/// Decodes contents without a byte order mark into UTF-8.
+fn decode(encoding: &'static Encoding, bytes: &[u8]) -> (Option<&'static str>, Cow<'static, [u8]>) {
+    let text = encoding.decode_without_bom_handling(bytes).0;
+    (
+        Some(encoding.name()),
+        Cow::Owned(text.into_owned().into_bytes()),
+    )
+}
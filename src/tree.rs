@@ -0,0 +1,137 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use crate::color::{CYAN, paint};
+use code_line_count::report::{FileCount, Report};
+use std::{
+    collections::BTreeMap,
+    ffi::OsString,
+    fmt::Write,
+    path::{Component, PathBuf},
+};
+
+/// A directory or file of the tree, with the lines of the files in or below
+/// it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Node {
+    /// Whether the node is a directory.
+    dir: bool,
+    /// Total lines in or below the node, saturating on overflow.
+    lines: u64,
+    /// Nodes below, sorted by name.
+    children: BTreeMap<OsString, Self>,
+}
+
+impl Node {
+    /// Creates an empty node.
+    const fn new(dir: bool) -> Self {
+        Self {
+            dir,
+            lines: 0,
+            children: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a file's lines to the node.
+    const fn add(&mut self, lines: u64) {
+        self.lines = self.lines.saturating_add(lines);
+    }
+}
+
+/// Appends one node, after the tree drawing in `prefix`.
+fn write_node(out: &mut String, label: &str, node: &Node, prefix: &str, width: usize, color: bool) {
+    let label = if node.dir {
+        let slash = if label.ends_with('/') { "" } else { "/" };
+        paint(format!("{label}{slash}"), CYAN, color)
+    } else {
+        label.to_string()
+    };
+    let _ = writeln!(out, "{:>width$}  {prefix}{label}", node.lines);
+}
+
+/// Appends the nodes below `node`, each line starting with `indent`.
+fn write_children(out: &mut String, node: &Node, indent: &str, width: usize, color: bool) {
+    let last = node.children.len().saturating_sub(1);
+    for (idx, (name, child)) in node.children.iter().enumerate() {
+        let (branch, nested) = if idx == last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        let label = name.to_string_lossy();
+        write_node(
+            out,
+            &label,
+            child,
+            &format!("{indent}{branch}"),
+            width,
+            color,
+        );
+        write_children(out, child, &format!("{indent}{nested}"), width, color);
+    }
+}
+
+/// Renders the directories and files under each root as a tree, like
+/// `tree`, with the total lines of each.
+///
+/// Roots keep their order; the nodes below them are sorted by name. Only
+/// counted files and the directories containing them are shown, down to
+/// `depth` levels below each root; deeper files count towards their
+/// ancestor at that depth. With `color`, directories are cyan.
+#[must_use]
+pub fn render_tree(report: &Report, roots: &[PathBuf], depth: usize, color: bool) -> String {
+    let mut trees: Vec<(&PathBuf, Node)> =
+        roots.iter().map(|root| (root, Node::new(true))).collect();
+
+    for FileCount { path, lines, .. } in &report.files {
+        let Some((root, node)) = trees.iter_mut().find(|(root, _)| path.starts_with(root)) else {
+            continue;
+        };
+        node.add(*lines);
+        if path == *root {
+            node.dir = false;
+            continue;
+        }
+
+        let names: Vec<OsString> = path
+            .strip_prefix(root)
+            .unwrap_or(path)
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(name) => Some(name.to_os_string()),
+                _ => None,
+            })
+            .collect();
+        let mut node = node;
+        for (level, name) in names.iter().enumerate().take(depth) {
+            node = node
+                .children
+                .entry(name.clone())
+                .or_insert_with(|| Node::new(level + 1 < names.len()));
+            node.add(*lines);
+        }
+    }
+
+    let width = trees
+        .iter()
+        .map(|(_, node)| node.lines.to_string().len())
+        .max()
+        .unwrap_or(0);
+
+    let mut out = String::new();
+    for (root, node) in &trees {
+        write_node(
+            &mut out,
+            &root.display().to_string(),
+            node,
+            "",
+            width,
+            color,
+        );
+        write_children(&mut out, node, "", width, color);
+    }
+    out
+}
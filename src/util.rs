@@ -0,0 +1,92 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use crate::FinalLine;
+use memchr::memchr_iter;
+use regex::bytes::Regex;
+
+/// Pattern collapsing runs of blank lines, for [`count_buffer`].
+pub const BLANK_LINES_PATTERN: &str = r"\n\s+";
+
+/// Counts the lines of a file's contents.
+///
+/// `re` must be [`BLANK_LINES_PATTERN`] compiled once and reused across
+/// calls. Whitespace-only lines, including leading ones, are skipped, so a
+/// file of nothing but whitespace has no lines at all. The exception is
+/// [`FinalLine::WcCompat`], which counts newline characters like `wc -l`.
+/// A leading UTF-8 BOM must be stripped beforehand, otherwise it makes a
+/// blank first line look like code.
+///
+/// # Example
+///
+/// ```
+/// use code_line_count::{
+///     FinalLine,
+///     util::{BLANK_LINES_PATTERN, count_buffer},
+/// };
+/// use regex::bytes::Regex;
+///
+/// let re = Regex::new(BLANK_LINES_PATTERN)?;
+/// assert_eq!(count_buffer(b"a\n\n  \nb", &re, FinalLine::Count), 2);
+/// assert_eq!(count_buffer(b"a\n\n  \nb", &re, FinalLine::Ignore), 1);
+/// # Ok::<(), regex::Error>(())
+/// ```
+#[must_use]
+pub fn count_buffer(bytes: &[u8], re: &Regex, final_line: FinalLine) -> usize {
+    if final_line == FinalLine::WcCompat {
+        return memchr_iter(b'\n', bytes).count();
+    }
+
+    // The regex only collapses blank lines that follow a newline, so
+    // leading ones are trimmed here, with the same Unicode whitespace.
+    let valid = match std::str::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(err) => std::str::from_utf8(&bytes[..err.valid_up_to()]).unwrap_or_default(),
+    };
+    let bytes = &bytes[valid.len() - valid.trim_start().len()..];
+    if bytes.is_empty() {
+        return 0;
+    }
+
+    let lines = memchr_iter(b'\n', &re.replace_all(bytes, b"\n")).count();
+
+    if final_line == FinalLine::Count {
+        lines.saturating_add(usize::from(!bytes.ends_with(b"\n")))
+    } else {
+        lines
+    }
+}
+
+/// Extension trait for partitioning an iterator into N buckets.
+pub trait PartitionN: Iterator {
+    /// Partitions items into `N` collections based on a classifier function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use code_line_count::util::PartitionN;
+    ///
+    /// let [evens, odds]: [Vec<_>; 2] = (0..10).partition_n(|n| n % 2);
+    /// assert_eq!(evens, [0, 2, 4, 6, 8]);
+    /// assert_eq!(odds, [1, 3, 5, 7, 9]);
+    /// ```
+    fn partition_n<B, const N: usize>(self, f: impl FnMut(&Self::Item) -> usize) -> [B; N]
+    where
+        B: Default + Extend<Self::Item>;
+}
+
+impl<I: Iterator> PartitionN for I {
+    fn partition_n<B, const N: usize>(self, mut f: impl FnMut(&Self::Item) -> usize) -> [B; N]
+    where
+        B: Default + Extend<Self::Item>,
+    {
+        let mut result: [B; N] = std::array::from_fn(|_| B::default());
+        for item in self {
+            result[f(&item) % N].extend(std::iter::once(item));
+        }
+        result
+    }
+}
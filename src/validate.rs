@@ -0,0 +1,82 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use code_line_count::{
+    json::Value,
+    report::{Report, saturating_sum},
+};
+use std::ffi::OsStr;
+
+/// The tool that produced an external report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tool {
+    /// `tokei --output json`.
+    Tokei,
+    /// `cloc --json`.
+    Cloc,
+}
+
+/// Counts of one language in an external report.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExternalCount {
+    /// Language name, as the tool spells it.
+    pub language: String,
+    /// Lines of code, excluding comments and blanks.
+    pub code: u64,
+    /// Comment lines.
+    pub comments: u64,
+}
+
+/// Reads per-language counts from a parsed tokei or cloc JSON report.
+///
+/// cloc reports are recognized by their `header` entry; anything else is
+/// read as tokei. Summary entries (`Total`, `SUM`) are skipped. Returns
+/// `None` if the document is not an object.
+#[must_use]
+pub fn parse_external(report: &Value) -> Option<(Tool, Vec<ExternalCount>)> {
+    let entries = report.as_object()?;
+    let tool = if report.get("header").is_some() {
+        Tool::Cloc
+    } else {
+        Tool::Tokei
+    };
+    let (comments_key, skipped) = match tool {
+        Tool::Tokei => ("comments", ["Total", "Total"]),
+        Tool::Cloc => ("comment", ["header", "SUM"]),
+    };
+
+    let counts = entries
+        .iter()
+        .filter(|(language, _)| !skipped.contains(&language.as_str()))
+        .filter_map(|(language, counts)| {
+            Some(ExternalCount {
+                language: language.clone(),
+                code: counts.get("code")?.as_u64()?,
+                comments: counts
+                    .get(comments_key)
+                    .and_then(Value::as_u64)
+                    .unwrap_or(0),
+            })
+        })
+        .collect();
+
+    Some((tool, counts))
+}
+
+/// Sums the lines of counted files with one of the given extensions.
+#[must_use]
+pub fn lines_with_exts(report: &Report, exts: &[&str]) -> u64 {
+    saturating_sum(
+        report
+            .files
+            .iter()
+            .filter(|file| {
+                let ext = file.extension();
+                exts.iter().any(|e| OsStr::new(e) == ext)
+            })
+            .map(|file| file.lines),
+    )
+}
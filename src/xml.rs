@@ -0,0 +1,155 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use code_line_count::json::Value;
+
+/// Objects of the structured report keyed by group name rather than by
+/// field, written as one element per group.
+pub const REPORT_MAPS: &[&str] = &["languages", "extensions"];
+
+/// Serializes a value as an XML document with a `root` element, keeping
+/// the order of object keys.
+///
+/// Scalar fields of an object become attributes of its element, leaving
+/// out nulls, and other fields child elements. Arrays become an element
+/// per item, named after the array without its plural `s` (`files` holds
+/// `file` elements). Objects named in `maps` are keyed by data instead of
+/// field names, so each entry likewise becomes an element named after
+/// the object, with the key as its `name` attribute.
+#[must_use]
+pub fn to_xml(value: &Value, root: &str, maps: &[&str]) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    write_element(&mut out, root, None, value, 0, maps);
+    out
+}
+
+/// Appends an element for `value`, indented `depth` levels deep, with a
+/// `name` attribute if given.
+fn write_element(
+    out: &mut String,
+    tag: &str,
+    name: Option<&str>,
+    value: &Value,
+    depth: usize,
+    maps: &[&str],
+) {
+    let indent = "  ".repeat(depth);
+    out.push_str(&indent);
+    out.push('<');
+    out.push_str(tag);
+    if let Some(name) = name {
+        push_attribute(out, "name", name);
+    }
+
+    let children: Vec<(&str, &Value)> = match value {
+        Value::Null => vec![],
+        Value::Bool(_) | Value::Number(_) | Value::String(_) => {
+            out.push('>');
+            out.push_str(&escape(&scalar(value).unwrap_or_default()));
+            out.push_str("</");
+            out.push_str(tag);
+            out.push_str(">\n");
+            return;
+        }
+        Value::Array(items) => {
+            let item_tag = singular(tag);
+            items.iter().map(|item| (item_tag, item)).collect()
+        }
+        Value::Object(entries) => {
+            for (key, value) in entries {
+                if let Some(text) = scalar(value) {
+                    push_attribute(out, key, &text);
+                }
+            }
+            entries
+                .iter()
+                .filter(|(_, value)| matches!(value, Value::Array(_) | Value::Object(_)))
+                .map(|(key, value)| (key.as_str(), value))
+                .collect()
+        }
+    };
+
+    if children.is_empty() {
+        out.push_str("/>\n");
+        return;
+    }
+    out.push_str(">\n");
+
+    for (key, child) in children {
+        match child {
+            Value::Object(entries) if maps.contains(&key) => {
+                let entry_tag = singular(key);
+                out.push_str(&indent);
+                out.push_str("  <");
+                out.push_str(key);
+                if entries.is_empty() {
+                    out.push_str("/>\n");
+                    continue;
+                }
+                out.push_str(">\n");
+                for (name, value) in entries {
+                    write_element(out, entry_tag, Some(name), value, depth + 2, maps);
+                }
+                out.push_str(&indent);
+                out.push_str("  </");
+                out.push_str(key);
+                out.push_str(">\n");
+            }
+            child => write_element(out, key, None, child, depth + 1, maps),
+        }
+    }
+
+    out.push_str(&indent);
+    out.push_str("</");
+    out.push_str(tag);
+    out.push_str(">\n");
+}
+
+/// Gets the text of a scalar, or `None` for nulls, arrays, and objects.
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Number(n) => Some(n.clone()),
+        Value::String(s) => Some(s.clone()),
+        Value::Null | Value::Array(_) | Value::Object(_) => None,
+    }
+}
+
+/// Appends ` key="value"` to an open start tag.
+fn push_attribute(out: &mut String, key: &str, value: &str) {
+    out.push(' ');
+    out.push_str(key);
+    out.push_str("=\"");
+    out.push_str(&escape(value));
+    out.push('"');
+}
+
+/// Gets the element name of an item of the array or map named `plural`.
+fn singular(plural: &str) -> &str {
+    plural.strip_suffix('s').unwrap_or("item")
+}
+
+/// Escapes text for an attribute value or element content.
+///
+/// Control characters XML cannot represent are replaced with U+FFFD.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\t' => out.push_str("&#x9;"),
+            '\n' => out.push_str("&#xa;"),
+            '\r' => out.push_str("&#xd;"),
+            c if c < ' ' => out.push('\u{fffd}'),
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,108 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2026 c1ph3rC4t
+
+use code_line_count::json::{Value, escape};
+
+/// Plain scalars YAML would read as something other than a string.
+const RESERVED: &[&str] = &[
+    "~", "null", "true", "false", "yes", "no", "on", "off", "y", "n", ".inf", ".nan",
+];
+
+/// Serializes a value as a block-style YAML document, keeping the order of
+/// object keys.
+#[must_use]
+pub fn to_yaml(value: &Value) -> String {
+    let mut out = String::new();
+    if is_block(value) {
+        write_block(&mut out, value, 0, false);
+    } else {
+        out.push_str(&inline(value));
+        out.push('\n');
+    }
+    out
+}
+
+/// Checks whether a value is written over several lines, as a non-empty
+/// array or object.
+const fn is_block(value: &Value) -> bool {
+    match value {
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(entries) => !entries.is_empty(),
+        _ => false,
+    }
+}
+
+/// Appends a non-empty array or object, indented `depth` levels deep.
+///
+/// With `continued`, the first line is appended to the current one, after
+/// a `- ` the caller wrote.
+fn write_block(out: &mut String, value: &Value, depth: usize, mut continued: bool) {
+    let mut indent = |out: &mut String| {
+        if !continued {
+            out.extend(std::iter::repeat_n("  ", depth));
+        }
+        continued = false;
+    };
+
+    match value {
+        Value::Array(items) => {
+            for item in items {
+                indent(out);
+                out.push_str("- ");
+                write_nested(out, item, depth + 1, true);
+            }
+        }
+        Value::Object(entries) => {
+            for (key, value) in entries {
+                indent(out);
+                out.push_str(&scalar(key));
+                out.push(':');
+                if is_block(value) {
+                    out.push('\n');
+                    write_nested(out, value, depth + 1, false);
+                } else {
+                    out.push(' ');
+                    write_nested(out, value, depth + 1, true);
+                }
+            }
+        }
+        value => unreachable!("not a block: {value}"),
+    }
+}
+
+/// Appends the value of an array item or object entry, ending the line.
+fn write_nested(out: &mut String, value: &Value, depth: usize, continued: bool) {
+    if is_block(value) {
+        write_block(out, value, depth, continued);
+    } else {
+        out.push_str(&inline(value));
+        out.push('\n');
+    }
+}
+
+/// Formats a value that fits on one line.
+fn inline(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::String(s) => scalar(s),
+        Value::Array(_) => "[]".to_string(),
+        Value::Object(_) => "{}".to_string(),
+        value => value.to_string(),
+    }
+}
+
+/// Formats a string as a plain scalar if it cannot be read back as anything
+/// else, such as a number or a boolean, or as a double-quoted one otherwise.
+fn scalar(s: &str) -> String {
+    let plain = !s.starts_with(|c: char| c == '-' || c.is_ascii_digit())
+        && !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '/' | '_' | '-'))
+        && !RESERVED.contains(&s.to_ascii_lowercase().as_str());
+
+    // JSON strings are valid double-quoted YAML scalars.
+    if plain { s.to_string() } else { escape(s) }
+}